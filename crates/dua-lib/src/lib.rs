@@ -6,8 +6,10 @@
 //! first when their reads finish sooner. Sibling order is unspecified in both modes.
 //!
 //! The `descend` predicate controls which directories are traversed; rejected directories are
-//! still yielded (but not traversed).
-//! Symbolic links are reported but never followed, and filesystem errors are
+//! still yielded (but not traversed). Directories can also be [summarized](Visit::Summarize),
+//! which reads everything below them on the same workers but yields only their combined size.
+//! Symbolic links are reported but not followed unless [`Links::Follow`] is used, in which case
+//! every directory is read at most once to break loops. Filesystem errors are
 //! returned as iterator items. Dropping the iterator stops and joins its workers.
 //!
 //! # Scheduling
@@ -28,11 +30,11 @@ use crossbeam::{
     sync::{Parker, Unparker},
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io,
     path::{Path, PathBuf},
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering},
         mpsc::{Receiver, SyncSender, sync_channel},
     },
@@ -53,8 +55,8 @@ mod windows;
 pub use windows::{Entry, FileType, Metadata};
 
 /// Decides whether to traverse an entry's children for a given root index.
-/// Returning `false` prunes descendants but still emits the entry itself.
-type Descend = dyn Fn(usize, &Entry) -> bool + Send + Sync;
+/// Returning [`Visit::Prune`] prunes descendants but still emits the entry itself.
+type Descend = dyn Fn(usize, &Entry) -> Visit + Send + Sync;
 /// Entries obtained from one directory read.
 /// The outer error means `fs::read_dir` could not open the directory; inner errors come from
 /// reading or converting individual directory entries.
@@ -72,6 +74,152 @@ pub enum Order {
     ParentFirst,
 }
 
+/// Tells what to do with a directory, as decided by the `descend` predicate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Visit {
+    /// Read the directory and yield its entries.
+    Descend,
+    /// Don't read the directory.
+    Prune,
+    /// Read the directory and all directories below it that aren't pruned, but instead of
+    /// yielding their entries, yield the directory once with the [`Summary`] of its contents.
+    ///
+    /// Below such a directory, the predicate is asked about files as well, to learn whether
+    /// they are [excluded](Visit::Exclude) from the summary.
+    Summarize,
+    /// Like [`Visit::Prune`], but also leave the entry out of the [`Summary`] of a directory
+    /// visited with [`Visit::Summarize`].
+    Exclude,
+}
+
+impl From<bool> for Visit {
+    fn from(descend: bool) -> Self {
+        if descend {
+            Visit::Descend
+        } else {
+            Visit::Prune
+        }
+    }
+}
+
+/// The combined contents of a directory visited with [`Visit::Summarize`].
+#[derive(Default)]
+pub struct Summary {
+    /// The amount of entries below the directory.
+    pub entries: u64,
+    /// The sum of the logical sizes of all entries, without `hard_links`.
+    pub apparent_size: u128,
+    /// The sum of the sizes all entries occupy on disk, without `hard_links`.
+    pub disk_size: u128,
+    /// The amount of entries or directories whose metadata couldn't be read.
+    pub io_errors: u64,
+    /// Files with more than one hard link, or all entries if links are followed, whose sizes are
+    /// left to the caller to decide how often to count them. Each file is listed at most as often
+    /// as it has hard links within the whole walk.
+    pub hard_links: Vec<HardLink>,
+}
+
+/// A file in a [`Summary`] that may be reached through more than one path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HardLink {
+    /// The identity of the file that is the same for all paths leading to it, which is its
+    /// device and inode on Unix.
+    pub id: (u64, u64),
+    /// The amount of hard links to the file, which is `1` for directories.
+    pub links: u64,
+    /// The logical size of the file.
+    pub apparent_size: u64,
+    /// The size the file occupies on disk.
+    pub disk_size: u64,
+}
+
+impl Summary {
+    fn add(&mut self, entry: &Entry, descender: &Descender) {
+        self.entries += 1;
+        let Ok(metadata) = &entry.metadata else {
+            self.io_errors += 1;
+            return;
+        };
+        let (apparent_size, disk_size) = (metadata.len(), disk_size(entry, metadata));
+        let links = hard_link_count(entry, metadata);
+        let id = match (descender.links, entry_id(entry)) {
+            // Followed links can lead to any entry more than once, whatever its link count.
+            (Links::Follow, Some(id)) => id,
+            (Links::Report, Some(id)) if links > 1 => id,
+            _ => {
+                self.apparent_size += u128::from(apparent_size);
+                self.disk_size += u128::from(disk_size);
+                return;
+            }
+        };
+        if descender.summarize_link(id, links) {
+            self.hard_links.push(HardLink {
+                id,
+                links,
+                apparent_size,
+                disk_size,
+            });
+        }
+    }
+
+    fn merge(&mut self, other: Summary) {
+        self.entries += other.entries;
+        self.apparent_size += other.apparent_size;
+        self.disk_size += other.disk_size;
+        self.io_errors += other.io_errors;
+        self.hard_links.extend(other.hard_links);
+    }
+}
+
+#[cfg(unix)]
+fn hard_link_count(entry: &Entry, metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    // The link count of directories is made up of their subdirectories.
+    if entry.file_type.is_dir() {
+        1
+    } else {
+        metadata.nlink()
+    }
+}
+
+#[cfg(not(unix))]
+fn hard_link_count(_entry: &Entry, _metadata: &Metadata) -> u64 {
+    1
+}
+
+#[cfg(unix)]
+fn disk_size(_entry: &Entry, metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * 512
+}
+
+#[cfg(windows)]
+fn disk_size(entry: &Entry, metadata: &Metadata) -> u64 {
+    if entry.file_type.is_dir() {
+        0
+    } else {
+        metadata.allocated_size()
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn disk_size(_entry: &Entry, metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Controls whether symbolic links are followed.
+#[derive(Clone, Copy, Default)]
+pub enum Links {
+    /// Yield symbolic links as entries of their own, without following them.
+    #[default]
+    Report,
+    /// Yield symbolic links with the type and metadata of their target, and traverse linked
+    /// directories. Each directory is read only the first time it is reached, identified by
+    /// device and inode, so loops terminate and no directory is reported twice.
+    Follow,
+}
+
 /// A filesystem entry produced by [`walk`].
 #[cfg(not(windows))]
 pub struct Entry {
@@ -79,12 +227,14 @@ pub struct Entry {
     pub depth: usize,
     /// File name relative to `parent_path`.
     pub file_name: OsString,
-    /// Filesystem entry type without following symbolic links.
+    /// Filesystem entry type, which is the type of the link target if links are followed.
     pub file_type: FileType,
     /// Entry metadata, or the error encountered while reading it.
     pub metadata: io::Result<Metadata>,
     /// Path containing this entry.
     pub parent_path: Arc<Path>,
+    /// The combined contents of this directory if it was visited with [`Visit::Summarize`].
+    pub summary: Option<Summary>,
 }
 
 enum Job {
@@ -105,6 +255,23 @@ enum Job {
         entry_depth: usize,
         entries: Vec<fs::DirEntry>,
     },
+    /// Read a directory below one that is summarized, adding its entries to `summary`.
+    Summarize {
+        root_idx: usize,
+        path: Arc<Path>,
+        /// Depth to be assigned to entries read from `path`; always at least `1`.
+        entry_depth: usize,
+        summary: Arc<PendingSummary>,
+    },
+}
+
+/// A directory that is yielded once all directories below it were added to its summary.
+struct PendingSummary {
+    /// The directory to yield, taken by the job that completes the summary.
+    entry: Mutex<Option<Entry>>,
+    summary: Mutex<Summary>,
+    /// Number of queued or running jobs for this summary.
+    jobs: AtomicUsize,
 }
 
 impl Job {
@@ -114,6 +281,7 @@ impl Job {
             Job::ReadDir { root_idx, .. } => *root_idx,
             #[cfg(not(windows))]
             Job::StatCompletion { root_idx, .. } => *root_idx,
+            Job::Summarize { root_idx, .. } => *root_idx,
         }
     }
 }
@@ -145,12 +313,156 @@ pub enum RootEvent {
     Finished,
 }
 
+/// Decides which directories are read, based on the `descend` predicate and on whether
+/// a directory was already reached through a symbolic link.
+struct Descender {
+    descend: Arc<Descend>,
+    links: Links,
+    /// Identities of all directories scheduled for reading, only used with [`Links::Follow`].
+    visited: Mutex<HashSet<(u64, u64)>>,
+    /// How often each file was added to a summary as [`HardLink`].
+    summarized_links: Mutex<HashMap<(u64, u64), u64>>,
+}
+
+impl Descender {
+    fn new(links: Links, descend: Arc<Descend>) -> Self {
+        Self {
+            descend,
+            links,
+            visited: Mutex::default(),
+            summarized_links: Mutex::default(),
+        }
+    }
+
+    /// Return `entry` as it should be yielded, which may mean to follow it if it is a link.
+    fn resolve(&self, entry: Entry) -> Entry {
+        match self.links {
+            Links::Report => entry,
+            Links::Follow => entry.follow(),
+        }
+    }
+
+    /// Return how to visit `entry`, which is [`Visit::Prune`] for everything but directories.
+    fn visit(&self, root_idx: usize, entry: &Entry) -> Visit {
+        if !entry.file_type.is_dir() {
+            return Visit::Prune;
+        }
+        self.first_visit((self.descend)(root_idx, entry), entry)
+    }
+
+    /// Like [`visit()`](Self::visit()), but for entries below a summarized directory, which are
+    /// all passed to the `descend` predicate. Return `None` if `entry` is excluded from the summary.
+    fn visit_summarized(&self, root_idx: usize, entry: &Entry) -> Option<Visit> {
+        match (self.descend)(root_idx, entry) {
+            Visit::Exclude => None,
+            _ if !entry.file_type.is_dir() => Some(Visit::Prune),
+            visit => Some(self.first_visit(visit, entry)),
+        }
+    }
+
+    /// Return `visit`, unless the directory `entry` was already reached through another path.
+    fn first_visit(&self, visit: Visit, entry: &Entry) -> Visit {
+        let first_visit = matches!(visit, Visit::Prune | Visit::Exclude)
+            || match self.links {
+                Links::Report => true,
+                // Directories without identity can't be part of a loop we could detect.
+                Links::Follow => entry_id(entry).is_none_or(|id| {
+                    self.visited
+                        .lock()
+                        .expect("no panic while holding the lock")
+                        .insert(id)
+                }),
+            };
+        if first_visit { visit } else { Visit::Prune }
+    }
+
+    /// Return `true` if the file with `id` should be added to a summary as [`HardLink`], which
+    /// is the case until it was added as often as it has `links`.
+    fn summarize_link(&self, id: (u64, u64), links: u64) -> bool {
+        // Without following links, each hard link is reached exactly once.
+        if matches!(self.links, Links::Report) {
+            return true;
+        }
+        let mut summarized_links = self
+            .summarized_links
+            .lock()
+            .expect("no panic while holding the lock");
+        let count = summarized_links.entry(id).or_insert(0);
+        *count += 1;
+        *count <= links
+    }
+
+    /// Resolve `entry` and queue the job needed to visit it into `jobs`.
+    /// Return it if it should be yielded right away, or `None` if it will be yielded with
+    /// its summary.
+    fn plan(
+        &self,
+        root_idx: usize,
+        entry: io::Result<Entry>,
+        jobs: &mut Vec<Job>,
+    ) -> Option<io::Result<Entry>> {
+        let entry = match entry {
+            Ok(entry) => self.resolve(entry),
+            Err(err) => return Some(Err(err)),
+        };
+        match self.visit(root_idx, &entry) {
+            Visit::Prune | Visit::Exclude => {}
+            Visit::Descend => jobs.push(Job::ReadDir {
+                root_idx,
+                path: Arc::from(entry.path()),
+                entry_depth: entry.depth + 1,
+            }),
+            Visit::Summarize => {
+                jobs.push(Job::Summarize {
+                    root_idx,
+                    path: Arc::from(entry.path()),
+                    entry_depth: entry.depth + 1,
+                    summary: Arc::new(PendingSummary {
+                        entry: Mutex::new(Some(entry)),
+                        summary: Mutex::default(),
+                        jobs: AtomicUsize::new(1),
+                    }),
+                });
+                return None;
+            }
+        }
+        Some(Ok(entry))
+    }
+}
+
+/// Return the identity of `entry` that is the same for all paths leading to it.
+#[cfg(unix)]
+fn entry_id(entry: &Entry) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    entry
+        .metadata
+        .as_ref()
+        .ok()
+        .map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+/// Return the identity of `entry` that is the same for all paths leading to it.
+#[cfg(windows)]
+fn entry_id(entry: &Entry) -> Option<(u64, u64)> {
+    entry
+        .metadata
+        .as_ref()
+        .ok()
+        .and_then(|metadata| metadata.hard_link_id())
+}
+
+/// Return the identity of `entry` that is the same for all paths leading to it.
+#[cfg(not(any(unix, windows)))]
+fn entry_id(_entry: &Entry) -> Option<(u64, u64)> {
+    None
+}
+
 struct PoolShared {
     /// Global queue that makes the initial root job available to whichever worker starts first.
     injector: Injector<Job>,
     stealers: Vec<Stealer<Job>>,
     stop: AtomicBool,
-    descend: Arc<Descend>,
+    descender: Descender,
     events: SyncSender<Event>,
     /// Number of roots with queued or running jobs.
     active_roots: AtomicUsize,
@@ -201,36 +513,41 @@ pub struct Walk {
 /// Walk `root` without following symlinks.
 /// Unlike `walk_roots`, this yields entries directly for a single root and hides
 /// completion events.
-pub fn walk(
+pub fn walk<V: Into<Visit>>(
     root: &Path,
     threads: usize,
     order: Order,
-    descend: impl Fn(&Entry) -> bool + Send + Sync + 'static,
+    descend: impl Fn(&Entry) -> V + Send + Sync + 'static,
 ) -> Walk {
-    let root = Entry::from_path(root);
-    let pool = match &root {
-        Ok(entry) if entry.file_type.is_dir() && descend(entry) => {
-            let path = Arc::from(entry.path());
-            let pool = start_pool(
-                threads.max(1),
-                HashMap::from([(0, AtomicUsize::new(0))]),
-                order,
-                Arc::new(move |_, entry| descend(entry)),
-            );
-            start_jobs(
-                &pool,
-                vec![Job::ReadDir {
-                    root_idx: 0,
-                    path,
-                    entry_depth: 1,
-                }],
-            );
-            Some(pool)
-        }
-        _ => None,
-    };
+    walk_with_links(root, threads, order, Links::Report, descend)
+}
+
+/// Like [`walk`], but follows symlinks with [`Links::Follow`].
+pub fn walk_with_links<V: Into<Visit>>(
+    root: &Path,
+    threads: usize,
+    order: Order,
+    links: Links,
+    descend: impl Fn(&Entry) -> V + Send + Sync + 'static,
+) -> Walk {
+    let descender = Descender::new(
+        links,
+        Arc::new(move |_, entry: &Entry| descend(entry).into()),
+    );
+    let mut jobs = Vec::new();
+    let root = descender.plan(0, Entry::from_path(root), &mut jobs);
+    let pool = (!jobs.is_empty()).then(|| {
+        let pool = start_pool(
+            threads.max(1),
+            HashMap::from([(0, AtomicUsize::new(0))]),
+            order,
+            descender,
+        );
+        start_jobs(&pool, jobs);
+        pool
+    });
     Walk {
-        next: vec![root],
+        next: root.into_iter().collect(),
         pool,
     }
 }
@@ -270,11 +587,27 @@ impl Iterator for Walk {
 /// # Panics
 ///
 /// Panics if two roots have the same index.
-pub fn walk_roots(
+pub fn walk_roots<V: Into<Visit>>(
+    roots: impl IntoIterator<Item = (usize, PathBuf)>,
+    threads: usize,
+    order: Order,
+    descend: impl Fn(usize, &Entry) -> V + Send + Sync + 'static,
+) -> RootWalk {
+    walk_roots_with_links(roots, threads, order, Links::Report, descend)
+}
+
+/// Like [`walk_roots`], but follows symlinks with [`Links::Follow`].
+/// Directories reachable from multiple roots are read only once when following links.
+///
+/// # Panics
+///
+/// Panics if two roots have the same index.
+pub fn walk_roots_with_links<V: Into<Visit>>(
     roots: impl IntoIterator<Item = (usize, PathBuf)>,
     threads: usize,
     order: Order,
-    descend: impl Fn(usize, &Entry) -> bool + Send + Sync + 'static,
+    links: Links,
+    descend: impl Fn(usize, &Entry) -> V + Send + Sync + 'static,
 ) -> RootWalk {
     let roots = roots.into_iter().collect::<Vec<_>>();
     let jobs_per_root = roots
@@ -286,12 +619,15 @@ pub fn walk_roots(
         roots.len(),
         "root indices must be unique"
     );
-    let descend = Arc::new(descend);
-    let (next, root_jobs) = begin_walks(roots, descend.as_ref());
+    let descender = Descender::new(
+        links,
+        Arc::new(move |root_idx, entry: &Entry| descend(root_idx, entry).into()),
+    );
+    let (next, root_jobs) = begin_walks(roots, &descender);
     let pool = if root_jobs.is_empty() {
         None
     } else {
-        let pool = start_pool(threads.max(1), jobs_per_root, order, descend);
+        let pool = start_pool(threads.max(1), jobs_per_root, order, descender);
         start_jobs(&pool, root_jobs);
         Some(pool)
     };
@@ -382,9 +718,22 @@ impl Entry {
             file_type: metadata.file_type(),
             metadata: Ok(metadata),
             parent_path: Arc::from(path.parent().unwrap_or(Path::new(""))),
+            summary: None,
         })
     }
 
+    /// Replace the type and metadata of a symbolic link with those of its target, leaving the
+    /// entry unchanged if the target can't be obtained.
+    fn follow(mut self) -> Self {
+        if self.file_type.is_symlink()
+            && let Ok(metadata) = fs::metadata(self.path())
+        {
+            self.file_type = metadata.file_type();
+            self.metadata = Ok(metadata);
+        }
+        self
+    }
+
     fn from_dir_entry(
         depth: usize,
         parent_path: Arc<Path>,
@@ -396,6 +745,7 @@ impl Entry {
             file_type: entry.file_type()?,
             metadata: entry.metadata(),
             parent_path,
+            summary: None,
         })
     }
 }
@@ -404,7 +754,7 @@ fn start_pool(
     threads: usize,
     jobs_per_root: HashMap<usize, AtomicUsize>,
     order: Order,
-    descend: Arc<Descend>,
+    descender: Descender,
 ) -> Pool {
     let workers: Vec<_> = (0..threads).map(|_| Worker::new_lifo()).collect();
     let parkers: Vec<_> = (0..threads).map(|_| Parker::new()).collect();
@@ -413,7 +763,7 @@ fn start_pool(
         injector: Injector::new(),
         stealers: workers.iter().map(Worker::stealer).collect(),
         stop: AtomicBool::new(false),
-        descend,
+        descender,
         events: event_tx,
         active_roots: AtomicUsize::new(0),
         jobs_per_root,
@@ -449,26 +799,17 @@ fn start_pool(
 /// Returns events in stack order for [`RootWalk::next`] to pop, plus jobs requiring a worker pool.
 fn begin_walks(
     roots: impl IntoIterator<Item = (usize, PathBuf)>,
-    descend: &Descend,
+    descender: &Descender,
 ) -> (Vec<(usize, RootEvent)>, Vec<Job>) {
     let mut next = Vec::new();
     let mut jobs = Vec::new();
     for (root_idx, path) in roots {
-        let entry = Entry::from_path(&path);
-        let has_job = if let Ok(entry) = &entry
-            && entry.file_type.is_dir()
-            && descend(root_idx, entry)
-        {
-            jobs.push(Job::ReadDir {
-                root_idx,
-                path: Arc::from(entry.path()),
-                entry_depth: 1,
-            });
-            true
-        } else {
-            false
-        };
-        next.push((root_idx, RootEvent::Entry(entry)));
+        let num_jobs = jobs.len();
+        let entry = descender.plan(root_idx, Entry::from_path(&path), &mut jobs);
+        let has_job = jobs.len() > num_jobs;
+        if let Some(entry) = entry {
+            next.push((root_idx, RootEvent::Entry(entry)));
+        }
         if !has_job {
             next.push((root_idx, RootEvent::Finished));
         }
@@ -491,6 +832,7 @@ fn start_jobs(pool: &Pool, root_jobs: Vec<Job>) {
             Job::ReadDir { entry_depth, .. } => *entry_depth,
             #[cfg(not(windows))]
             Job::StatCompletion { entry_depth, .. } => *entry_depth,
+            Job::Summarize { entry_depth, .. } => *entry_depth,
         } == 1),
         "the first jobs should be root jobs, so active_root counts match"
     );
@@ -600,7 +942,106 @@ fn run_job(job: Job, worker: &Worker<Job>, shared: &PoolShared) {
             entry_depth,
             entries,
         } => stat_entries_completion(root, path, entry_depth, entries, worker, shared),
+        Job::Summarize {
+            root_idx: root,
+            path,
+            entry_depth,
+            summary,
+        } => summarize_dir(root, path, entry_depth, summary, worker, shared),
+    }
+}
+
+/// Add all entries of the directory at `path` to `pending`, and schedule reading its
+/// subdirectories for the same summary. The last job of a summary yields its directory.
+fn summarize_dir(
+    root_idx: usize,
+    path: Arc<Path>,
+    depth: usize,
+    pending: Arc<PendingSummary>,
+    worker: &Worker<Job>,
+    shared: &PoolShared,
+) {
+    let mut summary = Summary::default();
+    let mut jobs = Vec::new();
+    match read_entries(path, depth) {
+        Ok(entries) => {
+            for entry in entries {
+                match entry.map(|entry| shared.descender.resolve(entry)) {
+                    Ok(entry) => {
+                        let Some(visit) = shared.descender.visit_summarized(root_idx, &entry)
+                        else {
+                            continue;
+                        };
+                        summary.add(&entry, &shared.descender);
+                        if !matches!(visit, Visit::Prune | Visit::Exclude) {
+                            jobs.push(Job::Summarize {
+                                root_idx,
+                                path: Arc::from(entry.path()),
+                                entry_depth: depth + 1,
+                                summary: Arc::clone(&pending),
+                            });
+                        }
+                    }
+                    Err(_) => summary.io_errors += 1,
+                }
+            }
+        }
+        Err(_) => summary.io_errors += 1,
+    }
+    pending.jobs.fetch_add(jobs.len(), AtomicOrdering::Relaxed);
+    add_pending(root_idx, jobs.len(), shared);
+    schedule_jobs(jobs, worker, shared);
+
+    pending
+        .summary
+        .lock()
+        .expect("no panic while holding the lock")
+        .merge(summary);
+    if pending.jobs.fetch_sub(1, AtomicOrdering::AcqRel) == 1 {
+        let mut entry = pending
+            .entry
+            .lock()
+            .expect("no panic while holding the lock")
+            .take()
+            .expect("only the last job yields the entry");
+        entry.summary = Some(std::mem::take(
+            &mut *pending
+                .summary
+                .lock()
+                .expect("no panic while holding the lock"),
+        ));
+        if shared
+            .events
+            .send(Event::Batch {
+                root_idx,
+                batch: Ok(vec![Ok(entry)]),
+            })
+            .is_err()
+        {
+            shared.stop.store(true, AtomicOrdering::Relaxed);
+        }
     }
+    finish_pending(root_idx, shared);
+}
+
+/// Open the directory at `path` to read its entries, which are assigned `depth`.
+#[cfg(not(windows))]
+fn read_entries(
+    path: Arc<Path>,
+    depth: usize,
+) -> io::Result<impl Iterator<Item = io::Result<Entry>>> {
+    Ok(fs::read_dir(&path)?.map(move |entry| {
+        entry.and_then(|entry| Entry::from_dir_entry(depth, Arc::clone(&path), entry))
+    }))
+}
+
+/// Open the directory at `path` to read its entries, which are assigned `depth`.
+#[cfg(windows)]
+fn read_entries(
+    path: Arc<Path>,
+    depth: usize,
+) -> io::Result<impl Iterator<Item = io::Result<Entry>>> {
+    windows::ReadDir::open(path, depth)
 }
 
 /// Read a directory for completion-order traversal.
@@ -717,16 +1158,9 @@ fn read_dir_completion(
     let mut entries = Vec::with_capacity(ENTRY_CHUNK_SIZE);
     let mut jobs = Vec::new();
     for entry in dir_entries {
-        if let Ok(entry) = &entry
-            && entry.file_type.is_dir()
-            && (shared.descend)(root_idx, entry)
-        {
-            jobs.push(Job::ReadDir {
-                root_idx,
-                path: Arc::from(entry.path()),
-                entry_depth: depth + 1,
-            });
-        }
+        let Some(entry) = shared.descender.plan(root_idx, entry, &mut jobs) else {
+            continue;
+        };
         entries.push(entry);
         if entries.len() == ENTRY_CHUNK_SIZE
             && !publish_completion_batch(root_idx, &mut entries, &mut jobs, worker, shared)
@@ -735,7 +1169,7 @@ fn read_dir_completion(
             return;
         }
     }
-    if !entries.is_empty() {
+    if !entries.is_empty() || !jobs.is_empty() {
         publish_completion_batch(root_idx, &mut entries, &mut jobs, worker, shared);
     }
     finish_pending(root_idx, shared);
@@ -786,17 +1220,7 @@ fn read_dir_parent_first(
     };
     let mut jobs = Vec::new();
     let entries = dir_entries
-        .map(|entry| {
-            entry.inspect(|entry| {
-                if entry.file_type.is_dir() && (shared.descend)(root_idx, entry) {
-                    jobs.push(Job::ReadDir {
-                        root_idx,
-                        path: Arc::from(entry.path()),
-                        entry_depth: depth + 1,
-                    });
-                }
-            })
-        })
+        .filter_map(|entry| shared.descender.plan(root_idx, entry, &mut jobs))
         .collect();
     finish_directory(root_idx, Ok(entries), jobs, worker, shared);
 }
@@ -813,16 +1237,12 @@ fn stat_entries_completion(
     let mut jobs = Vec::new();
     let entries = entries
         .into_iter()
-        .map(|entry| {
-            Entry::from_dir_entry(depth, Arc::clone(&path), entry).inspect(|entry| {
-                if entry.file_type.is_dir() && (shared.descend)(root_idx, entry) {
-                    jobs.push(Job::ReadDir {
-                        root_idx,
-                        path: Arc::from(entry.path()),
-                        entry_depth: entry.depth + 1,
-                    });
-                }
-            })
+        .filter_map(|entry| {
+            shared.descender.plan(
+                root_idx,
+                Entry::from_dir_entry(depth, Arc::clone(&path), entry),
+                &mut jobs,
+            )
         })
         .collect();
     add_pending(root_idx, jobs.len(), shared);
@@ -878,18 +1298,12 @@ fn read_dir_inline(
     };
     let mut jobs = Vec::new();
     let entries = dir_entries
-        .map(|entry| {
-            entry
-                .and_then(|entry| Entry::from_dir_entry(depth, Arc::clone(&path), entry))
-                .inspect(|entry| {
-                    if entry.file_type.is_dir() && (shared.descend)(root_idx, entry) {
-                        jobs.push(Job::ReadDir {
-                            root_idx,
-                            path: Arc::from(entry.path()),
-                            entry_depth: depth + 1,
-                        });
-                    }
-                })
+        .filter_map(|entry| {
+            shared.descender.plan(
+                root_idx,
+                entry.and_then(|entry| Entry::from_dir_entry(depth, Arc::clone(&path), entry)),
+                &mut jobs,
+            )
         })
         .collect();
     finish_directory(root_idx, Ok(entries), jobs, worker, shared);
@@ -1010,6 +1424,45 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn following_links_reads_each_directory_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("a/b")).unwrap();
+        fs::write(dir.path().join("a/b/file"), b"x").unwrap();
+        std::os::unix::fs::symlink(dir.path().join("a"), dir.path().join("link")).unwrap();
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("a/b/loop")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("missing"), dir.path().join("dangling"))
+            .unwrap();
+
+        for (order, threads) in [(Order::ParentFirst, 1), (Order::Completion, 4)] {
+            let entries = walk_with_links(dir.path(), threads, order, Links::Follow, |_| true)
+                .map(Result::unwrap)
+                .collect::<Vec<_>>();
+            let count = |name: &str| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.file_name == name)
+                    .count()
+            };
+            assert_eq!(count("file"), 1, "the linked directory is read only once");
+            assert_eq!(count("loop"), 1, "the loop is yielded, but not followed");
+            assert!(
+                entries
+                    .iter()
+                    .filter(|entry| entry.file_name == "link" || entry.file_name == "loop")
+                    .all(|entry| entry.file_type.is_dir()),
+                "links are yielded with the type of their target"
+            );
+            assert!(
+                entries
+                    .iter()
+                    .any(|entry| entry.file_name == "dangling" && entry.file_type.is_symlink()),
+                "dangling links remain links"
+            );
+        }
+    }
+
     #[test]
     fn pruning_keeps_the_directory_and_missing_roots_are_errors() {
         let dir = tempfile::tempdir().unwrap();
@@ -1038,6 +1491,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn summarized_directories_are_yielded_once_with_their_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("deep/a/b")).unwrap();
+        fs::create_dir_all(dir.path().join("deep/skip")).unwrap();
+        fs::write(dir.path().join("deep/a/one"), b"1").unwrap();
+        fs::write(dir.path().join("deep/a/b/two"), b"22").unwrap();
+        fs::write(dir.path().join("deep/skip/ignored"), b"333").unwrap();
+        fs::write(dir.path().join("top"), b"4444").unwrap();
+
+        for (order, threads) in [(Order::ParentFirst, 1), (Order::Completion, 4)] {
+            let entries = walk(dir.path(), threads, order, |entry| {
+                if entry.file_name == "skip" {
+                    Visit::Prune
+                } else if entry.depth == 1 {
+                    Visit::Summarize
+                } else {
+                    Visit::Descend
+                }
+            })
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+
+            let mut names = entries
+                .iter()
+                .skip(1)
+                .map(|entry| entry.file_name.clone())
+                .collect::<Vec<_>>();
+            names.sort();
+            assert_eq!(
+                names,
+                ["deep", "top"],
+                "nothing below a summarized directory should be yielded"
+            );
+            let summary = entries
+                .iter()
+                .find(|entry| entry.file_name == "deep")
+                .and_then(|entry| entry.summary.as_ref())
+                .expect("the summarized directory carries its summary");
+            assert_eq!(
+                summary.entries, 5,
+                "a, b, one, two and the pruned skip directory are counted"
+            );
+            assert_eq!(summary.io_errors, 0);
+            assert!(
+                summary.apparent_size >= 3,
+                "file sizes below the directory are summed, except for pruned directories"
+            );
+            assert!(
+                entries
+                    .iter()
+                    .find(|entry| entry.file_name == "top")
+                    .is_some_and(|entry| entry.summary.is_none()),
+                "non-summarized entries don't have a summary"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn summaries_list_each_linked_file_at_most_once_per_hard_link() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("deep")).unwrap();
+        fs::write(dir.path().join("deep/file"), b"1").unwrap();
+        fs::write(dir.path().join("deep/linked"), b"22").unwrap();
+        fs::hard_link(dir.path().join("deep/linked"), dir.path().join("deep/hard")).unwrap();
+        for idx in 0..10 {
+            for name in ["file", "linked"] {
+                std::os::unix::fs::symlink(
+                    dir.path().join("deep").join(name),
+                    dir.path().join(format!("deep/{name}-{idx}")),
+                )
+                .unwrap();
+            }
+        }
+
+        for links in [Links::Report, Links::Follow] {
+            let entries = walk_with_links(dir.path(), 4, Order::Completion, links, |entry| {
+                if entry.depth == 1 {
+                    Visit::Summarize
+                } else {
+                    Visit::Descend
+                }
+            })
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+            let summary = entries
+                .iter()
+                .find(|entry| entry.file_name == "deep")
+                .and_then(|entry| entry.summary.as_ref())
+                .expect("the summarized directory carries its summary");
+            assert_eq!(summary.entries, 23);
+
+            let mut sizes = summary
+                .hard_links
+                .iter()
+                .map(|link| (link.links, link.apparent_size))
+                .collect::<Vec<_>>();
+            sizes.sort_unstable();
+            match links {
+                Links::Report => assert_eq!(
+                    sizes,
+                    [(2, 2), (2, 2)],
+                    "only the hard links are left to the caller"
+                ),
+                Links::Follow => assert_eq!(
+                    sizes,
+                    [(1, 1), (2, 2), (2, 2)],
+                    "entries reached through symbolic links are listed at most once per hard link"
+                ),
+            }
+        }
+    }
+
     #[test]
     fn concurrent_roots_keep_their_identity() {
         let dir = tempfile::tempdir().unwrap();
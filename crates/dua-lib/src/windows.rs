@@ -32,22 +32,36 @@ pub struct Entry {
     pub depth: usize,
     /// File name relative to `parent_path`.
     pub file_name: OsString,
-    /// Filesystem entry type without following symbolic links.
+    /// Filesystem entry type, which is the type of the link target if links are followed.
     pub file_type: FileType,
     /// Metadata obtained with directory enumeration.
     pub metadata: io::Result<Metadata>,
     /// Path containing this entry.
     pub parent_path: Arc<Path>,
+    /// The combined contents of this directory if it was visited with [`crate::Visit::Summarize`].
+    pub summary: Option<crate::Summary>,
 }
 
 impl Entry {
     /// Create an entry from a filesystem path.
     pub fn from_path(path: &Path) -> io::Result<Self> {
-        let handle = OwnedHandle::open(
-            path,
-            FILE_READ_ATTRIBUTES | SYNCHRONIZE,
-            FILE_FLAG_OPEN_REPARSE_POINT,
-        )?;
+        Self::open(path, FILE_FLAG_OPEN_REPARSE_POINT)
+    }
+
+    /// Replace the type and metadata of a symbolic link or junction with those of its target,
+    /// leaving the entry unchanged if the target can't be opened.
+    pub(crate) fn follow(mut self) -> Self {
+        if self.file_type.is_symlink()
+            && let Ok(target) = Self::open(&self.path(), 0)
+        {
+            self.file_type = target.file_type;
+            self.metadata = target.metadata;
+        }
+        self
+    }
+
+    fn open(path: &Path, extra_flags: u32) -> io::Result<Self> {
+        let handle = OwnedHandle::open(path, FILE_READ_ATTRIBUTES | SYNCHRONIZE, extra_flags)?;
         let mut info = BY_HANDLE_FILE_INFORMATION::default();
         // SAFETY: `handle` is owned and valid, and `info` is writable for the call's duration.
         if unsafe { GetFileInformationByHandle(handle.0, &raw mut info) } == 0 {
@@ -102,6 +116,7 @@ impl Entry {
             file_type,
             metadata: Ok(metadata),
             parent_path: Arc::from(path.parent().unwrap_or(Path::new(""))),
+            summary: None,
         })
     }
 
@@ -319,6 +334,7 @@ impl ReadDir {
                     file_id,
                 }),
                 parent_path: Arc::clone(&self.parent_path),
+                summary: None,
             },
             next_offset,
         ))
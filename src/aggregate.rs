@@ -1,3 +1,4 @@
+use crate::ownership::{OwnerUsage, UsageByOwner, user_name_of};
 use crate::{ByteFormat, InodeFilter, Throttle, WalkOptions, WalkResult, WalkRoot, crossdev};
 use anyhow::Result;
 #[cfg(not(windows))]
@@ -53,30 +54,15 @@ pub fn aggregate(
             )
         })
         .collect::<Vec<_>>();
-    let mut device_ids = vec![0; num_roots];
     let mut completed = vec![false; num_roots];
-    let mut roots = Vec::with_capacity(num_roots);
-    let has_ignore_patterns = walk_options.ignore_patterns.is_some();
-    for (root_idx, path) in paths.into_iter().enumerate() {
-        let device_id = if walk_options.cross_filesystems {
-            0
-        } else {
-            let Ok(device_id) = crossdev::init(&path) else {
-                aggregates[root_idx].2 += 1;
-                completed[root_idx] = true;
-                continue;
-            };
-            device_id
-        };
-        device_ids[root_idx] = device_id;
-        roots.push(WalkRoot {
-            index: root_idx,
-            pattern_root: has_ignore_patterns.then(|| path.clone()),
-            path,
-            device_id,
-        });
+    let (roots, device_ids) = walk_roots(&walk_options, paths);
+    for (root_idx, device_id) in device_ids.iter().enumerate() {
+        if device_id.is_none() {
+            aggregates[root_idx].2 += 1;
+            completed[root_idx] = true;
+        }
     }
-    let mut inodes = InodeFilter::default();
+    let mut inodes = InodeFilter::new(walk_options.follow_links, walk_options.count_hard_links);
     let progress = Throttle::new(Duration::from_millis(100), Duration::from_secs(1).into());
     let mut progress_visible = false;
     let mut next_output = 0;
@@ -113,30 +99,21 @@ pub fn aggregate(
         });
         match entry {
             Ok(entry) => {
-                let file_size = u128::from(match &entry.metadata {
-                    Ok(m)
-                        if (walk_options.count_hard_links || inodes.add(m))
-                            && (walk_options.cross_filesystems
-                                || crossdev::is_same_device(device_ids[root_idx], m)) =>
-                    {
-                        if walk_options.apparent_size {
-                            m.len()
-                        } else {
-                            size_on_disk(&entry, m).unwrap_or_else(|_| {
-                                *num_errors += 1;
-                                0
-                            })
-                        }
-                    }
-                    Ok(_) => 0,
-                    Err(_) => {
-                        *num_errors += 1;
-                        0
-                    }
-                });
+                let file_size = entry_size(
+                    &walk_options,
+                    &mut inodes,
+                    device_ids[root_idx].unwrap_or_default(),
+                    &entry,
+                    num_errors,
+                );
                 stats.largest_file_in_bytes = stats.largest_file_in_bytes.max(file_size);
                 stats.smallest_file_in_bytes = stats.smallest_file_in_bytes.min(file_size);
                 *num_bytes += file_size;
+                if let Some(summary) = &entry.summary {
+                    stats.entries_traversed += summary.entries;
+                    *num_errors += summary.io_errors;
+                    *num_bytes += walk_options.summarized_size(summary, &mut inodes);
+                }
             }
             Err(_) => *num_errors += 1,
         }
@@ -183,6 +160,202 @@ pub fn aggregate(
     Ok((res, stats))
 }
 
+/// Aggregate the given `paths` by the user owning each entry, and write the bytes and entries of
+/// each user to `out` in a human-readable format. Owners are only known on Unix.
+/// If `compute_total` is set, it will write an additional line with the total across all users.
+/// If `sort_by_size_in_bytes` is set, users are sorted by their size (ascending), otherwise by their id.
+pub fn aggregate_by_owner(
+    mut out: impl io::Write,
+    mut err: Option<impl io::Write>,
+    walk_options: WalkOptions,
+    compute_total: bool,
+    sort_by_size_in_bytes: bool,
+    byte_format: ByteFormat,
+    paths: Vec<PathBuf>,
+) -> Result<(WalkResult, Statistics)> {
+    let mut stats = Statistics {
+        smallest_file_in_bytes: u128::MAX,
+        ..Default::default()
+    };
+    let mut usage = UsageByOwner::default();
+    let (roots, device_ids) = walk_roots(&walk_options, paths);
+    let failed_roots = device_ids.iter().filter(|id| id.is_none()).count() as u64;
+    if failed_roots != 0 {
+        usage.of(None).io_errors += failed_roots;
+    }
+    let mut inodes = InodeFilter::new(walk_options.follow_links, walk_options.count_hard_links);
+    let progress = Throttle::new(Duration::from_millis(100), Duration::from_secs(1).into());
+    let mut progress_visible = false;
+
+    for (root_idx, event) in
+        walk_options.iter_from_paths(roots, false, crate::walk::Order::Completion)
+    {
+        let crate::walk::RootEvent::Entry(entry) = event else {
+            continue;
+        };
+        stats.entries_traversed += 1;
+        progress.throttled(|| {
+            if let Some(err) = err.as_mut() {
+                write!(err, "Enumerating {} items\r", stats.entries_traversed).ok();
+                progress_visible = true;
+            }
+        });
+        let Ok(entry) = entry else {
+            usage.of(None).io_errors += 1;
+            continue;
+        };
+        let owner = usage.of(owner_of(&entry));
+        let file_size = entry_size(
+            &walk_options,
+            &mut inodes,
+            device_ids[root_idx].unwrap_or_default(),
+            &entry,
+            &mut owner.io_errors,
+        );
+        stats.largest_file_in_bytes = stats.largest_file_in_bytes.max(file_size);
+        stats.smallest_file_in_bytes = stats.smallest_file_in_bytes.min(file_size);
+        owner.bytes += file_size;
+        owner.entries += 1;
+        // Summarized contents are attributed to the owner of their directory.
+        if let Some(summary) = &entry.summary {
+            stats.entries_traversed += summary.entries;
+            owner.entries += summary.entries;
+            owner.io_errors += summary.io_errors;
+            owner.bytes += walk_options.summarized_size(summary, &mut inodes);
+        }
+    }
+
+    if stats.entries_traversed == 0 {
+        stats.smallest_file_in_bytes = 0;
+    }
+    if progress_visible && let Some(err) = err.as_mut() {
+        write!(err, "{CLEAR_CURRENT_LINE}").ok();
+    }
+
+    let mut owners = usage.into_sorted_by_size();
+    if !sort_by_size_in_bytes {
+        owners.sort_by_key(|owner| (owner.uid.is_none(), owner.uid));
+    }
+    for owner in &owners {
+        output_owner(&mut out, owner, &owner_label(owner.uid), byte_format)?;
+    }
+    let total = owners
+        .iter()
+        .fold(OwnerUsage::default(), |total, owner| OwnerUsage {
+            bytes: total.bytes + owner.bytes,
+            entries: total.entries + owner.entries,
+            io_errors: total.io_errors + owner.io_errors,
+            ..total
+        });
+    if owners.len() > 1 && compute_total {
+        output_owner(&mut out, &total, "total", byte_format)?;
+    }
+    let res = WalkResult {
+        num_errors: total.io_errors,
+    };
+    Ok((res, stats))
+}
+
+#[cfg(unix)]
+fn owner_of(entry: &crate::walk::Entry) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    entry.metadata.as_ref().ok().map(MetadataExt::uid)
+}
+
+#[cfg(not(unix))]
+fn owner_of(_entry: &crate::walk::Entry) -> Option<u32> {
+    None
+}
+
+/// Return the name of the user with `uid`, its id if it has no name, or a placeholder if it's unknown.
+fn owner_label(uid: Option<u32>) -> String {
+    match uid {
+        Some(uid) => user_name_of(uid).map_or_else(|| uid.to_string(), |name| name.to_string()),
+        None => "<unknown owner>".into(),
+    }
+}
+
+fn output_owner(
+    out: &mut impl io::Write,
+    owner: &OwnerUsage,
+    label: &str,
+    byte_format: ByteFormat,
+) -> io::Result<()> {
+    let entries = format!(
+        "{} entr{}",
+        owner.entries,
+        if owner.entries == 1 { "y" } else { "ies" }
+    );
+    output_colored_path(
+        out,
+        Path::new(&format!("{label} ({entries})")),
+        owner.bytes,
+        owner.io_errors,
+        owner.uid.map(|_| Color::Cyan),
+        byte_format,
+    )
+}
+
+/// Prepare walking `paths`, returning the roots to walk along with the device id of each path,
+/// which is `None` if it couldn't be obtained and the path won't be walked.
+fn walk_roots(
+    walk_options: &WalkOptions,
+    paths: Vec<PathBuf>,
+) -> (Vec<WalkRoot>, Vec<Option<u64>>) {
+    let mut device_ids = vec![None; paths.len()];
+    let mut roots = Vec::with_capacity(paths.len());
+    let has_ignore_patterns = walk_options.ignore_patterns.is_some();
+    for (root_idx, path) in paths.into_iter().enumerate() {
+        let device_id = if walk_options.cross_filesystems {
+            0
+        } else {
+            let Ok(device_id) = crossdev::init(&path) else {
+                continue;
+            };
+            device_id
+        };
+        device_ids[root_idx] = Some(device_id);
+        roots.push(WalkRoot {
+            index: root_idx,
+            pattern_root: has_ignore_patterns.then(|| path.clone()),
+            path,
+            device_id,
+        });
+    }
+    (roots, device_ids)
+}
+
+/// Return the size of `entry` itself, which is 0 if it was counted before or resides on another
+/// device than `device_id`. `num_errors` is increased if its size couldn't be obtained.
+fn entry_size(
+    walk_options: &WalkOptions,
+    inodes: &mut InodeFilter,
+    device_id: u64,
+    entry: &crate::walk::Entry,
+    num_errors: &mut u64,
+) -> u128 {
+    u128::from(match &entry.metadata {
+        Ok(m)
+            if inodes.add(m)
+                && (walk_options.cross_filesystems || crossdev::is_same_device(device_id, m)) =>
+        {
+            if walk_options.apparent_size {
+                m.len()
+            } else {
+                size_on_disk(entry, m).unwrap_or_else(|_| {
+                    *num_errors += 1;
+                    0
+                })
+            }
+        }
+        Ok(_) => 0,
+        Err(_) => {
+            *num_errors += 1;
+            0
+        }
+    })
+}
+
 /// Write the contiguous run of completed roots starting at `next_output`, preserving input order.
 /// Clears a visible progress line before writing the first completed root.
 /// `progress_visible` tracks if progress information is currently shown, taking up the last line.
@@ -249,8 +422,13 @@ fn output_colored_path(
     path_color: Option<Color>,
     byte_format: ByteFormat,
 ) -> std::result::Result<(), io::Error> {
+    let colored = !crate::theme::no_color();
     let size = byte_format.display(num_bytes).to_string();
-    let size = size.green();
+    let size = if colored {
+        size.green().to_string()
+    } else {
+        size
+    };
     let size_width = byte_format.width();
     let path = path.as_ref().display();
 
@@ -263,7 +441,7 @@ fn output_colored_path(
         String::new()
     };
 
-    if let Some(color) = path_color {
+    if let Some(color) = path_color.filter(|_| colored) {
         writeln!(out, "{size:>size_width$} {}{errors}", path.color(color))
     } else {
         writeln!(out, "{size:>size_width$} {path}{errors}")
@@ -284,6 +462,7 @@ pub struct Statistics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::test_walk_options;
 
     fn byte_counts(out: &[u8]) -> Vec<u128> {
         let out = std::str::from_utf8(out).unwrap();
@@ -371,9 +550,7 @@ mod tests {
                 threads: 2,
                 count_hard_links: true,
                 apparent_size: false,
-                cross_filesystems: true,
-                ignore_dirs: std::collections::BTreeSet::default(),
-                ignore_patterns: None,
+                ..test_walk_options()
             },
             true,
             true,
@@ -401,12 +578,9 @@ mod tests {
             Vec::new(),
             None::<Vec<u8>>,
             WalkOptions {
-                threads: 1,
                 count_hard_links: true,
-                apparent_size: true,
                 cross_filesystems: false,
-                ignore_dirs: std::collections::BTreeSet::default(),
-                ignore_patterns: None,
+                ..test_walk_options()
             },
             false,
             true,
@@ -418,6 +592,51 @@ mod tests {
         assert_eq!(result.num_errors, 1);
     }
 
+    #[test]
+    fn sizes_by_owner_add_up_to_the_aggregated_size() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested/file"), [0; 100]).unwrap();
+        std::fs::write(dir.path().join("file"), [0; 10]).unwrap();
+
+        let walk_options = || WalkOptions {
+            count_hard_links: true,
+            ..test_walk_options()
+        };
+        let mut by_path = Vec::new();
+        aggregate(
+            &mut by_path,
+            None::<&mut Vec<u8>>,
+            walk_options(),
+            true,
+            true,
+            ByteFormat::Bytes,
+            vec![dir.path().to_owned()],
+        )
+        .unwrap();
+        let mut by_owner = Vec::new();
+        let (res, stats) = aggregate_by_owner(
+            &mut by_owner,
+            None::<&mut Vec<u8>>,
+            walk_options(),
+            true,
+            true,
+            ByteFormat::Bytes,
+            vec![dir.path().to_owned()],
+        )
+        .unwrap();
+
+        assert_eq!(res.num_errors, 0);
+        assert_eq!(stats.entries_traversed, 4);
+        assert_eq!(
+            byte_counts(&by_owner),
+            byte_counts(&by_path),
+            "all entries belong to the same user, so there is no total either"
+        );
+        let out = std::str::from_utf8(&by_owner).unwrap();
+        assert!(out.contains("(4 entries)"), "entries are counted: {out:?}");
+    }
+
     #[test]
     fn ignored_patterns_are_left_out_of_the_reported_size() {
         let dir = tempfile::tempdir().unwrap();
@@ -440,10 +659,8 @@ mod tests {
                 WalkOptions {
                     threads: 2,
                     count_hard_links: true,
-                    apparent_size: true,
-                    cross_filesystems: true,
-                    ignore_dirs: std::collections::BTreeSet::default(),
                     ignore_patterns: crate::IgnorePatterns::from_files(ignore_from).unwrap(),
+                    ..test_walk_options()
                 },
                 false,
                 true,
@@ -0,0 +1,175 @@
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Paths to jump to by name, stored on disk for each set of roots so they are available
+/// whenever the same roots are looked at again.
+#[derive(Debug, Default)]
+pub struct Bookmarks {
+    /// The absolute paths of the roots these bookmarks belong to, sorted.
+    roots: Vec<PathBuf>,
+    /// The file the bookmarks of all roots are stored in, or `None` to keep them in memory.
+    file: Option<PathBuf>,
+    /// The path of each bookmark relative to the roots, by its name.
+    by_name: BTreeMap<String, PathBuf>,
+}
+
+/// The contents of the bookmarks file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default)]
+    roots: Vec<RootBookmarks>,
+}
+
+/// The bookmarks of one set of roots.
+#[derive(Debug, Serialize, Deserialize)]
+struct RootBookmarks {
+    paths: Vec<PathBuf>,
+    bookmarks: BTreeMap<String, PathBuf>,
+}
+
+impl Bookmarks {
+    /// Load the bookmarks of `roots` from `file`, which doesn't have to exist yet.
+    ///
+    /// Roots are made absolute relative to the working directory, and no roots stand for it.
+    pub fn load(file: PathBuf, roots: &[PathBuf]) -> Result<Self> {
+        let roots = absolute_roots(roots)?;
+        let by_name = read(&file)?
+            .roots
+            .into_iter()
+            .find(|stored| stored.paths == roots)
+            .map(|stored| stored.bookmarks)
+            .unwrap_or_default();
+        Ok(Bookmarks {
+            roots,
+            file: Some(file),
+            by_name,
+        })
+    }
+
+    /// Return the location of the bookmarks file for the current platform, next to other
+    /// application data like `$XDG_DATA_HOME/dua-cli/bookmarks.toml`.
+    pub fn path() -> Result<PathBuf> {
+        let data_dir =
+            dirs::data_dir().ok_or_else(|| anyhow!("platform data directory is unavailable"))?;
+        Ok(data_dir.join("dua-cli").join("bookmarks.toml"))
+    }
+
+    /// All bookmarks, ordered by name.
+    #[must_use]
+    pub fn by_name(&self) -> &BTreeMap<String, PathBuf> {
+        &self.by_name
+    }
+
+    /// Return the path of the bookmark called `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Path> {
+        self.by_name.get(name).map(PathBuf::as_path)
+    }
+
+    /// Return the name of the bookmark pointing to `path`.
+    #[must_use]
+    pub fn name_of(&self, path: &Path) -> Option<&str> {
+        self.by_name
+            .iter()
+            .find_map(|(name, bookmarked)| (bookmarked == path).then_some(name.as_str()))
+    }
+
+    /// Bookmark `path` as `name`, replacing a bookmark with the same name, and store the change.
+    pub fn insert(&mut self, name: String, path: PathBuf) -> Result<()> {
+        self.by_name.insert(name, path);
+        self.store()
+    }
+
+    /// Remove the bookmark called `name` and store the change.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.by_name.remove(name);
+        self.store()
+    }
+
+    /// Write the bookmarks of our roots to the bookmarks file, keeping those of all other roots.
+    fn store(&self) -> Result<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        let mut stored = read(file)?;
+        stored.roots.retain(|stored| stored.paths != self.roots);
+        if !self.by_name.is_empty() {
+            stored.roots.push(RootBookmarks {
+                paths: self.roots.clone(),
+                bookmarks: self.by_name.clone(),
+            });
+        }
+
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let contents = toml::to_string(&stored).context("Failed to serialize bookmarks")?;
+        std::fs::write(file, contents)
+            .with_context(|| format!("Failed to write bookmarks to {}", file.display()))
+    }
+}
+
+fn read(file: &Path) -> Result<BookmarksFile> {
+    match std::fs::read_to_string(file) {
+        Ok(contents) => toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse bookmarks at {}", file.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(BookmarksFile::default()),
+        Err(err) => {
+            Err(err).with_context(|| format!("Failed to read bookmarks at {}", file.display()))
+        }
+    }
+}
+
+/// Return the roots as sorted absolute paths, with no roots standing for the working directory.
+fn absolute_roots(roots: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut roots = if roots.is_empty() {
+        vec![std::env::current_dir()?]
+    } else {
+        roots
+            .iter()
+            .map(std::path::absolute)
+            .collect::<std::io::Result<_>>()?
+    };
+    roots.sort();
+    roots.dedup();
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bookmarks_are_stored_per_set_of_roots() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("data").join("bookmarks.toml");
+        let roots = [PathBuf::from("/b"), PathBuf::from("/a")];
+
+        let mut bookmarks = Bookmarks::load(file.clone(), &roots)?;
+        assert!(bookmarks.by_name().is_empty(), "the file doesn't exist yet");
+        bookmarks.insert("cache".into(), ".cache".into())?;
+        bookmarks.insert("build".into(), "target/debug".into())?;
+        let mut other_bookmarks = Bookmarks::load(file.clone(), &[PathBuf::from("/a")])?;
+        other_bookmarks.insert("cache".into(), "elsewhere".into())?;
+
+        let roots_in_other_order = [PathBuf::from("/a"), PathBuf::from("/b")];
+        let mut bookmarks = Bookmarks::load(file.clone(), &roots_in_other_order)?;
+        assert_eq!(bookmarks.get("cache"), Some(Path::new(".cache")));
+        assert_eq!(bookmarks.name_of(Path::new("target/debug")), Some("build"));
+
+        bookmarks.remove("cache")?;
+        bookmarks.remove("build")?;
+        assert!(Bookmarks::load(file.clone(), &roots)?.by_name().is_empty());
+        assert_eq!(
+            Bookmarks::load(file, &[PathBuf::from("/a")])?.get("cache"),
+            Some(Path::new("elsewhere")),
+            "bookmarks of other roots are kept"
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{crossdev, walk};
+use crate::{crossdev, inodefilter::InodeFilter, walk};
 use anyhow::Context;
 use byte_unit::{Byte, Unit, UnitType};
 use serde::Deserialize;
@@ -11,6 +11,7 @@ use std::{fmt, path::Path};
 
 /// Specifies a way to format bytes
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ByteFormat {
     /// metric format, based on 1000.
     #[serde(rename = "metric")]
@@ -165,6 +166,10 @@ impl Throttle {
 #[derive(Clone, Debug)]
 pub struct IgnorePatterns {
     search: gix::ignore::Search,
+    /// The files the patterns were read from along with their content, in order, to be able
+    /// to recreate `search` after deserialization.
+    #[cfg(feature = "serde")]
+    sources: Vec<(PathBuf, Vec<u8>)>,
 }
 
 impl IgnorePatterns {
@@ -175,19 +180,19 @@ impl IgnorePatterns {
     /// that cannot be read, is an error - a silently empty pattern set would quietly report sizes
     /// the caller did not ask for.
     pub fn from_files(files: &[PathBuf]) -> anyhow::Result<Option<Self>> {
-        let mut search = gix::ignore::Search::default();
-        for file in files {
-            let buf = std::fs::read(file).with_context(|| {
-                format!("Failed to read ignore patterns from {}", file.display())
-            })?;
-            search.add_patterns_buffer(
-                &buf,
-                file.clone(),
-                None,
-                gix::ignore::search::Ignore::default(),
-            );
-        }
-        let pattern_count = search
+        let sources = files
+            .iter()
+            .map(|file| {
+                std::fs::read(file)
+                    .map(|buf| (file.clone(), buf))
+                    .with_context(|| {
+                        format!("Failed to read ignore patterns from {}", file.display())
+                    })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let patterns = Self::from_sources(sources);
+        let pattern_count = patterns
+            .search
             .patterns
             .iter()
             .map(|list| list.patterns.len())
@@ -197,12 +202,30 @@ impl IgnorePatterns {
                 "Loaded {pattern_count} ignore pattern(s) from {file_count} file(s)",
                 file_count = files.len()
             );
-            Some(Self { search })
+            Some(patterns)
         } else {
             None
         })
     }
 
+    /// Parse the pattern file contents in `sources`, each along with the path it was read from.
+    fn from_sources(sources: Vec<(PathBuf, Vec<u8>)>) -> Self {
+        let mut search = gix::ignore::Search::default();
+        for (file, buf) in &sources {
+            search.add_patterns_buffer(
+                buf,
+                file.clone(),
+                None,
+                gix::ignore::search::Ignore::default(),
+            );
+        }
+        Self {
+            search,
+            #[cfg(feature = "serde")]
+            sources,
+        }
+    }
+
     /// Return `true` if `relative_path` is excluded, with `is_dir` telling directories from files
     /// so that patterns ending in `/` only match directories.
     ///
@@ -237,17 +260,79 @@ impl IgnorePatterns {
     }
 }
 
+/// The serialized form of a pattern file, as parsed patterns can't be serialized themselves.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct IgnorePatternsSource {
+    file: PathBuf,
+    patterns: String,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IgnorePatterns {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let sources = self
+            .sources
+            .iter()
+            .map(|(file, buf)| {
+                Ok(IgnorePatternsSource {
+                    file: file.clone(),
+                    patterns: String::from_utf8(buf.clone()).map_err(|err| {
+                        serde::ser::Error::custom(format!(
+                            "ignore patterns in {} are not valid UTF-8: {err}",
+                            file.display()
+                        ))
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<_>, S::Error>>()?;
+        serializer.collect_seq(sources)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IgnorePatterns {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sources = Vec::<IgnorePatternsSource>::deserialize(deserializer)?;
+        Ok(Self::from_sources(
+            sources
+                .into_iter()
+                .map(|source| (source.file, source.patterns.into_bytes()))
+                .collect(),
+        ))
+    }
+}
+
 /// Configures a filesystem walk, including output and formatting options.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag is an independent walk option"
+)]
 pub struct WalkOptions {
     /// The amount of filesystem worker threads to use.
     pub threads: usize,
-    /// If `true`, count every hard-link occurrence independently.
+    /// If `true`, count every hard-link occurrence independently. Entries reached again through
+    /// followed symbolic links are still counted once.
     pub count_hard_links: bool,
     /// If `true`, use apparent size (`metadata.len()`), not allocated blocks on disk.
     pub apparent_size: bool,
     /// If `false`, traversal is constrained to the root filesystem/device.
     pub cross_filesystems: bool,
+    /// If `true`, symbolic links are followed and their targets are counted instead.
+    pub follow_links: bool,
+    /// If set, directories this many levels below a root are still counted with everything
+    /// they contain, but their contents aren't yielded individually.
+    ///
+    /// Ignore patterns, ignored directories and other filesystems are respected below this
+    /// depth as well, so sizes are the same as without it.
+    pub max_depth: Option<usize>,
+    /// If set, files smaller than this many bytes aren't added to the traversal tree
+    /// individually, but are combined into one [small-files node](crate::traverse::EntryData::is_small_files)
+    /// per directory. This keeps the memory used by the tree proportional to the amount of
+    /// directories and large files, while sizes and entry counts remain exact.
+    pub collapse_files_below: Option<u64>,
     /// Canonicalized directories to skip from traversal.
     pub ignore_dirs: BTreeSet<PathBuf>,
     /// Gitignore-style patterns whose matches are left out of the traversal entirely.
@@ -305,6 +390,7 @@ impl WalkOptions {
         let ignore_dirs = self.ignore_dirs.clone();
         let cwd = std::env::current_dir().unwrap_or_default();
         let cross_filesystems = self.cross_filesystems;
+        let max_depth = self.max_depth;
 
         // Excluding an entry means pruning it from the walk *and* from the emitted events, so the
         // predicate is shared between the two. It short-circuits on the pattern set being empty to
@@ -326,17 +412,33 @@ impl WalkOptions {
         };
         let is_excluded_while_walking = Arc::clone(&is_excluded);
 
-        walk::walk_roots(
+        walk::walk_roots_with_links(
             paths_with_idx,
             self.threads,
             order,
+            if self.follow_links {
+                walk::Links::Follow
+            } else {
+                walk::Links::Report
+            },
             move |root_idx, entry| {
-                (cross_filesystems
+                let same_device = cross_filesystems
                     || entry.metadata.as_ref().map_or(true, |metadata| {
                         crossdev::is_same_device(device_ids[root_idx], metadata)
-                    }))
-                    && (entry.depth == 0 || !ignore_directory(&entry.path(), &ignore_dirs, &cwd))
-                    && !is_excluded_while_walking(root_idx, entry)
+                    });
+                // Entries on other devices aren't counted, so they are excluded from summaries
+                // just like the ones matching a pattern, which aren't even emitted.
+                if !same_device || is_excluded_while_walking(root_idx, entry) {
+                    walk::Visit::Exclude
+                } else if !entry.file_type.is_dir()
+                    || (entry.depth > 0 && ignore_directory(&entry.path(), &ignore_dirs, &cwd))
+                {
+                    walk::Visit::Prune
+                } else if max_depth.is_some_and(|max_depth| entry.depth >= max_depth) {
+                    walk::Visit::Summarize
+                } else {
+                    walk::Visit::Descend
+                }
             },
         )
         .filter(move |(root_idx, event)| match event {
@@ -346,6 +448,44 @@ impl WalkOptions {
             walk::RootEvent::Entry(Err(_)) | walk::RootEvent::Finished => true,
         })
     }
+
+    /// Return the size of everything within a directory that was summarized as it reached
+    /// `max_depth`, counting hard links only if `inodes` didn't see them yet.
+    pub(crate) fn summarized_size(
+        &self,
+        summary: &walk::Summary,
+        inodes: &mut InodeFilter,
+    ) -> u128 {
+        self.summarized_sizes(summary, inodes).0
+    }
+
+    /// Like [`summarized_size()`](Self::summarized_size()), but also return the disk usage if the
+    /// apparent size is used and vice versa.
+    pub(crate) fn summarized_sizes(
+        &self,
+        summary: &walk::Summary,
+        inodes: &mut InodeFilter,
+    ) -> (u128, u128) {
+        let (apparent_size, disk_size) = summary
+            .hard_links
+            .iter()
+            .filter(|link| inodes.add_dev_inode(link.id, link.links))
+            .map(|link| (link.apparent_size, link.disk_size))
+            .fold(
+                (summary.apparent_size, summary.disk_size),
+                |(apparent, disk), (link_apparent, link_disk)| {
+                    (
+                        apparent + u128::from(link_apparent),
+                        disk + u128::from(link_disk),
+                    )
+                },
+            );
+        if self.apparent_size {
+            (apparent_size, disk_size)
+        } else {
+            (disk_size, apparent_size)
+        }
+    }
 }
 
 /// Information we gather during a filesystem walk
@@ -411,6 +551,23 @@ fn ignore_directory(path: &Path, ignore_dirs: &BTreeSet<PathBuf>, cwd: &Path) ->
     })
 }
 
+/// Return options for walks in tests that use one thread, count hard links once, use the
+/// apparent size and cross filesystems, to be adjusted with struct-update syntax.
+#[cfg(test)]
+pub(crate) fn test_walk_options() -> WalkOptions {
+    WalkOptions {
+        threads: 1,
+        count_hard_links: false,
+        apparent_size: true,
+        cross_filesystems: true,
+        follow_links: false,
+        max_depth: None,
+        collapse_files_below: None,
+        ignore_dirs: BTreeSet::default(),
+        ignore_patterns: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -474,6 +631,9 @@ mod tests {
             count_hard_links: false,
             apparent_size: false,
             cross_filesystems: true,
+            follow_links: false,
+            max_depth: None,
+            collapse_files_below: None,
             ignore_dirs: canonicalize_ignore_dirs(&[root.path().to_owned()]),
             ignore_patterns: None,
         };
@@ -652,6 +812,9 @@ mod tests {
             count_hard_links: false,
             apparent_size: false,
             cross_filesystems: true,
+            follow_links: false,
+            max_depth: None,
+            collapse_files_below: None,
             ignore_dirs: BTreeSet::default(),
             ignore_patterns: Some(patterns_from("nested/secret\n")),
         };
@@ -705,6 +868,9 @@ mod tests {
             count_hard_links: false,
             apparent_size: false,
             cross_filesystems: true,
+            follow_links: false,
+            max_depth: None,
+            collapse_files_below: None,
             ignore_dirs: BTreeSet::default(),
             ignore_patterns: Some(patterns_from(contents)),
         };
@@ -730,4 +896,65 @@ mod tests {
         paths.sort();
         paths
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn walk_options_round_trip_through_serde() {
+        let options = WalkOptions {
+            threads: 3,
+            count_hard_links: true,
+            apparent_size: false,
+            cross_filesystems: true,
+            follow_links: false,
+            max_depth: None,
+            collapse_files_below: None,
+            ignore_dirs: BTreeSet::from([PathBuf::from("/ignored")]),
+            ignore_patterns: Some(patterns_from("*.log\n!keep.log\n")),
+        };
+
+        let json = serde_json::to_string(&options).unwrap();
+        let options: WalkOptions = serde_json::from_str(&json).unwrap();
+        assert_eq!(options.threads, 3);
+        assert!(options.count_hard_links && !options.apparent_size && options.cross_filesystems);
+        assert_eq!(
+            options.ignore_dirs,
+            BTreeSet::from([PathBuf::from("/ignored")])
+        );
+        let patterns = options
+            .ignore_patterns
+            .expect("patterns survive the round-trip");
+        assert!(patterns.is_excluded(Path::new("debug.log"), false));
+        assert!(!patterns.is_excluded(Path::new("keep.log"), false));
+        assert_eq!(
+            serde_json::to_string(&WalkOptions {
+                ignore_patterns: Some(patterns),
+                ..options
+            })
+            .unwrap(),
+            json,
+            "serializing again yields the same representation"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn byte_format_round_trips_through_serde() {
+        for format in [
+            ByteFormat::Metric,
+            ByteFormat::Binary,
+            ByteFormat::Bytes,
+            ByteFormat::GB,
+            ByteFormat::GiB,
+            ByteFormat::MB,
+            ByteFormat::MiB,
+        ] {
+            let json = serde_json::to_string(&format).unwrap();
+            assert_eq!(serde_json::from_str::<ByteFormat>(&json).unwrap(), format);
+        }
+        assert_eq!(
+            serde_json::to_string(&ByteFormat::Metric).unwrap(),
+            r#""metric""#,
+            "uses the same names as the configuration file"
+        );
+    }
 }
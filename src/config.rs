@@ -2,7 +2,10 @@ use anyhow::{Context, Result, anyhow};
 
 use serde::Deserialize;
 
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
+
+pub mod keys;
+pub mod theme;
 
 /// Runtime configuration used by interactive and CLI components.
 ///
@@ -24,9 +27,52 @@ use std::path::PathBuf;
 /// # If unset, behavior defaults to true.
 /// # cleanup_heuristics = true
 ///
+/// # Controls whether hidden entries, whose name starts with a dot, are listed in interactive mode.
+/// # Supported values: true, false.
+/// # If unset, behavior defaults to true.
+/// # show_hidden = true
+///
+/// # Controls whether the mouse can be used in interactive mode, to click entries and column
+/// # headers and to scroll with the wheel.
+/// # Supported values: true, false.
+/// # If unset, behavior defaults to false.
+/// # mouse = true
+///
+/// # The difference in size, in percent of the larger one, above which entries with the same
+/// # name on both sides of a split view are highlighted.
+/// # If unset, behavior defaults to 10.
+/// # split_threshold = 10
+///
+/// # Commands to run on entries in interactive mode, by name.
+/// # {path} is replaced with the selected entry, and {paths} with all marked entries, or the
+/// # selected one if none are marked.
+/// [commands]
+/// ncdu = "ncdu {path}"
+/// archive = "tar czf archive.tar.gz {paths}"
+///
+/// # Directories that can be recreated, highlighted as cleanup candidates in interactive mode.
+/// # `name` is a directory name or glob pattern, and if `siblings` is set, one of these entries
+/// # must exist next to the directory. If set, the rules replace the built-in ones.
+/// [[cleanup_rules]]
+/// name = "target"
+/// siblings = ["Cargo.toml"]
+///
+/// [[cleanup_rules]]
+/// name = "cmake-build-*"
+///
 /// [keys]
 /// esc_navigates_back = true
 ///
+/// # Bind key chords to named actions, per pane.
+/// [keys.main]
+/// "ctrl-n" = "down"
+/// "ctrl-p" = "up"
+///
+/// [theme]
+/// # One of "default", "light", "high-contrast" or "monochrome".
+/// name = "light"
+/// directory = { fg = "blue", bold = true }
+///
 /// [notifications]
 /// scan_finished = true
 /// delete_finished = true
@@ -43,6 +89,11 @@ pub struct Config {
     /// Interactive completion-notification settings.
     pub notifications: NotificationsConfig,
 
+    /// The colors and modifiers used by the interactive UI.
+    ///
+    /// If `NO_COLOR` is set, the monochrome theme is used instead.
+    pub theme: theme::ThemeConfig,
+
     /// Whether Git-ignored entry detection is enabled.
     ///
     /// Supported values: `true` and `false`.
@@ -54,6 +105,82 @@ pub struct Config {
     /// Supported values: `true` and `false`.
     /// If unset, defaults to `true`.
     pub cleanup_heuristics: Option<bool>,
+
+    /// Whether hidden entries, whose name starts with a dot, are listed initially.
+    ///
+    /// Hidden entries are always counted in the size of their parent.
+    /// Supported values: `true` and `false`.
+    /// If unset, defaults to `true`.
+    pub show_hidden: Option<bool>,
+
+    /// Whether mouse events are captured in interactive mode.
+    ///
+    /// Clicking selects entries, double-clicking enters directories, the wheel scrolls the pane
+    /// under the pointer, and clicking a column header sorts by that column.
+    /// Capturing the mouse prevents selecting text in the terminal as usual.
+    /// Supported values: `true` and `false`.
+    /// If unset, defaults to `false`.
+    pub mouse: Option<bool>,
+
+    /// The difference in size, in percent of the larger one, above which entries with the same
+    /// name on both sides of a split view are highlighted.
+    ///
+    /// Entries without counterpart on the other side are always highlighted.
+    /// If unset, defaults to `10`.
+    pub split_threshold: Option<u32>,
+
+    /// Shell commands to run on the selected or marked entries in interactive mode, by name.
+    ///
+    /// `{path}` is replaced with the path of the selected entry, and `{paths}` with the paths
+    /// of all marked entries, or the selected one if none are marked, each quoted for the shell.
+    /// The interactive UI is suspended while the command runs in the terminal.
+    pub commands: BTreeMap<String, String>,
+
+    /// The rules for directories that are highlighted as cleanup candidates.
+    ///
+    /// If unset, the [built-in rules](CleanupRule::defaults) are used.
+    pub cleanup_rules: Option<Vec<CleanupRule>>,
+}
+
+/// A rule for directories that can be recreated, which makes them candidates to clean up.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CleanupRule {
+    /// The name of the directory, or a glob pattern like `cmake-build-*` its name must match.
+    pub name: String,
+    /// The names of entries of which at least one must exist next to the directory, like
+    /// `Cargo.toml` for `target`.
+    ///
+    /// If empty, directories are candidates wherever they are.
+    #[serde(default)]
+    pub siblings: Vec<String>,
+}
+
+impl CleanupRule {
+    /// Return the built-in rules, which match caches and virtual environments by name, and
+    /// build outputs with ambiguous names only next to the file describing the build.
+    #[must_use]
+    pub fn defaults() -> Vec<Self> {
+        let anywhere = [
+            ".mypy_cache",
+            ".pytest_cache",
+            ".ruff_cache",
+            ".tox",
+            ".venv",
+            "__pycache__",
+            "node_modules",
+            "target",
+            "venv",
+        ];
+        anywhere
+            .into_iter()
+            .map(|name| (name, &[][..]))
+            .chain([("build", &["build.gradle", "build.gradle.kts"][..])])
+            .map(|(name, siblings)| CleanupRule {
+                name: name.into(),
+                siblings: siblings.iter().map(|&sibling| sibling.into()).collect(),
+            })
+            .collect()
+    }
 }
 
 /// Completion notifications emitted by interactive mode.
@@ -83,8 +210,15 @@ impl NotificationsConfig {
 }
 
 /// Keyboard interaction settings.
+///
+/// Key chords are bound to [actions](keys::Action) in the `[keys.global]`, `[keys.main]`,
+/// `[keys.mark]`, `[keys.help]`, `[keys.treemap]` and `[keys.prompt]` tables. These bindings are
+/// added to the defaults, and replace the action of chords that are bound already, while chords
+/// bound to `"none"` lose their default binding. Bindings are validated when the configuration is
+/// loaded, so unknown keys or actions, actions bound in panes they aren't available in, and chords
+/// that are shadowed by a global binding are reported as errors.
 #[derive(Debug, Deserialize)]
-#[serde(default)]
+#[serde(try_from = "keys::RawKeysConfig")]
 pub struct KeysConfig {
     /// Changes `<Esc>` behavior in the interactive UI.
     ///
@@ -92,8 +226,10 @@ pub struct KeysConfig {
     /// If `false`, pressing `<Esc>` follows the default quit behavior, as if `q` was pressed.
     ///
     /// Default: `true`.
-    #[serde(default = "default_esc_navigates_back")]
     pub esc_navigates_back: bool,
+
+    /// The key chords bound to actions, per pane.
+    pub bindings: keys::KeyBindings,
 }
 
 fn default_esc_navigates_back() -> bool {
@@ -104,6 +240,7 @@ impl Default for KeysConfig {
     fn default() -> Self {
         Self {
             esc_navigates_back: default_esc_navigates_back(),
+            bindings: keys::KeyBindings::default(),
         }
     }
 }
@@ -163,11 +300,60 @@ impl Config {
             "# If unset, behavior defaults to true.\n",
             "# cleanup_heuristics = true\n",
             "#\n",
+            "# Controls whether hidden entries, whose name starts with a dot, are listed in interactive mode.\n",
+            "# Supported values: true, false.\n",
+            "# If unset, behavior defaults to true.\n",
+            "# show_hidden = true\n",
+            "#\n",
+            "# Controls whether the mouse can be used in interactive mode, to click entries and column\n",
+            "# headers and to scroll with the wheel.\n",
+            "# Supported values: true, false.\n",
+            "# If unset, behavior defaults to false.\n",
+            "# mouse = true\n",
+            "#\n",
+            "# The difference in size, in percent of the larger one, above which entries with the same\n",
+            "# name on both sides of a split view are highlighted.\n",
+            "# If unset, behavior defaults to 10.\n",
+            "# split_threshold = 10\n",
+            "#\n",
+            "# Commands to run on entries in interactive mode, by name.\n",
+            "# {path} is replaced with the selected entry, and {paths} with all marked entries, or the\n",
+            "# selected one if none are marked.\n",
+            "# [commands]\n",
+            "# ncdu = \"ncdu {path}\"\n",
+            "# archive = \"tar czf archive.tar.gz {paths}\"\n",
+            "#\n",
+            "# Directories that can be recreated, highlighted as cleanup candidates in interactive mode.\n",
+            "# name is a directory name or glob pattern, and if siblings is set, one of these entries\n",
+            "# must exist next to the directory. If set, the rules replace the built-in ones.\n",
+            "# [[cleanup_rules]]\n",
+            "# name = \"target\"\n",
+            "# siblings = [\"Cargo.toml\"]\n",
+            "#\n",
             "[keys]\n",
             "# If true, pressing <Esc> in the main pane ascends to the parent directory.\n",
             "# If false, <Esc> follows the default quit behavior.\n",
             "esc_navigates_back = true\n",
             "#\n",
+            "# Bind key chords like \"j\", \"ctrl-d\" or \"page-down\" to named actions, per pane.\n",
+            "# Tables: [keys.global], [keys.main], [keys.mark], [keys.help], [keys.treemap] and [keys.prompt].\n",
+            "# Bindings extend the defaults and replace the action of chords that are bound already.\n",
+            "# Chords bound to \"none\" lose their default binding.\n",
+            "# [keys.main]\n",
+            "# \"ctrl-n\" = \"down\"\n",
+            "# \"ctrl-p\" = \"up\"\n",
+            "# \"d\" = \"none\"\n",
+            "#\n",
+            "[theme]\n",
+            "# The built-in theme to use: default, light, high-contrast or monochrome.\n",
+            "# If NO_COLOR is set, monochrome is used instead.\n",
+            "name = \"default\"\n",
+            "# Styles of single elements can be replaced, with colors given by name, #rrggbb or palette index.\n",
+            "# Elements: header, header_marked, header_danger, footer, message, prompt, prompt_key, danger,\n",
+            "# selection, border, border_focussed, directory, file, marked, marked_file, missing,\n",
+            "# cleanup_candidate, gitignored, sparse, difference, bar and highlight.\n",
+            "# directory = { fg = \"blue\", bold = true }\n",
+            "#\n",
             "[notifications]\n",
             "# Send terminal notifications when interactive operations finish while unfocused.\n",
             "scan_finished = true\n",
@@ -194,7 +380,7 @@ impl Config {
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{CleanupRule, Config};
 
     #[test]
     fn notifications_default_to_enabled_and_can_be_disabled() {
@@ -313,4 +499,62 @@ mod tests {
 
         assert_eq!(config.cleanup_heuristics, None);
     }
+
+    #[test]
+    fn parses_configured_mouse_support() {
+        let config: Config = toml::from_str("mouse = true").expect("valid config");
+        assert_eq!(config.mouse, Some(true));
+
+        let config: Config = toml::from_str("").expect("valid config");
+        assert_eq!(config.mouse, None, "the mouse isn't captured by default");
+    }
+
+    #[test]
+    fn parses_configured_commands() {
+        let config: Config = toml::from_str(
+            r#"
+            [commands]
+            ncdu = "ncdu {path}"
+            archive = "tar czf archive.tar.gz {paths}"
+            "#,
+        )
+        .expect("valid config");
+        assert_eq!(
+            config.commands.keys().collect::<Vec<_>>(),
+            ["archive", "ncdu"],
+            "commands are ordered by name"
+        );
+        assert_eq!(config.commands["ncdu"], "ncdu {path}");
+    }
+
+    #[test]
+    fn parses_configured_cleanup_rules() {
+        let config: Config = toml::from_str(
+            r#"
+            [[cleanup_rules]]
+            name = "target"
+            siblings = ["Cargo.toml"]
+
+            [[cleanup_rules]]
+            name = "cmake-build-*"
+            "#,
+        )
+        .expect("valid config");
+        assert_eq!(
+            config.cleanup_rules,
+            Some(vec![
+                CleanupRule {
+                    name: "target".into(),
+                    siblings: vec!["Cargo.toml".into()],
+                },
+                CleanupRule {
+                    name: "cmake-build-*".into(),
+                    siblings: Vec::new(),
+                },
+            ])
+        );
+
+        let config: Config = toml::from_str("").expect("valid config");
+        assert_eq!(config.cleanup_rules, None, "the built-in rules are used");
+    }
 }
@@ -0,0 +1,1040 @@
+//! Named actions of the interactive UI and the key chords bound to them.
+//!
+//! Every pane has its own table of bindings, with [`Pane::Global`] bindings taking precedence
+//! over those of the focussed pane, except for [`Pane::Prompt`]. The defaults mirror the classic
+//! key layout and are extended or overridden by the `[keys.<pane>]` tables of the configuration
+//! file.
+
+use anyhow::{Result, anyhow, bail};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// A pane of the interactive UI with its own set of key bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    /// Bindings that apply no matter which pane is focussed.
+    Global,
+    /// The list of entries.
+    Main,
+    /// The list of marked entries.
+    Mark,
+    /// The help pane.
+    Help,
+    /// The treemap, whose bindings take precedence over those of [`Pane::Main`] while it is
+    /// focussed.
+    Treemap,
+    /// The jump and glob prompts, whose bindings take precedence over global ones as keys are
+    /// typed into them.
+    Prompt,
+}
+
+impl Pane {
+    /// The name of the configuration table holding the bindings of this pane.
+    #[must_use]
+    pub fn table_name(self) -> &'static str {
+        match self {
+            Pane::Global => "keys.global",
+            Pane::Main => "keys.main",
+            Pane::Mark => "keys.mark",
+            Pane::Help => "keys.help",
+            Pane::Treemap => "keys.treemap",
+            Pane::Prompt => "keys.prompt",
+        }
+    }
+}
+
+/// Something the interactive UI can do in response to a key chord.
+///
+/// Actions are spelled in kebab-case in the configuration file, like `sort-by-size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    /// Close the focussed pane, or quit the application from the main pane.
+    Quit,
+    /// Quit the application right away.
+    ForceQuit,
+    /// Quit the application to change into the directory in view, see `--cwd-file`.
+    QuitToDirectory,
+    /// Cycle the focus between all open panes.
+    CycleFocus,
+    /// Show or hide the help pane.
+    ToggleHelp,
+    /// Open or close the glob search.
+    ToggleSearch,
+
+    /// Move the selection up by one entry.
+    Up,
+    /// Move the selection down by one entry.
+    Down,
+    /// Move the selection up by a page.
+    PageUp,
+    /// Move the selection down by a page.
+    PageDown,
+    /// Move the selection to the first entry.
+    ToTop,
+    /// Move the selection to the last entry.
+    ToBottom,
+
+    /// Descend into the selected directory, or list the selected small files.
+    Enter,
+    /// Ascend into the parent directory.
+    Ascend,
+    /// Prompt for a path relative to the roots and navigate to it directly.
+    JumpToPath,
+    /// Bookmark the selected entry under a name, or remove its bookmark.
+    Bookmark,
+    /// Show or hide the bookmarks of the roots.
+    ToggleBookmarks,
+    /// Show a second, independently navigated directory side by side, or hide it.
+    ToggleSplit,
+    /// Switch the side of the split view that is navigated.
+    SwitchSplitSide,
+    /// Make the other side of the split view follow the navigated one by relative path, or stop.
+    ToggleSplitSync,
+    /// Open the selected entry with its associated program.
+    Open,
+    /// Prompt for the name of a configured command to run on the selected or marked entries.
+    RunCommand,
+    /// Start a shell in the selected directory, and refresh it once the shell exits.
+    OpenShell,
+    /// Copy the absolute path of the selected entry to the clipboard.
+    CopyPath,
+    /// Toggle the mark of the selected entry.
+    ToggleMark,
+    /// Toggle the mark of the selected entry and move down.
+    ToggleMarkAndAdvance,
+    /// Mark the selected entry for deletion and move down.
+    MarkAndAdvance,
+    /// Toggle the marks of all entries in view.
+    ToggleMarkAll,
+    /// Toggle cleanup-candidate detection.
+    ToggleCleanupCandidates,
+    /// Mark all cleanup candidates in view.
+    MarkCleanupCandidates,
+    /// List all cleanup candidates below the directory in view, like search results.
+    SweepCleanupCandidates,
+    /// Toggle Git-ignored entry detection.
+    ToggleGitignored,
+    /// Mark all Git-ignored entries in view.
+    MarkGitignored,
+    /// Display entries as a tree in which directories can be expanded, or as a list.
+    ToggleTreeMode,
+    /// Expand or collapse the selected directory in the tree display.
+    ToggleExpansion,
+    /// Show or hide entries whose name starts with a dot.
+    ToggleHidden,
+    /// Refresh the selected entry.
+    Refresh,
+    /// Refresh all entries in view.
+    RefreshAll,
+    /// Toggle sorting by size.
+    SortBySize,
+    /// Toggle sorting by modification time.
+    SortByMtime,
+    /// Show the modification time, or cycle through modification time sort modes.
+    CycleMtimeSortMode,
+    /// Toggle sorting by entry count.
+    SortByCount,
+    /// Toggle sorting by name.
+    SortByName,
+    /// Toggle sorting by owner.
+    SortByOwner,
+    /// Show or hide the owner column.
+    ToggleOwnerColumn,
+    /// Show or hide the group column.
+    ToggleGroupColumn,
+    /// Show or hide the permissions column.
+    TogglePermissionsColumn,
+    /// Show or hide the bytes and entries owned by each user below the current directory.
+    ToggleOwnerUsage,
+    /// Show or hide the entries of the current directory as rectangles proportional to their size.
+    ToggleTreemap,
+    /// Show or hide the metadata and contents of the selected entry.
+    TogglePreview,
+    /// Show or hide the tracked, untracked, ignored and precious bytes of each git repository
+    /// below the scanned directories.
+    ToggleRepositories,
+    /// Show or hide the entry count column.
+    ToggleCountColumn,
+    /// Show or hide the column with the size not in use and the ratio of disk usage to apparent size.
+    ToggleAlternateSizeColumn,
+    /// Switch between the apparent size and the disk usage without traversing again.
+    ToggleApparentSize,
+    /// Cycle through percentage and bar display options.
+    CycleByteVisualization,
+
+    /// Remove the selected entry from the marked entries.
+    Unmark,
+    /// Remove all entries from the marked entries.
+    UnmarkAll,
+    /// Copy a line with the size and path of each marked entry to the clipboard.
+    CopyMarked,
+    /// Permanently delete all marked entries.
+    Delete,
+    /// Move all marked entries to the trash bin, if supported by this build.
+    Trash,
+
+    /// Select the rectangle above the selected one in the treemap.
+    TreemapUp,
+    /// Select the rectangle below the selected one in the treemap.
+    TreemapDown,
+    /// Select the rectangle left of the selected one in the treemap.
+    TreemapLeft,
+    /// Select the rectangle right of the selected one in the treemap.
+    TreemapRight,
+
+    /// Jump, name the bookmark, run the command or search with the input of the prompt.
+    Submit,
+    /// Complete the path or command name typed into the jump prompt.
+    Complete,
+}
+
+impl Action {
+    /// The name of this action as used in the configuration file.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        use Action::*;
+        match self {
+            Quit => "quit",
+            ForceQuit => "force-quit",
+            QuitToDirectory => "quit-to-directory",
+            CycleFocus => "cycle-focus",
+            ToggleHelp => "toggle-help",
+            ToggleSearch => "toggle-search",
+            Up => "up",
+            Down => "down",
+            PageUp => "page-up",
+            PageDown => "page-down",
+            ToTop => "to-top",
+            ToBottom => "to-bottom",
+            Enter => "enter",
+            Ascend => "ascend",
+            JumpToPath => "jump-to-path",
+            Bookmark => "bookmark",
+            ToggleBookmarks => "toggle-bookmarks",
+            ToggleSplit => "toggle-split",
+            SwitchSplitSide => "switch-split-side",
+            ToggleSplitSync => "toggle-split-sync",
+            Open => "open",
+            RunCommand => "run-command",
+            OpenShell => "open-shell",
+            CopyPath => "copy-path",
+            ToggleMark => "toggle-mark",
+            ToggleMarkAndAdvance => "toggle-mark-and-advance",
+            MarkAndAdvance => "mark-and-advance",
+            ToggleMarkAll => "toggle-mark-all",
+            ToggleCleanupCandidates => "toggle-cleanup-candidates",
+            MarkCleanupCandidates => "mark-cleanup-candidates",
+            SweepCleanupCandidates => "sweep-cleanup-candidates",
+            ToggleGitignored => "toggle-gitignored",
+            MarkGitignored => "mark-gitignored",
+            ToggleHidden => "toggle-hidden",
+            ToggleTreeMode => "toggle-tree-mode",
+            ToggleExpansion => "toggle-expansion",
+            Refresh => "refresh",
+            RefreshAll => "refresh-all",
+            SortBySize => "sort-by-size",
+            SortByMtime => "sort-by-mtime",
+            CycleMtimeSortMode => "cycle-mtime-sort-mode",
+            SortByCount => "sort-by-count",
+            SortByName => "sort-by-name",
+            SortByOwner => "sort-by-owner",
+            ToggleOwnerColumn => "toggle-owner-column",
+            ToggleGroupColumn => "toggle-group-column",
+            TogglePermissionsColumn => "toggle-permissions-column",
+            ToggleOwnerUsage => "toggle-owner-usage",
+            ToggleTreemap => "toggle-treemap",
+            TogglePreview => "toggle-preview",
+            ToggleRepositories => "toggle-repositories",
+            ToggleCountColumn => "toggle-count-column",
+            ToggleAlternateSizeColumn => "toggle-alternate-size-column",
+            ToggleApparentSize => "toggle-apparent-size",
+            CycleByteVisualization => "cycle-byte-visualization",
+            Unmark => "unmark",
+            UnmarkAll => "unmark-all",
+            CopyMarked => "copy-marked",
+            Delete => "delete",
+            Trash => "trash",
+            TreemapUp => "treemap-up",
+            TreemapDown => "treemap-down",
+            TreemapLeft => "treemap-left",
+            TreemapRight => "treemap-right",
+            Submit => "submit",
+            Complete => "complete",
+        }
+    }
+
+    /// Return `true` if this action can be bound in `pane`.
+    #[must_use]
+    pub fn is_available_in(self, pane: Pane) -> bool {
+        use Action::*;
+        match self {
+            Quit | ForceQuit | CycleFocus | ToggleHelp | ToggleSearch => pane == Pane::Global,
+            Up | Down | PageUp | PageDown | ToTop | ToBottom => {
+                matches!(pane, Pane::Main | Pane::Mark | Pane::Help)
+            }
+            Enter
+            | Ascend
+            | JumpToPath
+            | Bookmark
+            | ToggleBookmarks
+            | ToggleSplit
+            | SwitchSplitSide
+            | ToggleSplitSync
+            | Open
+            | RunCommand
+            | OpenShell
+            | QuitToDirectory
+            | CopyPath
+            | ToggleMark
+            | ToggleMarkAndAdvance
+            | MarkAndAdvance
+            | ToggleMarkAll
+            | ToggleCleanupCandidates
+            | MarkCleanupCandidates
+            | SweepCleanupCandidates
+            | ToggleGitignored
+            | MarkGitignored
+            | ToggleHidden
+            | ToggleTreeMode
+            | ToggleExpansion
+            | Refresh
+            | RefreshAll
+            | SortBySize
+            | SortByMtime
+            | CycleMtimeSortMode
+            | SortByCount
+            | SortByName
+            | SortByOwner
+            | ToggleOwnerColumn
+            | ToggleGroupColumn
+            | TogglePermissionsColumn
+            | ToggleOwnerUsage
+            | ToggleTreemap
+            | TogglePreview
+            | ToggleRepositories
+            | ToggleCountColumn
+            | ToggleAlternateSizeColumn
+            | ToggleApparentSize
+            | CycleByteVisualization => pane == Pane::Main,
+            Unmark | UnmarkAll | CopyMarked | Delete | Trash => pane == Pane::Mark,
+            TreemapUp | TreemapDown | TreemapLeft | TreemapRight => pane == Pane::Treemap,
+            Submit | Complete => pane == Pane::Prompt,
+        }
+    }
+}
+
+/// A key without its modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[expect(missing_docs, reason = "the variants are named after their keys")]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8),
+}
+
+/// A key along with the modifiers that have to be held while pressing it.
+///
+/// The `Shift` modifier isn't tracked as it's already part of the character, like in `G`.
+///
+/// Chords are written like `j`, `ctrl-d`, `alt+<Enter>` or `page-down` in the configuration
+/// file, and are displayed like `Ctrl + d` or `<Page Down>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    /// The key to press.
+    pub key: Key,
+    /// Whether `Ctrl` is held.
+    pub ctrl: bool,
+    /// Whether `Alt` is held.
+    pub alt: bool,
+}
+
+impl KeyChord {
+    /// A chord of `key` without modifiers.
+    #[must_use]
+    pub const fn plain(key: Key) -> Self {
+        KeyChord {
+            key,
+            ctrl: false,
+            alt: false,
+        }
+    }
+
+    /// A chord of `key` with `Ctrl` held.
+    #[must_use]
+    pub const fn ctrl(key: Key) -> Self {
+        KeyChord {
+            key,
+            ctrl: true,
+            alt: false,
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut ctrl = false;
+        let mut alt = false;
+        let mut rest = input.trim();
+        while let Some((modifier, tail)) = rest.split_once(['-', '+']) {
+            let tail = tail.trim();
+            if tail.is_empty() {
+                break;
+            }
+            match modifier.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => ctrl = true,
+                "alt" => alt = true,
+                _ => break,
+            }
+            rest = tail;
+        }
+
+        let mut chars = rest.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(c), None) if ctrl => Key::Char(c.to_ascii_lowercase()),
+            (Some(c), None) => Key::Char(c),
+            _ => {
+                let name = rest
+                    .strip_prefix('<')
+                    .and_then(|name| name.strip_suffix('>'))
+                    .unwrap_or(rest)
+                    .replace(['-', '_', ' '], "")
+                    .to_ascii_lowercase();
+                match name.as_str() {
+                    "space" => Key::Char(' '),
+                    "enter" | "return" => Key::Enter,
+                    "esc" | "escape" => Key::Esc,
+                    "tab" => Key::Tab,
+                    "backspace" => Key::Backspace,
+                    "delete" | "del" => Key::Delete,
+                    "insert" | "ins" => Key::Insert,
+                    "left" => Key::Left,
+                    "right" => Key::Right,
+                    "up" => Key::Up,
+                    "down" => Key::Down,
+                    "home" => Key::Home,
+                    "end" => Key::End,
+                    "pageup" | "pgup" => Key::PageUp,
+                    "pagedown" | "pgdn" => Key::PageDown,
+                    _ => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n @ 1..=24) => Key::F(n),
+                        _ => bail!("unknown key {rest:?} in key chord {input:?}"),
+                    },
+                }
+            }
+        };
+        Ok(KeyChord { key, ctrl, alt })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl + ")?;
+        }
+        if self.alt {
+            f.write_str("Alt + ")?;
+        }
+        let name = match self.key {
+            Key::Char(' ') => "<Space>",
+            Key::Char(c) => return write!(f, "{c}"),
+            Key::F(n) => return write!(f, "<F{n}>"),
+            Key::Enter => "<Enter>",
+            Key::Esc => "<Esc>",
+            Key::Tab => "<Tab>",
+            Key::Backspace => "<Backspace>",
+            Key::Delete => "<Delete>",
+            Key::Insert => "<Insert>",
+            Key::Left => "<Left>",
+            Key::Right => "<Right>",
+            Key::Up => "<Up>",
+            Key::Down => "<Down>",
+            Key::Home => "<Home>",
+            Key::End => "<End>",
+            Key::PageUp => "<Page Up>",
+            Key::PageDown => "<Page Down>",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The key chords bound to [actions](Action), per [pane](Pane).
+///
+/// Bindings keep the order in which they were added, which is the order in which they are
+/// shown in the help pane.
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    global: Vec<(KeyChord, Action)>,
+    main: Vec<(KeyChord, Action)>,
+    mark: Vec<(KeyChord, Action)>,
+    help: Vec<(KeyChord, Action)>,
+    treemap: Vec<(KeyChord, Action)>,
+    prompt: Vec<(KeyChord, Action)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use Action::*;
+        let key = KeyChord::plain;
+        let char = |c| KeyChord::plain(Key::Char(c));
+        let ctrl = |c| KeyChord::ctrl(Key::Char(c));
+        let navigation = |to_top: &[KeyChord], to_bottom: &[KeyChord]| {
+            let mut bindings: Vec<_> = to_top.iter().map(|&chord| (chord, ToTop)).collect();
+            bindings.extend(to_bottom.iter().map(|&chord| (chord, ToBottom)));
+            bindings.extend([
+                (ctrl('u'), PageUp),
+                (key(Key::PageUp), PageUp),
+                (char('k'), Up),
+                (key(Key::Up), Up),
+                (char('j'), Down),
+                (key(Key::Down), Down),
+                (ctrl('d'), PageDown),
+                (key(Key::PageDown), PageDown),
+            ]);
+            bindings
+        };
+
+        let global = vec![
+            (char('q'), Quit),
+            (key(Key::Esc), Quit),
+            (key(Key::Tab), CycleFocus),
+            (char('?'), ToggleHelp),
+            (char('/'), ToggleSearch),
+            (ctrl('c'), ForceQuit),
+        ];
+
+        let mut main = vec![
+            (char('o'), Enter),
+            (char('l'), Enter),
+            (key(Key::Enter), Enter),
+            (key(Key::Right), Enter),
+            (char('u'), Ascend),
+            (char('h'), Ascend),
+            (key(Key::Left), Ascend),
+            (key(Key::Backspace), Ascend),
+            (char(':'), JumpToPath),
+            (char('b'), Bookmark),
+            (char('B'), ToggleBookmarks),
+            (char('|'), ToggleSplit),
+            (char('\\'), SwitchSplitSide),
+            (char('='), ToggleSplitSync),
+        ];
+        main.extend(navigation(
+            &[char('H'), key(Key::Home)],
+            &[char('G'), key(Key::End)],
+        ));
+        main.extend([
+            (char('s'), SortBySize),
+            (char('m'), SortByMtime),
+            (char('M'), CycleMtimeSortMode),
+            (char('c'), SortByCount),
+            (char('C'), ToggleCountColumn),
+            (char('n'), SortByName),
+            (char('w'), SortByOwner),
+            (char('W'), ToggleOwnerColumn),
+            (char('Y'), ToggleGroupColumn),
+            (char('P'), TogglePermissionsColumn),
+            (char('U'), ToggleOwnerUsage),
+            (char('v'), ToggleTreemap),
+            (char('p'), TogglePreview),
+            (char('E'), ToggleRepositories),
+            (char('Z'), ToggleAlternateSizeColumn),
+            (char('A'), ToggleApparentSize),
+            (char('g'), CycleByteVisualization),
+            (char('S'), CycleByteVisualization),
+            (char('O'), Open),
+            (char('!'), RunCommand),
+            (char('$'), OpenShell),
+            (char('Q'), QuitToDirectory),
+            (char('y'), CopyPath),
+            (char('d'), ToggleMarkAndAdvance),
+            (char('x'), MarkAndAdvance),
+            (char(' '), ToggleMark),
+            (char('X'), MarkCleanupCandidates),
+            (char('t'), ToggleCleanupCandidates),
+            (char('F'), SweepCleanupCandidates),
+            (char('I'), MarkGitignored),
+            (char('i'), ToggleGitignored),
+            (char('.'), ToggleHidden),
+            (char('T'), ToggleTreeMode),
+            (char('e'), ToggleExpansion),
+            (char('a'), ToggleMarkAll),
+            (char('r'), Refresh),
+            (char('R'), RefreshAll),
+        ]);
+
+        let mut mark = vec![
+            (char('x'), Unmark),
+            (char('d'), Unmark),
+            (char(' '), Unmark),
+            (char('a'), UnmarkAll),
+            (char('y'), CopyMarked),
+            (ctrl('r'), Delete),
+        ];
+        if cfg!(feature = "trash-move") {
+            mark.push((ctrl('t'), Trash));
+        }
+        mark.extend(navigation(&[char('H')], &[char('G')]));
+
+        KeyBindings {
+            global,
+            main,
+            mark,
+            help: navigation(&[char('H')], &[char('G')]),
+            treemap: vec![
+                (key(Key::Up), TreemapUp),
+                (key(Key::Down), TreemapDown),
+                (key(Key::Left), TreemapLeft),
+                (key(Key::Right), TreemapRight),
+            ],
+            prompt: vec![(key(Key::Enter), Submit), (key(Key::Tab), Complete)],
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Return the action bound to `chord` in `pane`, if there is one.
+    ///
+    /// Note that [`Pane::Global`] bindings are not consulted unless `pane` is global.
+    #[must_use]
+    pub fn action(&self, pane: Pane, chord: KeyChord) -> Option<Action> {
+        self.bindings(pane)
+            .iter()
+            .find_map(|&(bound, action)| (bound == chord).then_some(action))
+    }
+
+    /// Return all chords bound to `action` in `pane`, in the order they were bound.
+    pub fn chords(&self, pane: Pane, action: Action) -> impl Iterator<Item = KeyChord> + '_ {
+        self.bindings(pane)
+            .iter()
+            .filter_map(move |&(chord, bound)| (bound == action).then_some(chord))
+    }
+
+    fn bindings(&self, pane: Pane) -> &[(KeyChord, Action)] {
+        match pane {
+            Pane::Global => &self.global,
+            Pane::Main => &self.main,
+            Pane::Mark => &self.mark,
+            Pane::Help => &self.help,
+            Pane::Treemap => &self.treemap,
+            Pane::Prompt => &self.prompt,
+        }
+    }
+
+    fn bind(&mut self, pane: Pane, chord: KeyChord, binding: Binding) {
+        let bindings = match pane {
+            Pane::Global => &mut self.global,
+            Pane::Main => &mut self.main,
+            Pane::Mark => &mut self.mark,
+            Pane::Help => &mut self.help,
+            Pane::Treemap => &mut self.treemap,
+            Pane::Prompt => &mut self.prompt,
+        };
+        match binding {
+            Binding::Action(action) => {
+                match bindings.iter_mut().find(|(bound, _)| *bound == chord) {
+                    Some((_, bound_action)) => *bound_action = action,
+                    None => bindings.push((chord, action)),
+                }
+            }
+            Binding::Unbound => bindings.retain(|(bound, _)| *bound != chord),
+        }
+    }
+}
+
+/// What a chord is bound to in the configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Binding {
+    /// Run the action when the chord is pressed.
+    Action(Action),
+    /// Remove the default binding of the chord, spelled `"none"`.
+    Unbound,
+}
+
+impl Binding {
+    fn name(self) -> &'static str {
+        match self {
+            Binding::Action(action) => action.name(),
+            Binding::Unbound => "none",
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name == "none" {
+            return Ok(Binding::Unbound);
+        }
+        Action::deserialize(serde::de::value::StringDeserializer::<D::Error>::new(name))
+            .map(Binding::Action)
+    }
+}
+
+/// The `[keys]` table as it is written in the configuration file.
+#[derive(Deserialize)]
+#[serde(default)]
+pub(super) struct RawKeysConfig {
+    esc_navigates_back: bool,
+    global: BTreeMap<String, Binding>,
+    main: BTreeMap<String, Binding>,
+    mark: BTreeMap<String, Binding>,
+    help: BTreeMap<String, Binding>,
+    treemap: BTreeMap<String, Binding>,
+    prompt: BTreeMap<String, Binding>,
+}
+
+impl Default for RawKeysConfig {
+    fn default() -> Self {
+        RawKeysConfig {
+            esc_navigates_back: super::default_esc_navigates_back(),
+            global: BTreeMap::default(),
+            main: BTreeMap::default(),
+            mark: BTreeMap::default(),
+            help: BTreeMap::default(),
+            treemap: BTreeMap::default(),
+            prompt: BTreeMap::default(),
+        }
+    }
+}
+
+impl TryFrom<RawKeysConfig> for super::KeysConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawKeysConfig) -> Result<Self> {
+        let mut bindings = KeyBindings::default();
+        for (pane, table) in [
+            (Pane::Global, &raw.global),
+            (Pane::Main, &raw.main),
+            (Pane::Mark, &raw.mark),
+            (Pane::Help, &raw.help),
+            (Pane::Treemap, &raw.treemap),
+            (Pane::Prompt, &raw.prompt),
+        ] {
+            let mut configured = Vec::<(KeyChord, &str, Binding)>::new();
+            for (spelling, &binding) in table {
+                let chord: KeyChord = spelling
+                    .parse()
+                    .map_err(|err| anyhow!("{}: {err}", pane.table_name()))?;
+                if let Binding::Action(action) = binding
+                    && !action.is_available_in(pane)
+                {
+                    bail!(
+                        "{}: {spelling:?} is bound to '{}', which isn't available in this pane",
+                        pane.table_name(),
+                        action.name()
+                    );
+                }
+                if let Some((_, other_spelling, other_binding)) = configured
+                    .iter()
+                    .find(|(other, _, other_binding)| *other == chord && *other_binding != binding)
+                {
+                    bail!(
+                        "{}: {other_spelling:?} and {spelling:?} both denote {chord}, but are bound to '{}' and '{}'",
+                        pane.table_name(),
+                        other_binding.name(),
+                        binding.name()
+                    );
+                }
+                configured.push((chord, spelling, binding));
+                bindings.bind(pane, chord, binding);
+            }
+        }
+
+        // Only validated now, so chords can be unbound in one table and bound in another.
+        // Prompt bindings take precedence over global ones, so they can't be shadowed.
+        for pane in [Pane::Main, Pane::Mark, Pane::Help, Pane::Treemap] {
+            for &(chord, action) in bindings.bindings(pane) {
+                if let Some(global_action) = bindings.action(Pane::Global, chord) {
+                    bail!(
+                        "{chord} is bound to '{}' in {} and to '{}' in {}, but global bindings take precedence; bind one of them to another key or to \"none\"",
+                        global_action.name(),
+                        Pane::Global.table_name(),
+                        action.name(),
+                        pane.table_name()
+                    );
+                }
+            }
+        }
+
+        Ok(super::KeysConfig {
+            esc_navigates_back: raw.esc_navigates_back,
+            bindings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    fn chord(input: &str) -> KeyChord {
+        input.parse().expect("valid chord")
+    }
+
+    #[test]
+    fn chords_parse_in_various_spellings_and_display_like_the_help_pane() {
+        assert_eq!(chord("j"), KeyChord::plain(Key::Char('j')));
+        assert_eq!(chord("G"), KeyChord::plain(Key::Char('G')));
+        assert_eq!(chord("-"), KeyChord::plain(Key::Char('-')));
+        assert_eq!(chord("ctrl-d"), KeyChord::ctrl(Key::Char('d')));
+        assert_eq!(chord("Ctrl+D"), chord("ctrl-d"), "ctrl normalizes letters");
+        assert_eq!(chord("Ctrl + d"), chord("ctrl-d"));
+        assert_eq!(chord("ctrl--"), KeyChord::ctrl(Key::Char('-')));
+        assert_eq!(chord("page-down"), KeyChord::plain(Key::PageDown));
+        assert_eq!(chord("<Page Down>"), KeyChord::plain(Key::PageDown));
+        assert_eq!(chord("space"), KeyChord::plain(Key::Char(' ')));
+        assert_eq!(chord("F5"), KeyChord::plain(Key::F(5)));
+        assert_eq!(
+            chord("alt-ctrl-enter"),
+            KeyChord {
+                key: Key::Enter,
+                ctrl: true,
+                alt: true
+            }
+        );
+
+        for display in ["Ctrl + d", "<Page Down>", "<Space>", "Alt + <Enter>", "G"] {
+            assert_eq!(
+                chord(display).to_string(),
+                display,
+                "displayed chords parse back to themselves"
+            );
+        }
+
+        let err = "ctrl-nope".parse::<KeyChord>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"unknown key "nope" in key chord "ctrl-nope""#
+        );
+    }
+
+    #[test]
+    fn defaults_bind_the_classic_keys() {
+        let bindings = KeyBindings::default();
+        assert_eq!(
+            bindings.action(Pane::Main, chord("s")),
+            Some(Action::SortBySize)
+        );
+        assert_eq!(
+            bindings.action(Pane::Mark, chord("ctrl-r")),
+            Some(Action::Delete)
+        );
+        assert_eq!(bindings.action(Pane::Help, chord("s")), None);
+        assert_eq!(
+            bindings
+                .chords(Pane::Main, Action::Enter)
+                .map(|chord| chord.to_string())
+                .collect::<Vec<_>>(),
+            ["o", "l", "<Enter>", "<Right>"],
+            "chords are kept in the order they were bound in"
+        );
+    }
+
+    #[test]
+    fn prompt_bindings_may_take_global_chords() {
+        let config: Config = toml::from_str(
+            r#"
+            [keys.prompt]
+            "tab" = "submit"
+            "ctrl-c" = "complete"
+            "#,
+        )
+        .expect("prompt bindings take precedence over global ones");
+
+        let bindings = &config.keys.bindings;
+        assert_eq!(
+            bindings.action(Pane::Prompt, chord("tab")),
+            Some(Action::Submit)
+        );
+        assert_eq!(
+            bindings.action(Pane::Global, chord("tab")),
+            Some(Action::CycleFocus)
+        );
+        assert_eq!(
+            bindings.action(Pane::Treemap, chord("left")),
+            Some(Action::TreemapLeft)
+        );
+    }
+
+    #[test]
+    fn configured_bindings_extend_and_override_the_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+            [keys.main]
+            "ctrl-n" = "down"
+            "s" = "sort-by-name"
+            "#,
+        )
+        .expect("valid config");
+
+        let bindings = &config.keys.bindings;
+        assert!(config.keys.esc_navigates_back, "defaults are retained");
+        assert_eq!(
+            bindings.action(Pane::Main, chord("ctrl-n")),
+            Some(Action::Down)
+        );
+        assert_eq!(
+            bindings.action(Pane::Main, chord("j")),
+            Some(Action::Down),
+            "previous bindings stay"
+        );
+        assert_eq!(
+            bindings.action(Pane::Main, chord("s")),
+            Some(Action::SortByName)
+        );
+        assert_eq!(bindings.chords(Pane::Main, Action::SortBySize).count(), 0);
+    }
+
+    #[test]
+    fn chords_bound_to_none_lose_their_default_binding() {
+        let config: Config = toml::from_str(
+            r#"
+            [keys.global]
+            "q" = "none"
+            "ctrl-q" = "quit"
+            "/" = "none"
+
+            [keys.main]
+            "/" = "sort-by-name"
+            "#,
+        )
+        .expect("chords are unbound before bindings are validated");
+
+        let bindings = &config.keys.bindings;
+        assert_eq!(bindings.action(Pane::Global, chord("q")), None);
+        assert_eq!(
+            bindings.action(Pane::Global, chord("ctrl-q")),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            bindings.action(Pane::Main, chord("/")),
+            Some(Action::SortByName),
+            "chords freed from global bindings can be used in panes"
+        );
+        assert_eq!(
+            bindings.chords(Pane::Global, Action::ToggleSearch).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn global_bindings_can_take_chords_unbound_in_panes() {
+        let config: Config = toml::from_str(
+            r#"
+            [keys.global]
+            "d" = "quit"
+
+            [keys.main]
+            "d" = "none"
+
+            [keys.mark]
+            "d" = "none"
+            "#,
+        )
+        .expect("no pane binding is shadowed");
+
+        let bindings = &config.keys.bindings;
+        assert_eq!(
+            bindings.action(Pane::Global, chord("d")),
+            Some(Action::Quit)
+        );
+        assert_eq!(bindings.action(Pane::Main, chord("d")), None);
+        assert_eq!(bindings.action(Pane::Mark, chord("d")), None);
+        assert_eq!(
+            bindings.action(Pane::Mark, chord("x")),
+            Some(Action::Unmark),
+            "other chords of the action remain bound"
+        );
+    }
+
+    #[test]
+    fn conflicts_are_rejected_with_clear_errors() {
+        let error = |toml: &str| {
+            toml::from_str::<Config>(toml)
+                .expect_err("invalid config")
+                .message()
+                .to_owned()
+        };
+        assert_eq!(
+            error(
+                r#"
+                [keys.main]
+                "ctrl-n" = "down"
+                "Ctrl+N" = "up"
+                "#
+            ),
+            r#"keys.main: "Ctrl+N" and "ctrl-n" both denote Ctrl + n, but are bound to 'up' and 'down'"#
+        );
+        assert_eq!(
+            error(
+                r#"
+                [keys.mark]
+                "s" = "sort-by-size"
+                "#
+            ),
+            r#"keys.mark: "s" is bound to 'sort-by-size', which isn't available in this pane"#
+        );
+        assert_eq!(
+            error(
+                r#"
+                [keys.main]
+                "q" = "ascend"
+                "#
+            ),
+            "q is bound to 'quit' in keys.global and to 'ascend' in keys.main, but global bindings take precedence; bind one of them to another key or to \"none\""
+        );
+        assert_eq!(
+            error(
+                r#"
+                [keys.global]
+                "s" = "toggle-help"
+                "#
+            ),
+            "s is bound to 'toggle-help' in keys.global and to 'sort-by-size' in keys.main, but global bindings take precedence; bind one of them to another key or to \"none\""
+        );
+        assert_eq!(
+            error(
+                r#"
+                [keys.treemap]
+                "tab" = "treemap-up"
+                "#
+            ),
+            "<Tab> is bound to 'cycle-focus' in keys.global and to 'treemap-up' in keys.treemap, but global bindings take precedence; bind one of them to another key or to \"none\""
+        );
+        assert_eq!(
+            error(
+                r#"
+                [keys.help]
+                "hyper-x" = "up"
+                "#
+            ),
+            r#"keys.help: unknown key "hyper-x" in key chord "hyper-x""#
+        );
+        assert!(
+            error(
+                r#"
+                [keys.main]
+                "d" = "delete-me"
+                "#
+            )
+            .contains("unknown variant `delete-me`")
+        );
+    }
+}
@@ -0,0 +1,603 @@
+//! Named styles of the interactive UI, grouped into themes.
+//!
+//! A theme is one of the built-in [themes](ThemeName) with any of its [styles](ThemeElement)
+//! replaced by those of the `[theme]` table of the configuration file.
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use std::{collections::BTreeMap, str::FromStr, sync::OnceLock};
+
+/// Return `true` if the `NO_COLOR` environment variable asks for output without colors.
+/// It is read only once, just like the terminal backend does.
+///
+/// See <https://no-color.org> for details.
+#[must_use]
+pub fn no_color() -> bool {
+    static NO_COLOR: OnceLock<bool> = OnceLock::new();
+    *NO_COLOR.get_or_init(|| std::env::var("NO_COLOR").is_ok_and(|value| !value.is_empty()))
+}
+
+/// A terminal color.
+///
+/// Colors are written by name like `light-blue`, as `#rrggbb`, or as index into the 256-color
+/// palette, like `208`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+#[expect(missing_docs, reason = "the variants are named after their colors")]
+pub enum ThemeColor {
+    Reset,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+    DarkGray,
+    LightRed,
+    LightGreen,
+    LightYellow,
+    LightBlue,
+    LightMagenta,
+    LightCyan,
+    White,
+    Rgb(u8, u8, u8),
+    Indexed(u8),
+}
+
+impl FromStr for ThemeColor {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        use ThemeColor::*;
+        if let Some(hex) = input.strip_prefix('#') {
+            let channel = |range| {
+                hex.get(range)
+                    .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            };
+            return match (hex.len(), channel(0..2), channel(2..4), channel(4..6)) {
+                (6, Some(r), Some(g), Some(b)) => Ok(Rgb(r, g, b)),
+                _ => bail!("invalid color {input:?}, expected a hex color like \"#ffaa00\""),
+            };
+        }
+        if let Ok(index) = input.parse::<u8>() {
+            return Ok(Indexed(index));
+        }
+        Ok(
+            match input
+                .replace(['-', '_', ' '], "")
+                .to_ascii_lowercase()
+                .as_str()
+            {
+                "reset" | "default" => Reset,
+                "black" => Black,
+                "red" => Red,
+                "green" => Green,
+                "yellow" => Yellow,
+                "blue" => Blue,
+                "magenta" => Magenta,
+                "cyan" => Cyan,
+                "gray" | "grey" => Gray,
+                "darkgray" | "darkgrey" => DarkGray,
+                "lightred" => LightRed,
+                "lightgreen" => LightGreen,
+                "lightyellow" => LightYellow,
+                "lightblue" => LightBlue,
+                "lightmagenta" => LightMagenta,
+                "lightcyan" => LightCyan,
+                "white" => White,
+                _ => bail!(
+                    "unknown color {input:?}, expected a color name, a hex color like \"#ffaa00\" or a palette index"
+                ),
+            },
+        )
+    }
+}
+
+impl TryFrom<String> for ThemeColor {
+    type Error = anyhow::Error;
+
+    fn try_from(input: String) -> Result<Self> {
+        input.parse()
+    }
+}
+
+/// The colors and modifiers of one element of the interactive UI.
+///
+/// Unset colors keep the color of what's drawn below, which usually is the terminal default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[expect(missing_docs, reason = "modifiers are named after their effect")]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag toggles an independent text modifier"
+)]
+pub struct StyleSpec {
+    /// The foreground color.
+    pub fg: Option<ThemeColor>,
+    /// The background color.
+    pub bg: Option<ThemeColor>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub reversed: bool,
+    pub crossed_out: bool,
+}
+
+impl StyleSpec {
+    const fn fg(color: ThemeColor) -> Self {
+        StyleSpec {
+            fg: Some(color),
+            ..StyleSpec::NONE
+        }
+    }
+
+    const fn colors(fg: ThemeColor, bg: ThemeColor) -> Self {
+        StyleSpec {
+            fg: Some(fg),
+            bg: Some(bg),
+            ..StyleSpec::NONE
+        }
+    }
+
+    const NONE: StyleSpec = StyleSpec {
+        fg: None,
+        bg: None,
+        bold: false,
+        dim: false,
+        italic: false,
+        underlined: false,
+        reversed: false,
+        crossed_out: false,
+    };
+
+    const fn bold(self) -> Self {
+        StyleSpec { bold: true, ..self }
+    }
+
+    const fn reversed(self) -> Self {
+        StyleSpec {
+            reversed: true,
+            ..self
+        }
+    }
+
+    const fn underlined(self) -> Self {
+        StyleSpec {
+            underlined: true,
+            ..self
+        }
+    }
+}
+
+/// The built-in themes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    /// The classic colors, made for dark terminals.
+    #[default]
+    Default,
+    /// Darker colors for terminals with a light background.
+    Light,
+    /// Bold colors that don't rely on telling red from green, with modifiers to tell states apart.
+    HighContrast,
+    /// No colors at all, only modifiers. Used whenever `NO_COLOR` is set.
+    Monochrome,
+}
+
+/// An element of the interactive UI with its own style, as named in the `[theme]` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[expect(missing_docs, reason = "documented on the fields of `Theme`")]
+pub enum ThemeElement {
+    Header,
+    HeaderMarked,
+    HeaderDanger,
+    Footer,
+    Message,
+    Prompt,
+    PromptKey,
+    Danger,
+    Selection,
+    Border,
+    BorderFocussed,
+    Directory,
+    File,
+    Marked,
+    MarkedFile,
+    Missing,
+    CleanupCandidate,
+    Gitignored,
+    Sparse,
+    Difference,
+    Bar,
+    Highlight,
+}
+
+/// The styles of all elements of the interactive UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// The header line while nothing is marked.
+    pub header: StyleSpec,
+    /// The header line while entries are marked.
+    pub header_marked: StyleSpec,
+    /// The header line while entries are marked and the mark pane is focussed.
+    pub header_danger: StyleSpec,
+    /// The footer line.
+    pub footer: StyleSpec,
+    /// Messages shown in the footer line.
+    pub message: StyleSpec,
+    /// Prompts, like the one to confirm quitting or deleting.
+    pub prompt: StyleSpec,
+    /// Keys mentioned in prompts.
+    pub prompt_key: StyleSpec,
+    /// Keys mentioned in prompts which trigger irreversible operations.
+    pub danger: StyleSpec,
+    /// The selected entry.
+    pub selection: StyleSpec,
+    /// The borders of panes without focus.
+    pub border: StyleSpec,
+    /// The border of the focussed pane.
+    pub border_focussed: StyleSpec,
+    /// Directories.
+    pub directory: StyleSpec,
+    /// Files.
+    pub file: StyleSpec,
+    /// Marked directories.
+    pub marked: StyleSpec,
+    /// Marked files.
+    pub marked_file: StyleSpec,
+    /// Entries that don't exist anymore.
+    pub missing: StyleSpec,
+    /// Cleanup candidates.
+    pub cleanup_candidate: StyleSpec,
+    /// Git-ignored entries, applied on top of their regular style.
+    pub gitignored: StyleSpec,
+    /// Alternate sizes of files that take up much less space on disk than their apparent size,
+    /// like sparse files or those compressed by the filesystem.
+    pub sparse: StyleSpec,
+    /// Entries of a split view whose size differs from their counterpart on the other side.
+    pub difference: StyleSpec,
+    /// The percentage and bar column.
+    pub bar: StyleSpec,
+    /// The sorted column, sizes of marked entries and keys in the help pane.
+    pub highlight: StyleSpec,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::builtin(ThemeName::default())
+    }
+}
+
+impl Theme {
+    /// Return the built-in theme called `name`.
+    #[must_use]
+    pub fn builtin(name: ThemeName) -> Self {
+        use ThemeColor::*;
+        let none = StyleSpec::NONE;
+        let fg = StyleSpec::fg;
+        let colors = StyleSpec::colors;
+        let default = Theme {
+            header: colors(Black, White),
+            header_marked: colors(Black, Yellow),
+            header_danger: colors(Black, LightRed),
+            footer: none.reversed(),
+            message: colors(Yellow, Reset).bold(),
+            prompt: colors(Black, Yellow).bold(),
+            prompt_key: colors(White, Black),
+            danger: colors(LightRed, Black),
+            selection: none.reversed(),
+            border: colors(DarkGray, Reset),
+            border_focussed: none.bold(),
+            directory: fg(Cyan),
+            file: none,
+            marked: fg(Yellow),
+            marked_file: fg(Rgb(176, 126, 0)),
+            missing: fg(Red),
+            cleanup_candidate: fg(Magenta),
+            gitignored: StyleSpec { dim: true, ..none },
+            sparse: fg(LightBlue),
+            difference: fg(LightYellow).bold(),
+            bar: none,
+            highlight: fg(Green),
+        };
+        match name {
+            ThemeName::Default => default,
+            ThemeName::Light => {
+                let amber = Rgb(160, 100, 0);
+                Theme {
+                    message: colors(Blue, Reset).bold(),
+                    border: colors(Gray, Reset),
+                    directory: fg(Blue),
+                    marked: fg(amber).bold(),
+                    marked_file: fg(amber),
+                    sparse: fg(Blue),
+                    difference: fg(Rgb(160, 0, 160)).bold(),
+                    highlight: fg(Rgb(0, 128, 0)),
+                    ..default
+                }
+            }
+            ThemeName::HighContrast => {
+                let orange = Rgb(255, 176, 0);
+                Theme {
+                    header: colors(Black, White).bold(),
+                    header_marked: colors(Black, orange).bold(),
+                    header_danger: colors(White, Blue).bold(),
+                    danger: colors(orange, Black).bold(),
+                    border: colors(Gray, Reset),
+                    directory: fg(LightBlue).bold(),
+                    marked: fg(orange).bold().underlined(),
+                    marked_file: fg(orange).underlined(),
+                    missing: StyleSpec {
+                        crossed_out: true,
+                        ..none.bold()
+                    },
+                    cleanup_candidate: StyleSpec {
+                        italic: true,
+                        ..fg(LightCyan)
+                    },
+                    gitignored: StyleSpec {
+                        dim: true,
+                        italic: true,
+                        ..none
+                    },
+                    sparse: fg(LightCyan).bold(),
+                    difference: StyleSpec {
+                        italic: true,
+                        ..fg(LightYellow).bold()
+                    },
+                    highlight: fg(LightBlue).bold(),
+                    ..default
+                }
+            }
+            ThemeName::Monochrome => Theme {
+                header: none.reversed(),
+                header_marked: none.reversed().bold(),
+                header_danger: none.reversed().bold().underlined(),
+                footer: none.reversed(),
+                message: none.bold(),
+                prompt: none.reversed().bold(),
+                prompt_key: none.bold(),
+                danger: none.bold().underlined(),
+                selection: none.reversed(),
+                border: StyleSpec { dim: true, ..none },
+                border_focussed: none.bold(),
+                directory: none.bold(),
+                file: none,
+                marked: none.bold().underlined(),
+                marked_file: none.underlined(),
+                missing: StyleSpec {
+                    crossed_out: true,
+                    ..none
+                },
+                cleanup_candidate: StyleSpec {
+                    italic: true,
+                    ..none
+                },
+                gitignored: StyleSpec { dim: true, ..none },
+                sparse: StyleSpec {
+                    italic: true,
+                    ..none.bold()
+                },
+                difference: StyleSpec {
+                    italic: true,
+                    ..none.underlined()
+                },
+                bar: none,
+                highlight: none.bold(),
+            },
+        }
+    }
+
+    fn style_mut(&mut self, element: ThemeElement) -> &mut StyleSpec {
+        use ThemeElement::*;
+        match element {
+            Header => &mut self.header,
+            HeaderMarked => &mut self.header_marked,
+            HeaderDanger => &mut self.header_danger,
+            Footer => &mut self.footer,
+            Message => &mut self.message,
+            Prompt => &mut self.prompt,
+            PromptKey => &mut self.prompt_key,
+            Danger => &mut self.danger,
+            Selection => &mut self.selection,
+            Border => &mut self.border,
+            BorderFocussed => &mut self.border_focussed,
+            Directory => &mut self.directory,
+            File => &mut self.file,
+            Marked => &mut self.marked,
+            MarkedFile => &mut self.marked_file,
+            Missing => &mut self.missing,
+            CleanupCandidate => &mut self.cleanup_candidate,
+            Gitignored => &mut self.gitignored,
+            Sparse => &mut self.sparse,
+            Difference => &mut self.difference,
+            Bar => &mut self.bar,
+            Highlight => &mut self.highlight,
+        }
+    }
+}
+
+/// The `[theme]` table: a built-in theme and the styles replacing some of its own.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    /// The built-in theme to start from.
+    ///
+    /// Default: `default`.
+    pub name: ThemeName,
+
+    /// Styles replacing those of the built-in theme, like `directory = { fg = "blue", bold = true }`.
+    #[serde(flatten)]
+    pub styles: BTreeMap<ThemeElement, StyleSpec>,
+}
+
+impl ThemeConfig {
+    /// Return the theme to render with.
+    ///
+    /// If `no_color` is set, the configured styles are applied to the monochrome theme instead
+    /// and stripped of their colors.
+    #[must_use]
+    pub fn theme(&self, no_color: bool) -> Theme {
+        let mut theme = Theme::builtin(if no_color {
+            ThemeName::Monochrome
+        } else {
+            self.name
+        });
+        for (&element, &style) in &self.styles {
+            *theme.style_mut(element) = if no_color {
+                StyleSpec {
+                    fg: None,
+                    bg: None,
+                    ..style
+                }
+            } else {
+                style
+            };
+        }
+        theme
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    #[test]
+    fn colors_parse_from_names_hex_and_palette_indices() {
+        let color = |input: &str| input.parse::<ThemeColor>().expect("valid color");
+        assert_eq!(color("light-blue"), ThemeColor::LightBlue);
+        assert_eq!(color("DarkGrey"), ThemeColor::DarkGray);
+        assert_eq!(color("#ffaa00"), ThemeColor::Rgb(255, 170, 0));
+        assert_eq!(color("208"), ThemeColor::Indexed(208));
+
+        assert_eq!(
+            "#ffaa0".parse::<ThemeColor>().unwrap_err().to_string(),
+            r##"invalid color "#ffaa0", expected a hex color like "#ffaa00""##
+        );
+        assert!("purple".parse::<ThemeColor>().is_err());
+    }
+
+    #[test]
+    fn configured_styles_replace_those_of_the_builtin_theme() {
+        let config: Config = toml::from_str(
+            r##"
+            [theme]
+            name = "light"
+            directory = { fg = "#0000ff", underlined = true }
+            "##,
+        )
+        .expect("valid config");
+
+        let theme = config.theme.theme(false);
+        assert_eq!(
+            theme.directory,
+            StyleSpec {
+                fg: Some(ThemeColor::Rgb(0, 0, 255)),
+                underlined: true,
+                ..StyleSpec::default()
+            }
+        );
+        assert_eq!(
+            theme.marked,
+            Theme::builtin(ThemeName::Light).marked,
+            "other styles are taken from the built-in theme"
+        );
+
+        let theme = config.theme.theme(true);
+        assert_eq!(
+            theme.directory,
+            StyleSpec {
+                underlined: true,
+                ..StyleSpec::default()
+            },
+            "without colors, only modifiers remain"
+        );
+        assert_eq!(theme.marked, Theme::builtin(ThemeName::Monochrome).marked);
+    }
+
+    #[test]
+    fn the_default_theme_is_used_without_configuration() {
+        let config: Config = toml::from_str("").expect("valid config");
+        assert_eq!(
+            config.theme.theme(false),
+            Theme::builtin(ThemeName::Default)
+        );
+    }
+
+    #[test]
+    fn monochrome_has_no_colors() {
+        let Theme {
+            header,
+            header_marked,
+            header_danger,
+            footer,
+            message,
+            prompt,
+            prompt_key,
+            danger,
+            selection,
+            border,
+            border_focussed,
+            directory,
+            file,
+            marked,
+            marked_file,
+            missing,
+            cleanup_candidate,
+            gitignored,
+            sparse,
+            difference,
+            bar,
+            highlight,
+        } = Theme::builtin(ThemeName::Monochrome);
+        for style in [
+            header,
+            header_marked,
+            header_danger,
+            footer,
+            message,
+            prompt,
+            prompt_key,
+            danger,
+            selection,
+            border,
+            border_focussed,
+            directory,
+            file,
+            marked,
+            marked_file,
+            missing,
+            cleanup_candidate,
+            gitignored,
+            sparse,
+            difference,
+            bar,
+            highlight,
+        ] {
+            assert_eq!((style.fg, style.bg), (None, None), "{style:?}");
+        }
+        assert_ne!(
+            directory, marked,
+            "states can still be told apart by their modifiers"
+        );
+    }
+
+    #[test]
+    fn unknown_elements_and_style_keys_are_rejected() {
+        let error = |toml: &str| {
+            toml::from_str::<Config>(toml)
+                .expect_err("invalid config")
+                .message()
+                .to_owned()
+        };
+        assert!(error("[theme]\nname = \"solarized\"").contains("unknown variant `solarized`"));
+        assert!(error("[theme]\ndirectories = { bold = true }").contains("directories"));
+        assert!(error("[theme]\ndirectory = { blink = true }").contains("unknown field `blink`"));
+        assert!(error("[theme]\ndirectory = { fg = \"purple\" }").contains("unknown color"));
+    }
+}
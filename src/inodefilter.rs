@@ -4,23 +4,52 @@ use std::collections::HashMap;
 #[derive(Debug, Default, Clone)]
 pub(crate) struct InodeFilter {
     inner: HashMap<(u64, u64), u64>,
+    /// If `true`, symbolic links are followed and every entry may be seen more than once.
+    follow_links: bool,
+    /// If `true`, every hard link is counted, but entries reached again through symbolic links
+    /// are still counted only once.
+    count_hard_links: bool,
 }
 
 impl InodeFilter {
+    /// Create a filter for a traversal that follows symbolic links if `follow_links` is `true`,
+    /// and that counts each hard link of a file if `count_hard_links` is `true`.
+    pub(crate) fn new(follow_links: bool, count_hard_links: bool) -> Self {
+        Self {
+            inner: HashMap::default(),
+            follow_links,
+            count_hard_links,
+        }
+    }
+
     #[cfg(unix)]
     /// Register file metadata and return `true` if this link should be counted.
     pub(crate) fn add(&mut self, metadata: &std::fs::Metadata) -> bool {
         use std::os::unix::fs::MetadataExt;
 
-        self.add_dev_inode((metadata.dev(), metadata.ino()), metadata.nlink())
+        // Directories can't be hard-linked, their link count is made up of their subdirectories.
+        let nlinks = if metadata.is_dir() {
+            1
+        } else {
+            metadata.nlink()
+        };
+        self.add_dev_inode((metadata.dev(), metadata.ino()), nlinks)
     }
 
     #[cfg(windows)]
     /// Register file metadata and return `true` if this link should be counted.
     pub(crate) fn add(&mut self, metadata: &crate::walk::Metadata) -> bool {
-        metadata
-            .hard_link_id()
-            .is_none_or(|id| self.inner.insert(id, 0).is_none())
+        if self.count_hard_links && !self.follow_links {
+            return true;
+        }
+        // Without a link count, entries seen again are assumed to be reached through symbolic
+        // links when following them. Sightings are counted like in `add_dev_inode()`, which
+        // registers the links of summaries.
+        metadata.hard_link_id().is_none_or(|id| {
+            let seen = self.inner.entry(id).or_insert(0);
+            *seen += 1;
+            *seen == 1
+        })
     }
 
     #[cfg(not(any(unix, windows)))]
@@ -32,10 +61,16 @@ impl InodeFilter {
     /// Register a `(device, inode)` with its hard-link count.
     ///
     /// Returns `true` for the first observation that should contribute to size/count,
-    /// and `false` for subsequent links.
-    #[cfg(any(unix, test))]
+    /// and `false` for subsequent links, unless hard links are counted.
     pub(crate) fn add_dev_inode(&mut self, dev_inode: (u64, u64), nlinks: u64) -> bool {
-        if nlinks <= 1 {
+        if self.follow_links {
+            // Symbolic links can lead to any entry, so the hard-link count can't tell when all
+            // paths to it were seen, only how many of them may be hard links.
+            let seen = self.inner.entry(dev_inode).or_insert(0);
+            *seen += 1;
+            return *seen == 1 || self.count_hard_links && *seen <= nlinks;
+        }
+        if self.count_hard_links || nlinks <= 1 {
             return true;
         }
 
@@ -76,4 +111,44 @@ mod tests {
         assert!(inodes.add_dev_inode((1, 1), 1));
         assert!(inodes.add_dev_inode((1, 1), 1));
     }
+
+    #[test]
+    fn it_filters_all_inodes_when_following_links() {
+        let mut inodes = InodeFilter::new(true, false);
+
+        assert!(inodes.add_dev_inode((1, 1), 1));
+        assert!(!inodes.add_dev_inode((1, 1), 1));
+        assert!(inodes.add_dev_inode((1, 2), 2));
+        assert!(!inodes.add_dev_inode((1, 2), 2));
+        assert!(
+            !inodes.add_dev_inode((1, 2), 2),
+            "links may lead to an entry more often than it has hard links"
+        );
+    }
+
+    #[test]
+    fn it_counts_all_hard_links_if_asked_to() {
+        let mut inodes = InodeFilter::new(false, true);
+
+        assert!(inodes.add_dev_inode((1, 1), 2));
+        assert!(inodes.add_dev_inode((1, 1), 2));
+        assert!(inodes.add_dev_inode((1, 1), 2));
+    }
+
+    #[test]
+    fn it_counts_hard_links_but_not_symlinks_when_following_links() {
+        let mut inodes = InodeFilter::new(true, true);
+
+        assert!(inodes.add_dev_inode((1, 1), 1));
+        assert!(
+            !inodes.add_dev_inode((1, 1), 1),
+            "a file without hard links can only be reached again through a symbolic link"
+        );
+        assert!(inodes.add_dev_inode((1, 2), 2));
+        assert!(inodes.add_dev_inode((1, 2), 2));
+        assert!(
+            !inodes.add_dev_inode((1, 2), 2),
+            "links may lead to an entry more often than it has hard links"
+        );
+    }
 }
@@ -1,51 +1,97 @@
-use std::{collections::BTreeSet, ffi::OsStr};
-
-use dua::traverse::TreeIndex;
-
-use super::EntryDataBundle;
-
-/// Refresh with: `sed -n '/^const CLEANUP_DIR_NAMES_SORTED/,/^];/p' src/interactive/app/cleanup.rs | rg -o '"[^"]+"' | LC_ALL=C sort | sed 's/^/    /; s/$/,/'`.
-const CLEANUP_DIR_NAMES_SORTED_FOR_BISECT: &[&str] = &[
-    ".mypy_cache",
-    ".pytest_cache",
-    ".ruff_cache",
-    ".tox",
-    ".venv",
-    "__pycache__",
-    "node_modules",
-    "target",
-    "venv",
-];
-
-/// Return the indices of existing directories that match known cleanup names.
-pub fn cleanup_candidates(entries: &[EntryDataBundle]) -> BTreeSet<TreeIndex> {
+use std::{collections::BTreeSet, path::Path};
+
+use dua::{
+    CleanupRule,
+    traverse::{Tree, TreeIndex},
+};
+use petgraph::Direction;
+
+use super::{EntryDataBundle, tree_view::TreeView};
+use crate::interactive::path_of;
+
+/// Return the indices of existing directories that match one of `rules`.
+pub fn cleanup_candidates(
+    rules: &[CleanupRule],
+    tree_view: &TreeView<'_>,
+    entries: &[EntryDataBundle],
+) -> BTreeSet<TreeIndex> {
     entries
         .iter()
-        .filter(|entry| is_cleanup_candidate(entry))
+        .filter(|entry| {
+            is_cleanup_candidate(rules, entry, |sibling| {
+                // Small files may have been collapsed in the tree, so the filesystem is asked.
+                let path = tree_view.path_of(entry.index);
+                path.parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(sibling)
+                    .exists()
+            })
+        })
         .map(|entry| entry.index)
         .collect()
 }
 
-fn is_cleanup_candidate(entry: &EntryDataBundle) -> bool {
-    entry.exists
-        && entry.is_dir
-        && is_cleanup_dir_name(
-            entry
-                .name
-                .file_name()
-                .unwrap_or_else(|| entry.name.as_os_str()),
-        )
+/// Return all directories below `root` that match one of `rules`, without looking into them.
+pub fn sweep_cleanup_candidates(
+    rules: &[CleanupRule],
+    tree: &Tree,
+    root: TreeIndex,
+) -> Vec<TreeIndex> {
+    let mut candidates = Vec::new();
+    let mut dirs = vec![root];
+    while let Some(dir) = dirs.pop() {
+        for index in tree.neighbors_directed(dir, Direction::Outgoing) {
+            let entry = &tree[index];
+            if !entry.is_dir {
+                continue;
+            }
+            if matches_rules(rules, &entry.name, |sibling| {
+                let path = path_of(tree, index, None);
+                path.parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(sibling)
+                    .exists()
+            }) {
+                candidates.push(index);
+            } else {
+                dirs.push(index);
+            }
+        }
+    }
+    candidates
+}
+
+/// Return `true` if `entry` is an existing directory matching one of `rules`, with
+/// `has_sibling(name)` telling whether an entry called `name` exists next to it.
+fn is_cleanup_candidate(
+    rules: &[CleanupRule],
+    entry: &EntryDataBundle,
+    has_sibling: impl FnMut(&str) -> bool,
+) -> bool {
+    entry.exists && entry.is_dir && matches_rules(rules, &entry.name, has_sibling)
 }
 
-fn is_cleanup_dir_name(name: &OsStr) -> bool {
-    CLEANUP_DIR_NAMES_SORTED_FOR_BISECT
-        .binary_search_by(|candidate| OsStr::new(candidate).cmp(name))
-        .is_ok()
+/// Return `true` if the last component of `name` matches one of `rules`.
+fn matches_rules(
+    rules: &[CleanupRule],
+    name: &Path,
+    mut has_sibling: impl FnMut(&str) -> bool,
+) -> bool {
+    let name = name.file_name().map_or(name, Path::new);
+    let name = gix::path::into_bstr(name);
+    rules.iter().any(|rule| {
+        gix::glob::wildmatch(
+            rule.name.as_str().into(),
+            name.as_ref(),
+            gix::glob::wildmatch::Mode::empty(),
+        ) && (rule.siblings.is_empty() || rule.siblings.iter().any(|sibling| has_sibling(sibling)))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::interactive::app::tests::utils::add_entry;
     use std::path::PathBuf;
 
     fn entry(name: &str, is_dir: bool) -> EntryDataBundle {
@@ -53,21 +99,37 @@ mod tests {
             index: TreeIndex::new(0),
             name: PathBuf::from(name),
             size: 0,
+            alternate_size: 0,
             mtime: std::time::SystemTime::UNIX_EPOCH,
             entry_count: None,
             is_dir,
             exists: true,
+            is_small_files: false,
+            ownership: None,
+            depth: 0,
+            is_expanded: false,
+        }
+    }
+
+    fn is_default_candidate(entry: &EntryDataBundle) -> bool {
+        is_cleanup_candidate(&CleanupRule::defaults(), entry, |_| false)
+    }
+
+    fn rule(name: &str, siblings: &[&str]) -> CleanupRule {
+        CleanupRule {
+            name: name.into(),
+            siblings: siblings.iter().map(|&sibling| sibling.into()).collect(),
         }
     }
 
     #[test]
     fn identifies_conservative_cleanup_directories() {
         for name in ["target", "node_modules", "__pycache__", ".venv"] {
-            assert!(is_cleanup_candidate(&entry(name, true)));
+            assert!(is_default_candidate(&entry(name, true)));
         }
         for path in ["project/target", "project/node_modules", "project/.venv"] {
             assert!(
-                is_cleanup_candidate(&entry(path, true)),
+                is_default_candidate(&entry(path, true)),
                 "file paths are expected as `name` field."
             );
         }
@@ -76,19 +138,64 @@ mod tests {
     #[test]
     fn ignores_files_and_ambiguous_build_outputs() {
         for name in ["target", "build", "dist"] {
-            assert!(!is_cleanup_candidate(&entry(name, false)));
+            assert!(!is_default_candidate(&entry(name, false)));
         }
-        assert!(!is_cleanup_candidate(&entry("build", true)));
-        assert!(!is_cleanup_candidate(&entry("dist", true)));
+        assert!(!is_default_candidate(&entry("build", true)));
+        assert!(!is_default_candidate(&entry("dist", true)));
+    }
+
+    #[test]
+    fn ambiguous_build_outputs_are_candidates_next_to_their_build_file() {
+        assert!(is_cleanup_candidate(
+            &CleanupRule::defaults(),
+            &entry("build", true),
+            |sibling| sibling == "build.gradle.kts"
+        ));
     }
 
     #[test]
-    fn cleanup_directory_names_remain_sorted_for_bisection() {
+    fn configured_rules_match_globs_and_require_one_of_their_siblings() {
+        let rules = [rule("target", &["Cargo.toml"]), rule("cmake-build-*", &[])];
+        assert!(is_cleanup_candidate(
+            &rules,
+            &entry("project/cmake-build-debug", true),
+            |_| false
+        ));
+        assert!(!is_cleanup_candidate(
+            &rules,
+            &entry("target", true),
+            |_| false
+        ));
+        assert!(is_cleanup_candidate(
+            &rules,
+            &entry("target", true),
+            |sibling| sibling == "Cargo.toml"
+        ));
         assert!(
-            CLEANUP_DIR_NAMES_SORTED_FOR_BISECT
-                .windows(2)
-                .all(|names| OsStr::new(names[0]) < OsStr::new(names[1])),
-            "CLEANUP_DIR_NAMES_SORTED must remain sorted for binary search"
+            !is_cleanup_candidate(&rules, &entry("node_modules", true), |_| true),
+            "configured rules replace the built-in ones"
+        );
+    }
+
+    #[test]
+    fn the_sweep_finds_candidates_in_all_directories_below_but_not_inside_them() {
+        let mut tree = Tree::new();
+        let root = add_entry(&mut tree, None, "", 0, true);
+        let project = add_entry(&mut tree, Some(root), "project", 0, true);
+        let target = add_entry(&mut tree, Some(project), "target", 0, true);
+        let nested = add_entry(&mut tree, Some(target), "node_modules", 0, true);
+        let web = add_entry(&mut tree, Some(root), "web", 0, true);
+        let node_modules = add_entry(&mut tree, Some(web), "node_modules", 0, true);
+        add_entry(&mut tree, Some(web), "venv", 0, false);
+        add_entry(&mut tree, Some(web), "dist", 0, true);
+
+        let mut candidates = sweep_cleanup_candidates(&CleanupRule::defaults(), &tree, root);
+        candidates.sort();
+        assert_eq!(candidates, [target, node_modules]);
+        assert_eq!(
+            sweep_cleanup_candidates(&CleanupRule::defaults(), &tree, target),
+            [nested],
+            "the directory the sweep starts at isn't a candidate itself"
         );
     }
 }
@@ -0,0 +1,61 @@
+//! Utilities to copy text to the clipboard of the terminal.
+use std::io;
+
+/// Copy `text` to the system clipboard by asking the terminal to do it with an OSC 52 escape
+/// sequence, which also works over SSH.
+pub fn copy(text: &str) -> io::Result<()> {
+    let stderr = io::stderr();
+    copy_to(stderr.lock(), text)
+}
+
+fn copy_to(mut write: impl io::Write, text: &str) -> io::Result<()> {
+    write!(write, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    write.flush()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (pos, byte)| {
+            group | u32::from(*byte) << (16 - 8 * pos)
+        });
+        for pos in 0..4 {
+            if pos <= chunk.len() {
+                out.push(char::from(
+                    ALPHABET[(group >> (18 - 6 * pos)) as usize & 0x3f],
+                ));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_are_encoded_as_base64_with_padding() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("/tmp/ü?>", "L3RtcC/DvD8+"),
+        ] {
+            assert_eq!(base64(input.as_bytes()), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn text_is_copied_with_an_osc_52_sequence() -> io::Result<()> {
+        let mut out = Vec::new();
+        copy_to(&mut out, "foo")?;
+        assert_eq!(out, b"\x1b]52;c;Zm9v\x07");
+        Ok(())
+    }
+}
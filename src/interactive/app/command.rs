@@ -0,0 +1,211 @@
+//! Utilities to run other programs in the terminal of the interactive UI.
+use super::input::InputPause;
+use anyhow::{Context, Result};
+use crossterm::{
+    cursor,
+    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+};
+use tui::{Terminal, backend::Backend};
+
+/// The modes the terminal was put into for the interactive UI, which are left while another
+/// program runs in it.
+#[derive(Clone, Copy)]
+pub struct TerminalModes {
+    pub mouse_capture: bool,
+    pub focus_change: bool,
+}
+
+impl TerminalModes {
+    fn leave(self) -> io::Result<()> {
+        let mut stderr = io::stderr();
+        if self.focus_change {
+            execute!(stderr, DisableFocusChange)?;
+        }
+        if self.mouse_capture {
+            execute!(stderr, DisableMouseCapture)?;
+        }
+        execute!(stderr, cursor::Show, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()
+    }
+
+    fn enter(self) -> io::Result<()> {
+        let mut stderr = io::stderr();
+        terminal::enable_raw_mode()?;
+        execute!(stderr, EnterAlternateScreen)?;
+        if self.mouse_capture {
+            execute!(stderr, EnableMouseCapture)?;
+        }
+        if self.focus_change {
+            execute!(stderr, EnableFocusChange)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run `command` in the terminal and wait for it to exit, with the interactive UI suspended
+/// meanwhile if `modes` are set, and without reading its input.
+pub fn run_in_terminal<B>(
+    command: &mut Command,
+    modes: Option<TerminalModes>,
+    input_pause: &InputPause,
+    terminal: &mut Terminal<B>,
+) -> Result<ExitStatus>
+where
+    B: Backend,
+{
+    let program = command.get_program().to_string_lossy().into_owned();
+    let Some(modes) = modes else {
+        return command
+            .status()
+            .with_context(|| format!("Could not run '{program}'"));
+    };
+
+    input_pause.set_paused(true);
+    let status = modes
+        .leave()
+        .context("Could not restore the terminal")
+        .and_then(|()| {
+            command
+                .status()
+                .with_context(|| format!("Could not run '{program}'"))
+        });
+    let entered = modes.enter().context("Could not set up the terminal again");
+    input_pause.set_paused(false);
+    // Everything has to be drawn again on the alternate screen.
+    terminal
+        .clear()
+        .map_err(|err| anyhow::Error::msg(err.to_string()))?;
+    entered?;
+    status
+}
+
+/// Return `template` with `{path}` replaced by `path` and `{paths}` by all `paths`, each quoted
+/// for the shell. Paths are kept as they are, even if they aren't valid UTF-8.
+pub fn expand(template: &str, path: &Path, paths: &[PathBuf]) -> OsString {
+    let mut out = OsString::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("{paths}") {
+            for (idx, path) in paths.iter().enumerate() {
+                if idx > 0 {
+                    out.push(" ");
+                }
+                out.push(quote(path));
+            }
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{path}") {
+            out.push(quote(path));
+            rest = after;
+        } else {
+            out.push("{");
+            rest = &rest[1..];
+        }
+    }
+    out.push(rest);
+    out
+}
+
+/// Return a command that runs `command_line` with the shell of the platform.
+pub fn shell_command(command_line: &OsStr) -> Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // `cmd` doesn't follow the quoting rules `arg()` escapes for, so the line is passed as is.
+        let mut command = Command::new("cmd");
+        command.arg("/C").raw_arg(command_line);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(command_line);
+        command
+    }
+}
+
+/// Return a command that starts the shell of the user, as given by `SHELL`, or by `COMSPEC` on
+/// Windows.
+pub fn user_shell() -> Command {
+    let variable = if cfg!(windows) { "COMSPEC" } else { "SHELL" };
+    Command::new(shell_program(std::env::var_os(variable)))
+}
+
+fn shell_program(configured: Option<OsString>) -> OsString {
+    configured
+        .filter(|program| !program.is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "cmd" } else { "sh" }.into())
+}
+
+#[cfg(unix)]
+fn quote(path: &Path) -> OsString {
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+    let mut quoted = vec![b'\''];
+    for &byte in path.as_os_str().as_bytes() {
+        if byte == b'\'' {
+            quoted.extend_from_slice(br"'\''");
+        } else {
+            quoted.push(byte);
+        }
+    }
+    quoted.push(b'\'');
+    OsString::from_vec(quoted)
+}
+
+#[cfg(not(unix))]
+fn quote(path: &Path) -> OsString {
+    // Paths can't contain double quotes on Windows.
+    let mut quoted = OsString::from("\"");
+    quoted.push(path);
+    quoted.push("\"");
+    quoted
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_replaced_with_quoted_paths() {
+        let paths = [PathBuf::from("a b"), PathBuf::from("it's")];
+        assert_eq!(
+            expand("du -s {path} {paths} {other}", Path::new("x"), &paths),
+            r"du -s 'x' 'a b' 'it'\''s' {other}"
+        );
+        assert_eq!(expand("{{path}}", Path::new("x"), &[]), "{'x'}");
+    }
+
+    #[test]
+    fn the_shell_falls_back_to_sh() {
+        assert_eq!(shell_program(Some("/bin/zsh".into())), "/bin/zsh");
+        assert_eq!(shell_program(Some("".into())), "sh");
+        assert_eq!(shell_program(None), "sh");
+    }
+
+    #[test]
+    fn commands_run_with_the_shell() -> Result<()> {
+        let output = shell_command(&expand("printf %s {path}", Path::new("it's"), &[])).output()?;
+        assert_eq!(String::from_utf8(output.stdout)?, "it's");
+        Ok(())
+    }
+
+    #[test]
+    fn paths_that_are_not_utf8_are_passed_as_they_are() -> Result<()> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"caf\xe9's"));
+        let output =
+            shell_command(&expand("printf %s {paths}", Path::new("x"), &[path.into()])).output()?;
+        assert_eq!(output.stdout, b"caf\xe9's");
+        Ok(())
+    }
+}
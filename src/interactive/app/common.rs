@@ -1,9 +1,12 @@
 use crate::interactive::path_of;
-use dua::traverse::{Tree, TreeIndex};
+use dua::{
+    ownership::{Ownership, Ownerships},
+    traverse::{EntryData, Tree, TreeIndex},
+};
 use itertools::Itertools;
 use petgraph::Direction;
 use std::time::SystemTime;
-use std::{cmp::Ordering, path::PathBuf};
+use std::{borrow::Cow, cmp::Ordering, path::PathBuf};
 use unicode_segmentation::UnicodeSegmentation;
 
 /// Controls which modification time is used for mtime sorting.
@@ -40,6 +43,8 @@ pub enum SortMode {
     CountAscending,
     NameDescending,
     NameAscending,
+    OwnerAscending,
+    OwnerDescending,
 }
 
 impl SortMode {
@@ -91,9 +96,21 @@ impl SortMode {
             _ => NameAscending,
         }
     }
+
+    pub fn toggle_owner(&mut self) {
+        use SortMode::{OwnerAscending, OwnerDescending};
+        *self = match self {
+            OwnerAscending => OwnerDescending,
+            _ => OwnerAscending,
+        }
+    }
 }
 
 /// Filesystem entry data prepared for interactive views.
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag describes an independent property of the entry"
+)]
 pub struct EntryDataBundle {
     /// Index of this entry in the traversal tree.
     pub index: TreeIndex,
@@ -105,6 +122,8 @@ pub struct EntryDataBundle {
     pub name: PathBuf,
     /// Entry size in bytes, including recursive child sizes for directories.
     pub size: u128,
+    /// The disk usage if `size` is the apparent size and vice versa.
+    pub alternate_size: u128,
     /// Modification time used by the active view or sort mode.
     pub mtime: SystemTime,
     /// Recursive child entry count for directories, or `None` for files.
@@ -113,8 +132,18 @@ pub struct EntryDataBundle {
     pub is_dir: bool,
     /// Whether this entry still exists when metadata is checked for the view.
     pub exists: bool,
+    /// Whether this entry stands for the small files of its directory, which have to be
+    /// listed before they can be acted on.
+    pub is_small_files: bool,
+    /// The owner, group and permissions of this entry, if they are known.
+    pub ownership: Option<Ownership>,
+    /// How many expanded directories this entry is nested in within the tree display, or 0.
+    pub depth: usize,
+    /// Whether this directory is expanded to list its entries below it in the tree display.
+    pub is_expanded: bool,
 }
 
+#[derive(Clone, Copy)]
 pub enum EntryCheck {
     PossiblyCostlyLstat,
     Disabled,
@@ -130,18 +159,32 @@ impl EntryCheck {
     }
 }
 
+/// Return `true` if `entry` is hidden, i.e. its name starts with a dot.
+///
+/// Small files are never hidden as they stand for entries of all names.
+pub fn is_hidden(entry: &EntryData) -> bool {
+    !entry.is_small_files
+        && entry
+            .name
+            .file_name()
+            .is_some_and(|name| name.as_encoded_bytes().starts_with(b"."))
+}
+
 /// Note that with `glob_root` present, we will not obtain metadata anymore as we might be seeing
 /// a lot of entries. That way, displaying 250k entries is no problem.
+/// Hidden entries are left out unless `show_hidden` is set.
 pub fn sorted_entries(
     tree: &Tree,
+    ownerships: &Ownerships,
     node_idx: TreeIndex,
     sorting: SortMode,
     glob_root: Option<TreeIndex>,
+    show_hidden: bool,
     check: EntryCheck,
 ) -> Vec<EntryDataBundle> {
     use SortMode::{
         CountAscending, CountDescending, MTimeAscending, MTimeDescending, NameAscending,
-        NameDescending, SizeAscending, SizeDescending,
+        NameDescending, OwnerAscending, OwnerDescending, SizeAscending, SizeDescending,
     };
     fn cmp_count(l: &EntryDataBundle, r: &EntryDataBundle) -> Ordering {
         l.entry_count
@@ -157,35 +200,65 @@ pub fn sorted_entries(
             l.name.cmp(&r.name)
         }
     }
+    let cmp_owner = |l: &EntryDataBundle, r: &EntryDataBundle, descending: bool| {
+        let owner = |e: &EntryDataBundle| e.ownership.map(|o| owner_name(ownerships, o));
+        let (l_owner, r_owner) = (owner(l), owner(r));
+        // Entries of unknown owners go last, and the largest entries of each owner go first.
+        l_owner
+            .is_none()
+            .cmp(&r_owner.is_none())
+            .then_with(|| {
+                let by_owner = l_owner.cmp(&r_owner);
+                if descending {
+                    by_owner.reverse()
+                } else {
+                    by_owner
+                }
+            })
+            .then_with(|| r.size.cmp(&l.size))
+    };
     let mtime_sort = sorting.mtime_sort().unwrap_or_default();
     tree.neighbors_directed(node_idx, Direction::Outgoing)
         .filter_map(|idx| {
-            tree.node_weight(idx).map(|entry| {
-                let use_glob_path = glob_root.is_some_and(|glob_root| glob_root == node_idx);
-                let (path, exists, is_dir) = {
-                    let path = path_of(tree, idx, glob_root);
-                    if matches!(check, EntryCheck::Disabled) || glob_root == Some(node_idx) {
-                        (path, true, entry.is_dir)
-                    } else {
-                        let meta = path.symlink_metadata();
-                        let exists = meta.is_ok();
-                        (path, exists, meta.is_ok_and(|m| m.is_dir()))
+            tree.node_weight(idx)
+                .filter(|entry| show_hidden || !is_hidden(entry))
+                .map(|entry| {
+                    let use_glob_path = glob_root.is_some_and(|glob_root| glob_root == node_idx);
+                    let (path, exists, is_dir) = {
+                        let path = path_of(tree, idx, glob_root);
+                        // The path of small files is the one of their directory, so there is nothing to check.
+                        if matches!(check, EntryCheck::Disabled)
+                            || glob_root == Some(node_idx)
+                            || entry.is_small_files
+                        {
+                            (path, true, entry.is_dir)
+                        } else {
+                            let meta = path.symlink_metadata();
+                            let exists = meta.is_ok();
+                            (path, exists, meta.is_ok_and(|m| m.is_dir()))
+                        }
+                    };
+                    EntryDataBundle {
+                        index: idx,
+                        name: if entry.is_small_files {
+                            small_files_name(entry.entry_count.get().unwrap_or_default())
+                        } else if use_glob_path {
+                            path
+                        } else {
+                            entry.name.clone()
+                        },
+                        size: entry.size,
+                        alternate_size: u128::from(entry.alternate_size),
+                        mtime: mtime_for_sort(tree, idx, entry.mtime, mtime_sort),
+                        entry_count: entry.entry_count.get(),
+                        exists,
+                        is_dir,
+                        is_small_files: entry.is_small_files,
+                        ownership: entry.ownership.and_then(|id| ownerships.get(id)),
+                        depth: 0,
+                        is_expanded: false,
                     }
-                };
-                EntryDataBundle {
-                    index: idx,
-                    name: if use_glob_path {
-                        path
-                    } else {
-                        entry.name.clone()
-                    },
-                    size: entry.size,
-                    mtime: mtime_for_sort(tree, idx, entry.mtime, mtime_sort),
-                    entry_count: entry.entry_count,
-                    exists,
-                    is_dir,
-                }
-            })
+                })
         })
         .sorted_by(|l, r| match sorting {
             SizeDescending => r.size.cmp(&l.size),
@@ -196,10 +269,55 @@ pub fn sorted_entries(
             CountDescending => cmp_count(l, r).reverse(),
             NameAscending => cmp_name(l, r),
             NameDescending => cmp_name(l, r).reverse(),
+            OwnerAscending => cmp_owner(l, r, false),
+            OwnerDescending => cmp_owner(l, r, true),
         })
         .collect()
 }
 
+/// Files are considered sparse or compressed only if they save at least this many bytes on disk,
+/// to ignore small files whose contents are stored alongside their metadata.
+const SPARSE_MIN_SAVED_BYTES: u128 = 64 * 1024;
+
+/// Return the apparent size and the disk usage of `entry`, with `apparent_size` telling if its
+/// size is the apparent size.
+pub fn apparent_and_disk_size(entry: &EntryDataBundle, apparent_size: bool) -> (u128, u128) {
+    if apparent_size {
+        (entry.size, entry.alternate_size)
+    } else {
+        (entry.alternate_size, entry.size)
+    }
+}
+
+/// Return `true` if a file takes up less than half of its `apparent_size` on disk, as sparse
+/// files and those compressed by filesystems like btrfs or ZFS do.
+pub fn is_sparse_or_compressed(apparent_size: u128, disk_size: u128) -> bool {
+    disk_size.saturating_mul(2) < apparent_size
+        && apparent_size - disk_size >= SPARSE_MIN_SAVED_BYTES
+}
+
+/// Return the name of the user owning an entry with `ownership`, or its id if it has no name.
+pub fn owner_name(ownerships: &Ownerships, ownership: Ownership) -> Cow<'_, str> {
+    ownerships
+        .user_name(ownership.uid)
+        .map_or_else(|| ownership.uid.to_string().into(), Cow::Borrowed)
+}
+
+/// Return the name of the group owning an entry with `ownership`, or its id if it has no name.
+pub fn group_name(ownerships: &Ownerships, ownership: Ownership) -> Cow<'_, str> {
+    ownerships
+        .group_name(ownership.gid)
+        .map_or_else(|| ownership.gid.to_string().into(), Cow::Borrowed)
+}
+
+fn small_files_name(count: u64) -> PathBuf {
+    if count == 1 {
+        "1 small file".into()
+    } else {
+        format!("{count} small files").into()
+    }
+}
+
 fn mtime_for_sort(
     tree: &Tree,
     node_idx: TreeIndex,
@@ -304,4 +422,20 @@ mod tests {
             "spaces are counted as graphemes, too"
         );
     }
+
+    #[test]
+    fn only_files_saving_much_space_on_disk_are_sparse_or_compressed() {
+        let mib = 1024 * 1024;
+        assert!(is_sparse_or_compressed(mib, 0), "a hole-only file");
+        assert!(
+            is_sparse_or_compressed(mib, mib / 4),
+            "compressed to a quarter"
+        );
+        assert!(!is_sparse_or_compressed(mib, mib), "stored as is");
+        assert!(!is_sparse_or_compressed(4096, 8192), "rounded up to blocks");
+        assert!(
+            !is_sparse_or_compressed(1000, 0),
+            "small files stored inline save too little"
+        );
+    }
 }
@@ -1,29 +1,38 @@
 use crate::interactive::state::FilesystemScan;
 use crate::interactive::{
-    CursorDirection, CursorMode, DisplayOptions, EntryCheck, MarkEntryMode,
+    CursorDirection, CursorMode, DisplayOptions, EntryCheck, MarkEntryMode, SortMode,
     app::navigation::Navigation,
     state::FocussedPane,
-    widgets::{MainWindow, MainWindowProps, glob_search},
+    widgets::{
+        MainWindow, MainWindowProps, MarkPane, PromptPurpose, SortColumn, TreemapDirection,
+        glob_search,
+    },
 };
 use anyhow::Result;
 use crossbeam::channel::Receiver;
-use crossterm::{
-    event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    style::Colored,
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
 use dua::{
     Config, WalkResult,
+    keys::{Action, Key, Pane},
     traverse::{BackgroundTraversal, EntryData, Traversal, TreeIndex},
 };
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tui::{
     Terminal, backend::Backend, buffer::Buffer, layout::Rect, style::Color, widgets::Widget,
 };
 
+use super::command;
+use super::input;
 use super::notification;
 use super::state::{AppState, Cursor};
 use super::tree_view::TreeView;
 
+/// Two clicks on the same entry within this time enter it.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 impl AppState {
     pub fn navigation_mut(&mut self) -> &mut Navigation {
         self.glob_navigation
@@ -46,14 +55,39 @@ impl AppState {
     where
         B: Backend,
     {
+        if let Some(pane) = window.owners.as_mut() {
+            pane.update(tree_view.traversal, self.navigation().view_root);
+        }
+        if let Some(pane) = window.preview.as_mut() {
+            pane.update(
+                tree_view.traversal,
+                self.navigation().selected,
+                tree_view.glob_tree_root,
+                self.walk_options.apparent_size,
+            );
+        }
+        if let Some(pane) = window.repositories.as_mut()
+            && self.scan.is_none()
+        {
+            // Looking at every entry of the tree is too costly to do while it still changes.
+            pane.update(tree_view.traversal, tree_view.traversal.root_index);
+        }
+        self.update_split_view(tree_view);
         let props = MainWindowProps {
             current_path: tree_view.current_path(self.navigation().view_root),
+            split_path: self
+                .split
+                .as_ref()
+                .map(|split| tree_view.current_path(split.navigation.view_root)),
             entries_traversed: self.stats.entries_traversed,
             total_bytes: tree_view.total_size(),
+            hidden_entries: (!self.show_hidden)
+                .then(|| tree_view.hidden_entries(self.navigation().view_root)),
             start: self.stats.start,
             elapsed: self.stats.elapsed,
             display,
             state: self,
+            ownerships: &tree_view.traversal.ownerships,
             config,
         };
 
@@ -245,6 +279,12 @@ impl AppState {
                             traversal.cost = Some(traversal.start_time.elapsed());
                         }
                         self.update_state_during_traversal(traversal, previous_selection.as_ref(), is_finished);
+                        if is_finished && let Some(directory) = self.pending_refreshes.pop() {
+                            let mut tree_view = self.tree_view(traversal);
+                            return self
+                                .refresh(&mut tree_view, window, Refresh::Directory(directory))
+                                .map(|()| None);
+                        }
                         self.refresh_screen(window, traversal, display, terminal, config)?;
                         if is_finished {
                             let message = notification::scan_finished(
@@ -327,10 +367,8 @@ impl AppState {
     where
         B: Backend,
     {
-        use FocussedPane::{Glob, Help, Main, Mark};
-        use crossterm::event::KeyCode::{
-            Backspace, Char, Down, End, Enter, Esc, Home, Left, PageDown, PageUp, Right, Tab, Up,
-        };
+        use FocussedPane::{Glob, Help, Jump, Main, Mark, Treemap};
+        use crossterm::event::KeyCode::Esc;
 
         let key = match event {
             Event::FocusGained => {
@@ -348,43 +386,68 @@ impl AppState {
                 key
             }
             Event::Resize(_, _) => refresh_key(),
+            Event::Mouse(mouse) => {
+                let mut tree_view = self.tree_view(traversal);
+                if self.process_mouse_event(
+                    mouse,
+                    window,
+                    &mut tree_view,
+                    *display,
+                    terminal,
+                    config,
+                )? {
+                    self.received_events = true;
+                    self.draw(window, &tree_view, *display, terminal, config)?;
+                }
+                return Ok(None);
+            }
             _ => return Ok(None),
         };
 
         self.reset_message();
 
-        let glob_focussed = self.focussed == Glob;
+        let prompt_focussed = matches!(self.focussed, Glob | Jump);
         let mut tree_view = self.tree_view(traversal);
 
+        let bindings = &config.keys.bindings;
+        let chord = input::key_chord(key);
+        let action_in = |pane| chord.and_then(|chord| bindings.action(pane, chord));
+        // Only the jump prompt completes its input, the glob prompt just submits it.
+        let prompt_action = action_in(Pane::Prompt).filter(|&action| match self.focussed {
+            Jump => true,
+            Glob => action == Action::Submit,
+            _ => false,
+        });
+        // Characters are typed into prompts, so only global bindings of other keys apply there.
+        let typed_into_prompt = prompt_action.is_some()
+            || (prompt_focussed && chord.is_some_and(|c| matches!(c.key, Key::Char(_))));
+        let global_action = action_in(Pane::Global).filter(|_| !typed_into_prompt);
+        let treemap_action = action_in(Pane::Treemap).filter(|_| self.focussed == Treemap);
+
         let esc_navigates_back_in_main =
             config.keys.esc_navigates_back && key.code == Esc && self.focussed == Main;
 
         if esc_navigates_back_in_main {
             self.pending_exit = false;
             self.exit_node_with_traversal(&tree_view);
-        } else {
-            match (key.code, glob_focussed) {
-                (Esc, _) | (Char('q'), false) => {
-                    if let Some(result) = self.handle_quit(&mut tree_view, window) {
-                        return Ok(Some(result?));
-                    }
-                }
-                _ => {
-                    self.pending_exit = false;
-                }
+        } else if global_action == Some(Action::Quit) {
+            if let Some(result) = self.handle_quit(&mut tree_view, window) {
+                return Ok(Some(result?));
             }
+        } else {
+            self.pending_exit = false;
         }
 
         let mut handled = true;
-        match key.code {
-            Tab => {
+        match global_action {
+            Some(Action::CycleFocus) => {
                 self.cycle_focus(window);
             }
-            Char('/') if !glob_focussed => {
+            Some(Action::ToggleSearch) => {
                 self.toggle_glob_search(window);
             }
-            Char('?') if !glob_focussed => self.toggle_help_pane(window),
-            Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) && !glob_focussed => {
+            Some(Action::ToggleHelp) => self.toggle_help_pane(window),
+            Some(Action::ForceQuit) => {
                 return Ok(Some(WalkResult {
                     num_errors: self.stats.io_errors,
                 }));
@@ -396,21 +459,33 @@ impl AppState {
 
         if !handled {
             match self.focussed {
-                Mark => self.dispatch_to_mark_pane(
-                    key,
-                    window,
-                    &mut tree_view,
-                    *display,
-                    terminal,
-                    config,
-                ),
+                Mark => match action_in(Pane::Mark) {
+                    Some(Action::CopyMarked) => self.copy_marked(window, display.byte_format),
+                    Some(action) => {
+                        self.dispatch_to_mark_pane(
+                            action,
+                            window,
+                            &mut tree_view,
+                            *display,
+                            terminal,
+                            config,
+                        );
+                    }
+                    None => {}
+                },
                 Help => {
-                    window.help.as_mut().expect("help pane").process_events(key);
+                    if let Some(action) = action_in(Pane::Help) {
+                        window
+                            .help
+                            .as_mut()
+                            .expect("help pane")
+                            .process_events(action);
+                    }
                 }
                 Glob => {
                     let glob_pane = window.glob.as_mut().expect("glob pane");
-                    match key.code {
-                        Enter => self.search_glob_pattern(
+                    match prompt_action {
+                        Some(Action::Submit) => self.search_glob_pattern(
                             &mut tree_view,
                             &glob_pane.input,
                             glob_pane.case,
@@ -418,58 +493,138 @@ impl AppState {
                         _ => glob_pane.process_events(key),
                     }
                 }
-                Main => match key.code {
-                    Char('O') => self.open_that(&tree_view),
-                    Char(' ') => self.mark_entry(
+                Jump => {
+                    let jump_pane = window.jump.as_mut().expect("jump pane");
+                    match (prompt_action, &jump_pane.purpose) {
+                        (Some(Action::Submit), _) => {
+                            let input = std::mem::take(&mut jump_pane.input);
+                            let purpose = std::mem::take(&mut jump_pane.purpose);
+                            self.close_jump_prompt(window);
+                            match purpose {
+                                PromptPurpose::JumpToPath => self.jump_to_path(&tree_view, &input),
+                                PromptPurpose::NameBookmark(path) => {
+                                    self.add_bookmark(&input, path);
+                                }
+                                PromptPurpose::RunCommand => self.run_command(
+                                    &input,
+                                    &mut tree_view,
+                                    window,
+                                    terminal,
+                                    config,
+                                )?,
+                            }
+                        }
+                        (Some(Action::Complete), PromptPurpose::JumpToPath) => {
+                            self.complete_jump_path(&tree_view, jump_pane);
+                        }
+                        (Some(Action::Complete), PromptPurpose::RunCommand) => {
+                            self.complete_command_name(jump_pane, config);
+                        }
+                        _ => jump_pane.process_events(key),
+                    }
+                }
+                // The treemap is navigated spatially, and otherwise acts like the entries.
+                Treemap if treemap_action.is_some() => {
+                    let direction = match treemap_action {
+                        Some(Action::TreemapUp) => TreemapDirection::Up,
+                        Some(Action::TreemapDown) => TreemapDirection::Down,
+                        Some(Action::TreemapLeft) => TreemapDirection::Left,
+                        _ => TreemapDirection::Right,
+                    };
+                    self.change_treemap_selection(direction, window);
+                }
+                Main | Treemap => match action_in(Pane::Main) {
+                    Some(Action::Open) => self.open_that(&tree_view),
+                    Some(Action::RunCommand) => self.open_command_prompt(window, config),
+                    Some(Action::CopyPath) => self.copy_selected_path(&tree_view),
+                    Some(Action::QuitToDirectory) => {
+                        return Ok(Some(self.quit_to_directory(&tree_view)));
+                    }
+                    Some(Action::OpenShell) => {
+                        self.open_shell(&mut tree_view, window, terminal)?;
+                    }
+                    Some(Action::ToggleMark) => self.mark_entry(
                         CursorMode::KeepPosition,
                         MarkEntryMode::Toggle,
                         window,
                         &tree_view,
                     ),
-                    Char('x') => self.mark_entry(
+                    Some(Action::MarkAndAdvance) => self.mark_entry(
                         CursorMode::Advance,
                         MarkEntryMode::MarkForDeletion,
                         window,
                         &tree_view,
                     ),
-                    Char('a') => self.mark_all_entries(MarkEntryMode::Toggle, window, &tree_view),
-                    Char('t') => self.toggle_cleanup_candidates(&tree_view),
-                    Char('X') => self.mark_cleanup_candidates(window, &tree_view),
-                    Char('i') => self.toggle_gitignored_entries(&tree_view),
-                    Char('I') => self.mark_gitignored_entries(window, &tree_view),
-                    Char('o' | 'l') | Enter | Right => {
-                        self.enter_node_with_traversal(&tree_view);
-                    }
-                    Char('r') => self.refresh(&mut tree_view, window, Refresh::Selected)?,
-                    Char('R') => self.refresh(&mut tree_view, window, Refresh::AllInView)?,
-                    Char('H') | Home => self.change_entry_selection(CursorDirection::ToTop),
-                    Char('G') | End => self.change_entry_selection(CursorDirection::ToBottom),
-                    PageUp => self.change_entry_selection(CursorDirection::PageUp),
-                    Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        self.change_entry_selection(CursorDirection::PageUp);
-                    }
-                    Char('k') | Up => self.change_entry_selection(CursorDirection::Up),
-                    Char('j') | Down => self.change_entry_selection(CursorDirection::Down),
-                    PageDown => self.change_entry_selection(CursorDirection::PageDown),
-                    Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        self.change_entry_selection(CursorDirection::PageDown);
-                    }
-                    Char('s') => self.cycle_sorting(&tree_view),
-                    Char('m') => self.cycle_mtime_sorting(&tree_view),
-                    Char('M') => self.cycle_mtime_sort_mode(&tree_view),
-                    Char('c') => self.cycle_count_sorting(&tree_view),
-                    Char('C') => self.toggle_count_column(),
-                    Char('n') => self.cycle_name_sorting(&tree_view),
-                    Char('g' | 'S') => display.byte_vis.cycle(),
-                    Char('d') => self.mark_entry(
+                    Some(Action::ToggleMarkAndAdvance) => self.mark_entry(
                         CursorMode::Advance,
                         MarkEntryMode::Toggle,
                         window,
                         &tree_view,
                     ),
-                    Char('u' | 'h') | Backspace | Left => {
-                        self.exit_node_with_traversal(&tree_view);
+                    Some(Action::ToggleMarkAll) => {
+                        self.mark_all_entries(MarkEntryMode::Toggle, window, &tree_view);
+                    }
+                    Some(Action::ToggleCleanupCandidates) => {
+                        self.toggle_cleanup_candidates(&tree_view);
+                    }
+                    Some(Action::MarkCleanupCandidates) => {
+                        self.mark_cleanup_candidates(window, &tree_view);
                     }
+                    Some(Action::SweepCleanupCandidates) => {
+                        self.sweep_cleanup_candidates(&mut tree_view);
+                    }
+                    Some(Action::ToggleGitignored) => self.toggle_gitignored_entries(&tree_view),
+                    Some(Action::ToggleHidden) => self.toggle_hidden_entries(&mut tree_view),
+                    Some(Action::ToggleTreeMode) => self.toggle_tree_mode(&mut tree_view),
+                    Some(Action::ToggleExpansion) => self.toggle_expansion(&mut tree_view),
+                    Some(Action::MarkGitignored) => {
+                        self.mark_gitignored_entries(window, &tree_view);
+                    }
+                    Some(Action::Enter) => self.enter_selected(&mut tree_view, window)?,
+                    Some(Action::Ascend) => self.exit_node_with_traversal(&tree_view),
+                    Some(Action::JumpToPath) => self.open_jump_prompt(window),
+                    Some(Action::Bookmark) => self.bookmark_selected(window, &tree_view),
+                    Some(Action::ToggleBookmarks) => window.toggle_bookmarks_pane(),
+                    Some(Action::ToggleSplit) => self.toggle_split(window),
+                    Some(Action::SwitchSplitSide) => self.switch_split_side(window, &tree_view),
+                    Some(Action::ToggleSplitSync) => self.toggle_split_sync(&tree_view),
+                    Some(Action::Refresh) => {
+                        self.refresh(&mut tree_view, window, Refresh::Selected)?;
+                    }
+                    Some(Action::RefreshAll) => {
+                        self.refresh(&mut tree_view, window, Refresh::AllInView)?;
+                    }
+                    Some(Action::ToTop) => self.change_entry_selection(CursorDirection::ToTop),
+                    Some(Action::ToBottom) => {
+                        self.change_entry_selection(CursorDirection::ToBottom);
+                    }
+                    Some(Action::PageUp) => self.change_entry_selection(CursorDirection::PageUp),
+                    Some(Action::PageDown) => {
+                        self.change_entry_selection(CursorDirection::PageDown);
+                    }
+                    Some(Action::Up) => self.change_entry_selection(CursorDirection::Up),
+                    Some(Action::Down) => self.change_entry_selection(CursorDirection::Down),
+                    Some(Action::SortBySize) => self.cycle_sorting(&tree_view),
+                    Some(Action::SortByMtime) => self.cycle_mtime_sorting(&tree_view),
+                    Some(Action::CycleMtimeSortMode) => self.cycle_mtime_sort_mode(&tree_view),
+                    Some(Action::SortByCount) => self.cycle_count_sorting(&tree_view),
+                    Some(Action::ToggleCountColumn) => self.toggle_count_column(),
+                    Some(Action::SortByName) => self.cycle_name_sorting(&tree_view),
+                    Some(Action::SortByOwner) => self.cycle_owner_sorting(&tree_view),
+                    Some(Action::ToggleOwnerColumn) => self.toggle_owner_column(),
+                    Some(Action::ToggleGroupColumn) => self.toggle_group_column(),
+                    Some(Action::TogglePermissionsColumn) => self.toggle_permissions_column(),
+                    Some(Action::ToggleOwnerUsage) => window.toggle_owners_pane(),
+                    Some(Action::ToggleTreemap) => self.toggle_treemap_pane(window),
+                    Some(Action::TogglePreview) => window.toggle_preview_pane(),
+                    Some(Action::ToggleRepositories) => window.toggle_repositories_pane(),
+                    Some(Action::ToggleAlternateSizeColumn) => {
+                        self.toggle_alternate_size_column();
+                    }
+                    Some(Action::ToggleApparentSize) => {
+                        self.toggle_apparent_size(window, &mut tree_view);
+                    }
+                    Some(Action::CycleByteVisualization) => display.byte_vis.cycle(),
                     _ => {}
                 },
             }
@@ -479,6 +634,228 @@ impl AppState {
         Ok(None)
     }
 
+    /// Select and enter entries by clicking them, sort by clicking column headers, and scroll the
+    /// pane under the mouse with the wheel.
+    ///
+    /// Return `false` if the event didn't change anything.
+    fn process_mouse_event<B>(
+        &mut self,
+        mouse: MouseEvent,
+        window: &mut MainWindow,
+        tree_view: &mut TreeView<'_>,
+        display: DisplayOptions,
+        terminal: &mut Terminal<B>,
+        config: &Config,
+    ) -> Result<bool>
+    where
+        B: Backend,
+    {
+        let (column, row) = (mouse.column, mouse.row);
+        let Some(pane) = window.pane_at(column, row) else {
+            return Ok(false);
+        };
+        let scroll = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => None,
+            MouseEventKind::ScrollUp => Some((CursorDirection::Up, Action::Up)),
+            MouseEventKind::ScrollDown => Some((CursorDirection::Down, Action::Down)),
+            _ => return Ok(false),
+        };
+        self.reset_message();
+
+        match (scroll, pane) {
+            (None, FocussedPane::Main) => {
+                self.focus_pane(FocussedPane::Main, window);
+                if let Some(sort_column) = window.entries.sort_column_at(column, row) {
+                    match sort_column {
+                        SortColumn::Size => self.cycle_sorting(tree_view),
+                        SortColumn::MTime => self.cycle_mtime_sorting(tree_view),
+                        SortColumn::Count => self.cycle_count_sorting(tree_view),
+                        SortColumn::Owner => self.cycle_owner_sorting(tree_view),
+                        SortColumn::Name => self.cycle_name_sorting(tree_view),
+                    }
+                    return Ok(true);
+                }
+                let Some(clicked) = window
+                    .entries
+                    .entry_at(column, row)
+                    .and_then(|position| self.entries.get(position))
+                    .map(|entry| entry.index)
+                else {
+                    return Ok(true);
+                };
+                let now = Instant::now();
+                let is_double_click = self.last_click.is_some_and(|(time, index)| {
+                    index == clicked && now.duration_since(time) <= DOUBLE_CLICK_INTERVAL
+                });
+                self.navigation_mut().select(Some(clicked));
+                if is_double_click {
+                    self.last_click = None;
+                    self.enter_selected(tree_view, window)?;
+                } else {
+                    self.last_click = Some((now, clicked));
+                }
+            }
+            (None, FocussedPane::Treemap) => {
+                self.focus_pane(FocussedPane::Treemap, window);
+                let clicked = window
+                    .treemap
+                    .as_ref()
+                    .and_then(|pane| pane.entry_at(column, row));
+                if clicked.is_some() {
+                    self.navigation_mut().select(clicked);
+                }
+            }
+            (None, pane) => self.focus_pane(pane, window),
+            (Some((direction, _)), FocussedPane::Main | FocussedPane::Treemap) => {
+                self.change_entry_selection(direction);
+            }
+            (Some((_, action)), FocussedPane::Help) => {
+                if let Some(pane) = window.help.as_mut() {
+                    pane.process_events(action);
+                }
+            }
+            (Some((_, action)), FocussedPane::Mark) => {
+                // Only the focussed mark pane has a selection to move.
+                self.focus_pane(FocussedPane::Mark, window);
+                self.dispatch_to_mark_pane(action, window, tree_view, display, terminal, config);
+            }
+            (Some(_), FocussedPane::Glob | FocussedPane::Jump) => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn enter_selected(
+        &mut self,
+        tree_view: &mut TreeView<'_>,
+        window: &mut MainWindow,
+    ) -> Result<()> {
+        if self.selected_is_small_files() {
+            self.refresh(tree_view, window, Refresh::SmallFiles)?;
+        } else {
+            self.enter_node_with_traversal(tree_view);
+        }
+        Ok(())
+    }
+
+    /// Run the configured command named `name` on the selected or marked entries, and refresh
+    /// the directories of the marked entries, or the one in view, afterwards as the command may
+    /// have changed them. Marked entries that don't exist anymore are unmarked.
+    fn run_command<B>(
+        &mut self,
+        name: &str,
+        tree_view: &mut TreeView<'_>,
+        window: &mut MainWindow,
+        terminal: &mut Terminal<B>,
+        config: &Config,
+    ) -> Result<()>
+    where
+        B: Backend,
+    {
+        let name = name.trim();
+        let Some(template) = config.commands.get(name) else {
+            self.message = Some(format!("There is no command named '{name}'"));
+            return Ok(());
+        };
+        let Some((selected, paths)) = self.command_paths(window, tree_view) else {
+            return Ok(());
+        };
+        let marked: Option<Vec<_>> = window
+            .mark
+            .as_ref()
+            .map(|pane| pane.marked().keys().copied().collect());
+        let mut command = command::shell_command(&command::expand(template, &selected, &paths));
+        let message = match command::run_in_terminal(
+            &mut command,
+            self.terminal_modes,
+            &self.input_pause,
+            terminal,
+        ) {
+            Ok(status) if status.success() => format!("'{name}' finished"),
+            Ok(status) => format!("'{name}' failed with {status}"),
+            Err(err) => format!("{err:#}"),
+        };
+        match marked {
+            Some(marked) => {
+                window.mark = window.mark.take().and_then(MarkPane::retain_existing_paths);
+                self.refresh_parents_of(&marked, tree_view, window)?;
+            }
+            None => self.refresh(tree_view, window, Refresh::AllInView)?,
+        }
+        self.message = Some(message);
+        Ok(())
+    }
+
+    /// Refresh the directories containing `entries` one after another, leaving out those below
+    /// others as they are refreshed along with them.
+    fn refresh_parents_of(
+        &mut self,
+        entries: &[TreeIndex],
+        tree_view: &mut TreeView<'_>,
+        window: &mut MainWindow,
+    ) -> Result<()> {
+        let mut parents: Vec<_> = entries
+            .iter()
+            .filter_map(|&entry| tree_view.fs_parent_of(entry))
+            .collect();
+        parents.sort();
+        parents.dedup();
+        let ancestors = |dir| {
+            std::iter::successors(tree_view.fs_parent_of(dir), |&dir| {
+                tree_view.fs_parent_of(dir)
+            })
+        };
+        let mut directories: Vec<_> = parents
+            .iter()
+            .copied()
+            .filter(|&dir| !ancestors(dir).any(|ancestor| parents.contains(&ancestor)))
+            .collect();
+        let Some(first) = directories.pop() else {
+            return self.refresh(tree_view, window, Refresh::AllInView);
+        };
+        self.pending_refreshes = directories;
+        self.refresh(tree_view, window, Refresh::Directory(first))
+    }
+
+    /// Start the shell of the user in the selected directory, or in the one in view if no
+    /// directory is selected, and refresh that directory once the shell exits.
+    fn open_shell<B>(
+        &mut self,
+        tree_view: &mut TreeView<'_>,
+        window: &mut MainWindow,
+        terminal: &mut Terminal<B>,
+    ) -> Result<()>
+    where
+        B: Backend,
+    {
+        let selected_dir = self
+            .navigation()
+            .selected
+            .filter(|selected| tree_view.tree()[*selected].is_dir);
+        let (directory, what) = match selected_dir {
+            Some(selected) => (selected, Refresh::Selected),
+            None => (self.navigation().view_root, Refresh::AllInView),
+        };
+        let mut path = tree_view.path_of(directory);
+        if path.as_os_str().is_empty() {
+            path = PathBuf::from(".");
+        }
+
+        let mut shell = command::user_shell();
+        shell.current_dir(&path);
+        let message = match command::run_in_terminal(
+            &mut shell,
+            self.terminal_modes,
+            &self.input_pause,
+            terminal,
+        ) {
+            Ok(_) => format!("Left the shell in '{}'", path.display()),
+            Err(err) => format!("{err:#}"),
+        };
+        self.refresh(tree_view, window, what)?;
+        self.message = Some(message);
+        Ok(())
+    }
+
     fn refresh(
         &mut self,
         tree: &mut TreeView<'_>,
@@ -490,6 +867,11 @@ impl AppState {
             self.message = Some("Traversal already running".into());
             return Ok(());
         }
+        // Small files have no path of their own, refreshing them means listing them.
+        let what = match what {
+            Refresh::Selected if self.selected_is_small_files() => Refresh::SmallFiles,
+            what => what,
+        };
 
         let previous_selection = self.navigation().selected.and_then(|sel_index| {
             tree.tree().node_weight(sel_index).map(|w| {
@@ -543,31 +925,49 @@ impl AppState {
                     parent_index,
                 )
             }
-            Refresh::AllInView => {
-                let (paths, use_root_path, skip_root) = if self.navigation().view_root
-                    == tree.traversal.root_index
-                    && self.root_paths.len() > 1
-                {
-                    (self.root_paths.clone(), true, false)
-                } else {
-                    let mut path = tree.path_of(self.navigation().view_root);
-                    if path.to_str() == Some("") {
-                        path = PathBuf::from(".");
-                    }
-                    (vec![path], false, true)
+            Refresh::AllInView | Refresh::Directory(_) => {
+                let directory = match what {
+                    Refresh::Directory(directory) => directory,
+                    _ => self.navigation().view_root,
                 };
+                if tree.tree().node_weight(directory).is_none() {
+                    return Ok(());
+                }
+                let (paths, use_root_path, skip_root) =
+                    if directory == tree.traversal.root_index && self.root_paths.len() > 1 {
+                        (self.root_paths.clone(), true, false)
+                    } else {
+                        let mut path = tree.path_of(directory);
+                        if path.to_str() == Some("") {
+                            path = PathBuf::from(".");
+                        }
+                        (vec![path], false, true)
+                    };
 
-                (
-                    paths,
-                    false,
-                    skip_root,
-                    use_root_path,
-                    self.navigation().view_root,
-                    self.navigation().view_root,
-                )
+                (paths, false, skip_root, use_root_path, directory, directory)
+            }
+            Refresh::SmallFiles => {
+                let Some(selected) = self.navigation().selected else {
+                    return Ok(());
+                };
+                let directory = tree
+                    .fs_parent_of(selected)
+                    .expect("small files are always in a directory");
+                let mut path = tree.path_of(directory);
+                if path.to_str() == Some("") {
+                    path = PathBuf::from(".");
+                }
+                (vec![path], false, true, false, directory, directory)
             }
         };
 
+        // The refreshed directory is walked as root, but the depth limit is relative to the input paths.
+        let mut walk_options = self.walk_options.clone();
+        if let Some(max_depth) = &mut walk_options.max_depth {
+            let depth = std::iter::successors(Some(index), |&idx| tree.fs_parent_of(idx)).count();
+            *max_depth = max_depth.saturating_sub(depth.saturating_sub(2));
+        }
+
         tree.remove_entries(index, remove_root_node);
         tree.recompute_sizes_recursively(parent_index);
 
@@ -582,7 +982,7 @@ impl AppState {
         self.scan = Some(FilesystemScan {
             active_traversal: BackgroundTraversal::start(
                 parent_index,
-                &self.walk_options,
+                &walk_options,
                 paths,
                 self.walk_options
                     .ignore_patterns
@@ -590,7 +990,8 @@ impl AppState {
                     .map(|_| self.root_paths.as_slice()),
                 skip_root,
                 use_root_path,
-            )?,
+            )?
+            .expand_small_files(usize::from(matches!(what, Refresh::SmallFiles))),
             previous_selection,
         });
 
@@ -602,6 +1003,8 @@ impl AppState {
         TreeView {
             traversal,
             glob_tree_root: self.glob_navigation.as_ref().map(|n| n.tree_root),
+            show_hidden: self.show_hidden,
+            expanded: self.tree_mode.then(|| self.expanded.clone()),
         }
     }
 
@@ -611,7 +1014,6 @@ impl AppState {
         glob_pattern: &str,
         case: gix::glob::pattern::Case,
     ) {
-        use FocussedPane::Main;
         match glob_search(
             tree_view.tree(),
             self.navigation.view_root,
@@ -622,40 +1024,73 @@ impl AppState {
                 self.message = Some("No match found".into());
             }
             Ok(matches) => {
-                if let Some(glob_source) = &self.glob_navigation {
-                    tree_view.tree_mut().remove_node(glob_source.tree_root);
-                }
-
-                let tree_root = tree_view.tree_mut().add_node(EntryData::default());
-                let glob_source = Navigation {
-                    tree_root,
-                    view_root: tree_root,
-                    selected: Some(tree_root),
-                    ..Default::default()
-                };
-                self.glob_navigation = Some(glob_source);
+                self.cleanup_sweep = false;
+                self.show_search_results(tree_view, matches);
+            }
+            Err(err) => self.message = Some(err.to_string()),
+        }
+    }
 
-                for idx in matches {
-                    tree_view.tree_mut().add_edge(tree_root, idx, ());
-                }
+    /// Show all cleanup candidates below the directory in view like search results, largest
+    /// first unless sorted by size already.
+    fn sweep_cleanup_candidates(&mut self, tree_view: &mut TreeView<'_>) {
+        let candidates = super::cleanup::sweep_cleanup_candidates(
+            &self.cleanup_rules,
+            tree_view.tree(),
+            self.navigation.view_root,
+        );
+        if candidates.is_empty() {
+            self.message = Some("No cleanup candidates below this directory".into());
+            return;
+        }
+        if !matches!(
+            self.sorting,
+            SortMode::SizeDescending | SortMode::SizeAscending
+        ) {
+            self.sorting_before_sweep = Some(self.sorting);
+            self.sorting = SortMode::SizeDescending;
+        }
+        self.cleanup_sweep = true;
+        self.cleanup_candidates.get_or_insert_default();
+        self.show_search_results(tree_view, candidates);
+    }
 
-                let glob_tree_view = TreeView {
-                    traversal: tree_view.traversal,
-                    glob_tree_root: Some(tree_root),
-                };
-                let new_entries =
-                    glob_tree_view.sorted_entries(tree_root, self.sorting, self.entry_check());
+    /// Show `matches` as the entries of a new root, which replaces the one of previous results.
+    fn show_search_results(&mut self, tree_view: &mut TreeView<'_>, matches: Vec<TreeIndex>) {
+        use FocussedPane::Main;
+        if let Some(glob_source) = &self.glob_navigation {
+            tree_view.tree_mut().remove_node(glob_source.tree_root);
+        }
 
-                let new_entries = self
-                    .navigation_mut()
-                    .selected
-                    .map(|previously_selected| (previously_selected, new_entries));
+        let tree_root = tree_view.tree_mut().add_node(EntryData::default());
+        let glob_source = Navigation {
+            tree_root,
+            view_root: tree_root,
+            selected: Some(tree_root),
+            ..Default::default()
+        };
+        self.glob_navigation = Some(glob_source);
 
-                self.enter_node(new_entries, &glob_tree_view);
-                self.focussed = Main;
-            }
-            Err(err) => self.message = Some(err.to_string()),
+        for idx in matches {
+            tree_view.tree_mut().add_edge(tree_root, idx, ());
         }
+
+        let glob_tree_view = TreeView {
+            traversal: tree_view.traversal,
+            glob_tree_root: Some(tree_root),
+            show_hidden: self.show_hidden,
+            expanded: self.tree_mode.then(|| self.expanded.clone()),
+        };
+        let new_entries =
+            glob_tree_view.sorted_entries(tree_root, self.sorting, self.entry_check());
+
+        let new_entries = self
+            .navigation_mut()
+            .selected
+            .map(|previously_selected| (previously_selected, new_entries));
+
+        self.enter_node(new_entries, &glob_tree_view);
+        self.focussed = Main;
     }
 
     fn handle_quit(
@@ -663,7 +1098,7 @@ impl AppState {
         tree_view: &mut TreeView<'_>,
         window: &mut MainWindow,
     ) -> Option<std::result::Result<WalkResult, anyhow::Error>> {
-        use FocussedPane::{Glob, Help, Main, Mark};
+        use FocussedPane::{Glob, Help, Jump, Main, Mark, Treemap};
         match self.focussed {
             Main => {
                 if self.glob_navigation.is_some() {
@@ -681,7 +1116,7 @@ impl AppState {
                     }));
                 }
             }
-            Mark => self.focussed = Main,
+            Mark | Treemap => self.focussed = Main,
             Help => {
                 self.focussed = Main;
                 window.help = None;
@@ -689,6 +1124,7 @@ impl AppState {
             Glob => {
                 self.quit_glob_mode(tree_view, window);
             }
+            Jump => self.close_jump_prompt(window),
         }
         None
     }
@@ -700,6 +1136,13 @@ impl AppState {
             tree_view.tree_mut().remove_node(glob_source.tree_root);
         }
         self.glob_navigation = None;
+        self.cleanup_sweep = false;
+        // The sweep sorted by size, unless the ordering was changed since.
+        if let Some(sorting) = self.sorting_before_sweep.take()
+            && self.sorting == SortMode::SizeDescending
+        {
+            self.sorting = sorting;
+        }
         window.glob = None;
 
         tree_view.glob_tree_root.take();
@@ -715,8 +1158,12 @@ impl AppState {
 enum Refresh {
     /// Refresh the directory currently in view
     AllInView,
+    /// Refresh the given directory
+    Directory(TreeIndex),
     /// Refresh only the selected item
     Selected,
+    /// Refresh the directory of the selected small files, listing them individually
+    SmallFiles,
 }
 
 /// A [`Widget`] that renders by calling a function.
@@ -787,7 +1234,7 @@ where
             );
             // Disabled Crossterm color commands reset attributes such as reverse
             // video, so remove colors before they reach the backend.
-            if Colored::ansi_color_disabled_memoized() {
+            if dua::theme::no_color() {
                 strip_colors(frame.buffer_mut());
             }
         })
@@ -1,40 +1,58 @@
-use std::collections::BTreeSet;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
 
-use dua::traverse::TreeIndex;
+use dua::traverse::{Tree, TreeIndex};
 use gix::ignore::Kind;
+use petgraph::Direction;
 
 use super::{EntryDataBundle, tree_view::TreeView};
+use crate::interactive::path_of;
 
-pub fn gitignored_entries(
-    tree_view: &TreeView<'_>,
-    view_root: TreeIndex,
-    entries: &[EntryDataBundle],
-) -> BTreeSet<TreeIndex> {
-    use std::path::{Path, PathBuf};
-
-    fn absolute_path(path: PathBuf, cwd: &Path) -> PathBuf {
-        if path.is_absolute() {
-            path
-        } else {
-            cwd.join(path)
-        }
+fn absolute_path(path: PathBuf, cwd: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path
+    } else {
+        cwd.join(path)
     }
+}
 
-    fn mode(entry: &EntryDataBundle) -> gix::index::entry::Mode {
-        if entry.is_dir {
-            gix::index::entry::Mode::DIR
-        } else {
-            gix::index::entry::Mode::FILE
-        }
-    }
+fn open_options(trust: gix::sec::Trust) -> gix::open::Options {
+    use gix::sec::trust::DefaultForLevel;
 
-    fn open_options(trust: gix::sec::Trust) -> gix::open::Options {
-        use gix::sec::trust::DefaultForLevel;
+    gix::open::Options::default_for_level(trust).config_overrides(["gitoxide.parsePrecious=true"])
+}
 
-        gix::open::Options::default_for_level(trust)
-            .config_overrides(["gitoxide.parsePrecious=true"])
+/// Return the repository `path` is in, if it has a worktree.
+fn discover(path: &Path) -> Option<gix::Repository> {
+    let trust_map = gix::sec::trust::Mapping {
+        full: open_options(gix::sec::Trust::Full),
+        reduced: open_options(gix::sec::Trust::Reduced),
+    };
+    let repo = gix::ThreadSafeRepository::discover_opts(
+        path,
+        gix::discover::upwards::Options::default(),
+        trust_map,
+    )
+    .ok()?
+    .to_thread_local();
+    repo.workdir().is_some().then_some(repo)
+}
+
+fn mode(is_dir: bool) -> gix::index::entry::Mode {
+    if is_dir {
+        gix::index::entry::Mode::DIR
+    } else {
+        gix::index::entry::Mode::FILE
     }
+}
 
+pub fn gitignored_entries(
+    tree_view: &TreeView<'_>,
+    view_root: TreeIndex,
+    entries: &[EntryDataBundle],
+) -> BTreeSet<TreeIndex> {
     let current_path = tree_view.path_of(view_root);
     let current_path = if current_path.as_os_str().is_empty() {
         Path::new(".").to_owned()
@@ -42,18 +60,9 @@ pub fn gitignored_entries(
         current_path
     };
 
-    let trust_map = gix::sec::trust::Mapping {
-        full: open_options(gix::sec::Trust::Full),
-        reduced: open_options(gix::sec::Trust::Reduced),
-    };
-    let Ok(repo) = gix::ThreadSafeRepository::discover_opts(
-        &current_path,
-        gix::discover::upwards::Options::default(),
-        trust_map,
-    ) else {
+    let Some(repo) = discover(&current_path) else {
         return BTreeSet::new();
     };
-    let repo = repo.to_thread_local();
     let Ok(cwd) = std::env::current_dir() else {
         return BTreeSet::new();
     };
@@ -74,10 +83,13 @@ pub fn gitignored_entries(
 
     entries
         .iter()
+        .filter(|entry| !entry.is_small_files)
         .filter_map(|entry| {
             let path = absolute_path(tree_view.path_of(entry.index), &cwd);
             let relative_path = path.strip_prefix(&workdir).ok()?;
-            let platform = excludes.at_path(relative_path, Some(mode(entry))).ok()?;
+            let platform = excludes
+                .at_path(relative_path, Some(mode(entry.is_dir)))
+                .ok()?;
             platform
                 .excluded_kind()
                 .is_some_and(|kind| matches!(kind, Kind::Expendable))
@@ -85,3 +97,193 @@ pub fn gitignored_entries(
         })
         .collect()
 }
+
+/// The bytes of a git repository's worktree, by how git sees them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RepositoryUsage {
+    /// The directory of the worktree.
+    pub path: PathBuf,
+    /// Bytes of files in the index.
+    pub tracked: u128,
+    /// Bytes of files that are neither in the index nor ignored, and of directories whose
+    /// contents weren't traversed due to `--max-depth`.
+    pub untracked: u128,
+    /// Bytes of ignored files, which can be deleted as they are recreated when needed.
+    pub ignored: u128,
+    /// Bytes of files ignored with a `$` pattern, which git keeps as they are valuable.
+    pub precious: u128,
+}
+
+/// Return the usage of every git repository whose worktree is one of the directories below
+/// `root`, or contains one of its entries, largest ignored bytes first.
+///
+/// Repositories nested in others, like submodules, are accounted for on their own, and the
+/// `.git` directory isn't accounted for at all. Directories are only accounted for as a whole
+/// if they are ignored, otherwise just their files are. Small files that were collapsed into
+/// one entry count as untracked, as their paths aren't known.
+pub fn usage_by_repository(tree: &Tree, root: TreeIndex) -> Vec<RepositoryUsage> {
+    let Ok(cwd) = std::env::current_dir() else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    // The entries of the root are in the repository of the directory they are in, which can
+    // differ between them as they are the paths the traversal started from. Each of these
+    // directories is listed with the worktree it is in, if any, and the entries in it.
+    let mut parents: Vec<(PathBuf, Option<_>, Vec<_>)> = Vec::new();
+    // Each directory outside of the repositories found so far, along with whether repositories
+    // it is in are discovered, too.
+    let mut dirs = Vec::new();
+    for entry in tree.neighbors_directed(root, Direction::Outgoing) {
+        let path = absolute_path(path_of(tree, entry, None), &cwd);
+        let parent = path.parent().map(Path::to_owned).unwrap_or_default();
+        let position = parents
+            .iter()
+            .position(|(dir, _, _)| *dir == parent)
+            .unwrap_or_else(|| {
+                let worktree = discover(&parent).and_then(|repo| {
+                    let workdir = absolute_path(repo.workdir()?.to_owned(), &cwd);
+                    Some((repo, workdir))
+                });
+                parents.push((parent, worktree, Vec::new()));
+                parents.len() - 1
+            });
+        let (_, worktree, entries) = &mut parents[position];
+        match worktree
+            .as_ref()
+            .and_then(|(_, workdir)| path.strip_prefix(workdir).ok())
+        {
+            Some(relative_path) => entries.push((entry, relative_path.to_owned())),
+            None if tree[entry].is_dir => dirs.push((entry, true)),
+            None => {}
+        }
+    }
+    for (_, worktree, entries) in parents {
+        if let Some((repo, workdir)) = worktree.filter(|_| !entries.is_empty()) {
+            let nested = repository_usage(tree, entries, &repo, &workdir, &mut out);
+            dirs.extend(nested.into_iter().map(|dir| (dir, false)));
+        }
+    }
+
+    while let Some((dir, discover_upwards)) = dirs.pop() {
+        let path = absolute_path(path_of(tree, dir, None), &cwd);
+        let repo = if discover_upwards || is_worktree(tree, dir) {
+            discover(&path)
+        } else {
+            None
+        };
+        let worktree = repo.and_then(|repo| {
+            let workdir = absolute_path(repo.workdir()?.to_owned(), &cwd);
+            let relative_dir = path.strip_prefix(&workdir).ok()?.to_owned();
+            Some((repo, workdir, relative_dir))
+        });
+        match worktree {
+            Some((repo, workdir, relative_dir)) => {
+                let entries = tree
+                    .neighbors_directed(dir, Direction::Outgoing)
+                    .map(|entry| (entry, relative_dir.join(&tree[entry].name)))
+                    .collect();
+                let nested = repository_usage(tree, entries, &repo, &workdir, &mut out);
+                dirs.extend(nested.into_iter().map(|dir| (dir, false)));
+            }
+            None => dirs.extend(directories(tree, dir).map(|dir| (dir, false))),
+        }
+    }
+    out.sort_by_key(|usage| std::cmp::Reverse(usage.ignored));
+    out
+}
+
+fn directories(tree: &Tree, dir: TreeIndex) -> impl Iterator<Item = TreeIndex> + '_ {
+    tree.neighbors_directed(dir, Direction::Outgoing)
+        .filter(|index| tree[*index].is_dir)
+}
+
+fn is_worktree(tree: &Tree, dir: TreeIndex) -> bool {
+    tree.neighbors_directed(dir, Direction::Outgoing)
+        .any(|index| tree[index].name == Path::new(".git"))
+}
+
+/// Add the usage of `entries` of the worktree of `repo` at `workdir`, along with their paths
+/// relative to it, to the one of the worktree in `out`, and return the worktrees of other
+/// repositories among and below them.
+fn repository_usage(
+    tree: &Tree,
+    mut entries: Vec<(TreeIndex, PathBuf)>,
+    repo: &gix::Repository,
+    workdir: &Path,
+    out: &mut Vec<RepositoryUsage>,
+) -> Vec<TreeIndex> {
+    let mut nested = Vec::new();
+    let Ok(index) = repo.index_or_empty() else {
+        return nested;
+    };
+    let Ok(mut excludes) = repo.excludes(
+        &index,
+        None,
+        gix::worktree::stack::state::ignore::Source::WorktreeThenIdMappingIfNotSkipped,
+    ) else {
+        return nested;
+    };
+
+    let position = out
+        .iter()
+        .position(|usage| usage.path == workdir)
+        .unwrap_or_else(|| {
+            out.push(RepositoryUsage {
+                path: workdir.to_owned(),
+                ..Default::default()
+            });
+            out.len() - 1
+        });
+    let usage = &mut out[position];
+    while let Some((index_in_tree, path)) = entries.pop() {
+        let entry = &tree[index_in_tree];
+        if entry.is_small_files {
+            usage.untracked += entry.size;
+            continue;
+        }
+        // The entries of the roots are named by their whole path.
+        if path.file_name() == Some(".git".as_ref()) {
+            continue;
+        }
+        let repo_path = gix::path::to_unix_separators_on_windows(gix::path::into_bstr(&path));
+        let kind = excludes
+            .at_path(&path, Some(mode(entry.is_dir)))
+            .ok()
+            .and_then(|platform| platform.excluded_kind());
+        if entry.is_dir {
+            if is_worktree(tree, index_in_tree) {
+                nested.push(index_in_tree);
+                continue;
+            }
+            let mut prefix = repo_path.into_owned();
+            prefix.push(b'/');
+            // Tracked files may be in ignored directories, which are then looked into.
+            let has_tracked_files = index.prefixed_entries(prefix.as_ref()).is_some();
+            match kind {
+                Some(Kind::Expendable) if !has_tracked_files => usage.ignored += entry.size,
+                Some(Kind::Precious) if !has_tracked_files => usage.precious += entry.size,
+                // Directories summarized at the maximum depth have no children to look into.
+                _ if tree
+                    .neighbors_directed(index_in_tree, Direction::Outgoing)
+                    .next()
+                    .is_none() =>
+                {
+                    usage.untracked += entry.size;
+                }
+                _ => entries.extend(
+                    tree.neighbors_directed(index_in_tree, Direction::Outgoing)
+                        .map(|child| (child, path.join(&tree[child].name))),
+                ),
+            }
+        } else if index.entry_by_path(repo_path.as_ref()).is_some() {
+            usage.tracked += entry.size;
+        } else {
+            match kind {
+                Some(Kind::Expendable) => usage.ignored += entry.size,
+                Some(Kind::Precious) => usage.precious += entry.size,
+                None => usage.untracked += entry.size,
+            }
+        }
+    }
+    nested
+}
@@ -1,15 +1,18 @@
 use crate::interactive::{
     DisplayOptions, EntryDataBundle,
+    app::split::SplitView,
     app::tree_view::TreeView,
-    widgets::{Column, GlobPane, HelpPane, MainWindow, MarkMode, MarkPane},
+    widgets::{
+        Column, Entries, GlobPane, HelpPane, JumpPane, MainWindow, MarkMode, MarkPane,
+        TreemapDirection, complete_name, complete_path, resolve_path,
+    },
 };
-use crossterm::event::KeyEvent;
-use dua::Config;
 use dua::traverse::TreeIndex;
+use dua::{ByteFormat, Config, keys::Action};
 use std::{
     collections::BTreeSet,
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::atomic::{AtomicUsize, Ordering},
     thread,
     time::{Duration, Instant},
@@ -17,13 +20,16 @@ use std::{
 use tui::{Terminal, backend::Backend};
 
 use super::{
-    notification,
+    clipboard, notification,
     state::{
-        AppState,
-        FocussedPane::{Glob, Help, Main, Mark},
+        AppState, FocussedPane,
+        FocussedPane::{Glob, Help, Jump, Main, Mark, Treemap},
     },
 };
 
+/// Shown when acting on the small files of a directory, which are only known in aggregate.
+const SMALL_FILES_MESSAGE: &str = "Small files must be listed with Enter first";
+
 #[derive(Copy, Clone)]
 pub enum CursorMode {
     Advance,
@@ -81,6 +87,10 @@ impl CursorDirection {
 
 impl AppState {
     pub fn open_that(&mut self, tree_view: &TreeView<'_>) {
+        if self.selected_is_small_files() {
+            self.message = Some(SMALL_FILES_MESSAGE.into());
+            return;
+        }
         if let Some(idx) = self.navigation().selected {
             let path = tree_view.path_of(idx);
             if let Err(err) = open::that(&path) {
@@ -89,6 +99,61 @@ impl AppState {
         }
     }
 
+    /// Remember the absolute path of the directory in view to change into it after quitting.
+    pub fn quit_to_directory(&mut self, tree_view: &TreeView<'_>) -> dua::WalkResult {
+        let mut path = tree_view.path_of(self.navigation().view_root);
+        if path.as_os_str().is_empty() {
+            path = PathBuf::from(".");
+        }
+        self.quit_directory = Some(absolute(path));
+        dua::WalkResult {
+            num_errors: self.stats.io_errors,
+        }
+    }
+
+    /// Copy the absolute path of the selected entry to the clipboard.
+    pub fn copy_selected_path(&mut self, tree_view: &TreeView<'_>) {
+        if self.selected_is_small_files() {
+            self.message = Some(SMALL_FILES_MESSAGE.into());
+            return;
+        }
+        if let Some(idx) = self.navigation().selected {
+            let path = absolute(tree_view.path_of(idx));
+            let message = format!("Copied '{}'", path.display());
+            self.copy_to_clipboard(&path.to_string_lossy(), message);
+        }
+    }
+
+    /// Copy a line with the size and absolute path of each marked entry to the clipboard.
+    pub fn copy_marked(&mut self, window: &MainWindow, format: ByteFormat) {
+        let Some(pane) = window.mark.as_ref() else {
+            return;
+        };
+        let lines: Vec<_> = pane
+            .marked()
+            .values()
+            .map(|mark| {
+                let path = absolute(mark.path.clone());
+                format!("{} {}", format.display(mark.size), path.display())
+            })
+            .collect();
+        let message = format!("Copied {} marked entries", lines.len());
+        self.copy_to_clipboard(&lines.join("\n"), message);
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str, message: String) {
+        // Only a terminal set up for interactive use is there to pass the text on.
+        if self.terminal_modes.is_none() {
+            self.message =
+                Some("The clipboard is unavailable without an interactive terminal".into());
+            return;
+        }
+        self.message = Some(match clipboard::copy(text) {
+            Ok(()) => message,
+            Err(err) => format!("Could not copy to the clipboard: {err}"),
+        });
+    }
+
     pub fn exit_node_with_traversal(&mut self, tree_view: &TreeView<'_>) {
         let entries = self.entries_for_exit_node(tree_view);
         self.exit_node(entries, tree_view);
@@ -168,6 +233,25 @@ impl AppState {
         self.navigation_mut().select(next_index);
     }
 
+    /// Select the entry whose rectangle in the treemap is next to the selected one.
+    pub fn change_treemap_selection(&mut self, direction: TreemapDirection, window: &MainWindow) {
+        if let Some(next_index) = window
+            .treemap
+            .as_ref()
+            .and_then(|pane| pane.neighbour(direction))
+        {
+            self.navigation_mut().select(Some(next_index));
+        }
+    }
+
+    /// Show the treemap pane, or hide it and move the focus away from it.
+    pub fn toggle_treemap_pane(&mut self, window: &mut MainWindow) {
+        window.toggle_treemap_pane();
+        if window.treemap.is_none() && self.focussed == Treemap {
+            self.focussed = Main;
+        }
+    }
+
     pub fn cycle_sorting(&mut self, tree_view: &TreeView<'_>) {
         self.sorting.toggle_size();
         self.entries = tree_view.sorted_entries(
@@ -208,6 +292,16 @@ impl AppState {
         self.update_entry_annotations(tree_view);
     }
 
+    pub fn cycle_owner_sorting(&mut self, tree_view: &TreeView<'_>) {
+        self.sorting.toggle_owner();
+        self.entries = tree_view.sorted_entries(
+            self.navigation().view_root,
+            self.sorting,
+            self.entry_check(),
+        );
+        self.update_entry_annotations(tree_view);
+    }
+
     pub fn cycle_mtime_sort_mode(&mut self, tree_view: &TreeView<'_>) {
         if self.sorting.mtime_sort().is_some() {
             self.sorting.cycle_mtime_sort();
@@ -225,6 +319,49 @@ impl AppState {
         self.toggle_column(Column::Count);
     }
 
+    pub fn toggle_owner_column(&mut self) {
+        self.toggle_column(Column::Owner);
+    }
+
+    pub fn toggle_group_column(&mut self) {
+        self.toggle_column(Column::Group);
+    }
+
+    pub fn toggle_permissions_column(&mut self) {
+        self.toggle_column(Column::Permissions);
+    }
+
+    pub fn toggle_alternate_size_column(&mut self) {
+        self.toggle_column(Column::AlternateSize);
+    }
+
+    pub fn toggle_apparent_size(&mut self, window: &mut MainWindow, tree_view: &mut TreeView<'_>) {
+        if self.scan.is_some() {
+            // Entries still arriving would be added with the previous kind of size.
+            self.message = Some("Sizes can be switched once the scan is done".into());
+            return;
+        }
+        self.walk_options.apparent_size = !self.walk_options.apparent_size;
+        tree_view.traversal.swap_sizes();
+        if let Some(pane) = window.mark.as_mut() {
+            pane.update_sizes(tree_view.tree());
+        }
+        self.entries = tree_view.sorted_entries(
+            self.navigation().view_root,
+            self.sorting,
+            self.entry_check(),
+        );
+        self.update_entry_annotations(tree_view);
+        self.message = Some(
+            if self.walk_options.apparent_size {
+                "Showing apparent sizes"
+            } else {
+                "Showing disk usage"
+            }
+            .into(),
+        );
+    }
+
     pub fn toggle_cleanup_candidates(&mut self, tree_view: &TreeView<'_>) {
         self.cleanup_candidates = self.cleanup_candidates.is_none().then(BTreeSet::new);
         self.update_entry_annotations(tree_view);
@@ -237,6 +374,157 @@ impl AppState {
         self.reset_message();
     }
 
+    pub fn toggle_hidden_entries(&mut self, tree_view: &mut TreeView<'_>) {
+        self.show_hidden = !self.show_hidden;
+        tree_view.show_hidden = self.show_hidden;
+        self.entries = tree_view.sorted_entries(
+            self.navigation().view_root,
+            self.sorting,
+            self.entry_check(),
+        );
+        let selected = self.navigation().selected;
+        if !self.entries.iter().any(|e| Some(e.index) == selected) {
+            let first = self.entries.first().map(|e| e.index);
+            self.navigation_mut().select(first);
+        }
+        self.update_entry_annotations(tree_view);
+    }
+
+    /// Show the current directory a second time next to the navigated one, or hide it.
+    pub fn toggle_split(&mut self, window: &mut MainWindow) {
+        if self.split.take().is_some() {
+            window.split = None;
+        } else {
+            self.split = Some(SplitView::new(self.navigation.clone()));
+            window.split = Some(Entries::default());
+        }
+    }
+
+    /// Navigate the other side of the split view, leaving the current one in place.
+    pub fn switch_split_side(&mut self, window: &mut MainWindow, tree_view: &TreeView<'_>) {
+        let Some(split) = self.split.as_mut() else {
+            self.message = Some("There is only one side as the view isn't split".into());
+            return;
+        };
+        if self.glob_navigation.is_some() {
+            self.message = Some("The search must be closed to switch sides".into());
+            return;
+        }
+        split.switch_sides(&mut self.navigation, &mut self.entries);
+        if let Some(entries) = window.split.as_mut() {
+            std::mem::swap(entries, &mut window.entries);
+        }
+        self.entries =
+            tree_view.sorted_entries(self.navigation.view_root, self.sorting, self.entry_check());
+        self.update_entry_annotations(tree_view);
+    }
+
+    /// Make the other side of the split view follow the navigated one by relative path, or
+    /// stop following.
+    pub fn toggle_split_sync(&mut self, tree_view: &TreeView<'_>) {
+        let Some(split) = self.split.as_mut() else {
+            self.message = Some("There is nothing to sync as the view isn't split".into());
+            return;
+        };
+        split.sync = match split.sync {
+            Some(_) => None,
+            None => Some((self.navigation.view_root, split.navigation.view_root)),
+        };
+        self.message = Some(if split.sync.is_some() {
+            format!(
+                "'{}' follows '{}'",
+                tree_view.current_path(split.navigation.view_root).display(),
+                tree_view.current_path(self.navigation.view_root).display()
+            )
+        } else {
+            "Sides are navigated independently".into()
+        });
+    }
+
+    /// Let the other side of the split view follow the navigated one, and list its entries.
+    pub fn update_split_view(&mut self, tree_view: &TreeView<'_>) {
+        let (view_root, selected) = (self.navigation().view_root, self.navigation().selected);
+        let (sorting, check) = (self.sorting, self.entry_check());
+        let Some(split) = self.split.as_mut() else {
+            return;
+        };
+        let navigation = &mut split.navigation;
+        if tree_view.tree().node_weight(navigation.view_root).is_none() {
+            // The directory was deleted.
+            navigation.view_root = navigation.tree_root;
+            navigation.selected = None;
+            split.sync = None;
+        }
+        split.follow(tree_view, view_root, selected);
+
+        let navigation = &mut split.navigation;
+        split.entries = tree_view.sorted_entries(navigation.view_root, sorting, check);
+        if !split
+            .entries
+            .iter()
+            .any(|entry| Some(entry.index) == navigation.selected)
+        {
+            navigation.selected = split.entries.first().map(|entry| entry.index);
+        }
+    }
+
+    pub fn toggle_tree_mode(&mut self, tree_view: &mut TreeView<'_>) {
+        self.tree_mode = !self.tree_mode;
+        self.update_tree_display(tree_view);
+    }
+
+    /// Expand the selected directory or collapse it if it's expanded. Files collapse the
+    /// directory they are listed in. Tree mode is turned on if needed.
+    pub fn toggle_expansion(&mut self, tree_view: &mut TreeView<'_>) {
+        let Some(entry) = self
+            .navigation()
+            .selected
+            .and_then(|selected| self.entries.iter().find(|e| e.index == selected))
+        else {
+            return;
+        };
+        let (index, is_dir, depth) = (entry.index, entry.is_dir, entry.depth);
+        if !self.tree_mode {
+            self.tree_mode = true;
+            self.expanded.insert(index);
+        } else if is_dir && !entry.is_small_files {
+            if !self.expanded.remove(&index) {
+                self.expanded.insert(index);
+            }
+        } else if depth > 0
+            && let Some(parent) = tree_view.fs_parent_of(index)
+        {
+            self.expanded.remove(&parent);
+            self.navigation_mut().select(Some(parent));
+        }
+        self.update_tree_display(tree_view);
+    }
+
+    /// Apply a change of the tree mode or expanded directories to the displayed entries.
+    fn update_tree_display(&mut self, tree_view: &mut TreeView<'_>) {
+        self.expanded.retain(|index| tree_view.exists(*index));
+        tree_view.expanded = self.tree_mode.then(|| self.expanded.clone());
+        self.entries = tree_view.sorted_entries(
+            self.navigation().view_root,
+            self.sorting,
+            self.entry_check(),
+        );
+        let selected = self.navigation().selected;
+        if !self.entries.iter().any(|e| Some(e.index) == selected) {
+            // Select the directory the selected entry was collapsed into.
+            let mut ancestor = selected;
+            while let Some(index) = ancestor {
+                if self.entries.iter().any(|e| e.index == index) {
+                    break;
+                }
+                ancestor = tree_view.fs_parent_of(index);
+            }
+            let selected = ancestor.or_else(|| self.entries.first().map(|e| e.index));
+            self.navigation_mut().select(selected);
+        }
+        self.update_entry_annotations(tree_view);
+    }
+
     fn toggle_column(&mut self, column: Column) {
         if self.show_columns.contains(&column) {
             self.show_columns.remove(&column);
@@ -247,12 +535,186 @@ impl AppState {
 
     pub fn toggle_glob_search(&mut self, window: &mut MainWindow) {
         self.focussed = match self.focussed {
-            Main | Mark | Help => {
+            Main | Mark | Help | Treemap => {
                 window.glob = Some(GlobPane::default());
                 Glob
             }
-            Glob => unreachable!("BUG: glob pane must catch the input leading here"),
+            Glob | Jump => unreachable!("BUG: prompts must catch the input leading here"),
+        }
+    }
+
+    pub fn open_jump_prompt(&mut self, window: &mut MainWindow) {
+        window.jump = Some(JumpPane::default());
+        self.focussed = Jump;
+    }
+
+    pub fn close_jump_prompt(&mut self, window: &mut MainWindow) {
+        window.jump = None;
+        self.focussed = Main;
+    }
+
+    /// Prompt for the name of a configured command, listing all of them.
+    pub fn open_command_prompt(&mut self, window: &mut MainWindow, config: &Config) {
+        if config.commands.is_empty() {
+            self.message =
+                Some("There are no commands in the [commands] table of the configuration".into());
+            return;
+        }
+        window.jump = Some(JumpPane::run_command());
+        self.focussed = Jump;
+        self.message = Some(itertools::join(config.commands.keys(), "  "));
+    }
+
+    /// Return the path of the selected entry and the paths of the marked ones, or the selected
+    /// one if none are marked, to run a command on.
+    pub fn command_paths(
+        &mut self,
+        window: &MainWindow,
+        tree_view: &TreeView<'_>,
+    ) -> Option<(PathBuf, Vec<PathBuf>)> {
+        if self.selected_is_small_files() {
+            self.message = Some(SMALL_FILES_MESSAGE.into());
+            return None;
+        }
+        let Some(selected) = self.navigation().selected.map(|idx| tree_view.path_of(idx)) else {
+            self.message = Some("Select an entry to run a command on".into());
+            return None;
+        };
+        let marked: Vec<_> = window
+            .mark
+            .as_ref()
+            .map(|pane| {
+                pane.marked()
+                    .values()
+                    .map(|mark| mark.path.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let paths = if marked.is_empty() {
+            vec![selected.clone()]
+        } else {
+            marked
+        };
+        Some((selected, paths))
+    }
+
+    /// Complete the name of the command typed into the prompt, listing the candidates if there
+    /// are many.
+    pub fn complete_command_name(&mut self, jump_pane: &mut JumpPane, config: &Config) {
+        let completion =
+            complete_name(config.commands.keys().map(String::as_str), &jump_pane.input);
+        if completion.candidates.len() > 1 {
+            self.message = Some(completion.candidates.join("  "));
+        }
+        jump_pane.input = completion.input;
+    }
+
+    /// Complete the path or bookmark typed into the jump prompt, listing the candidates if
+    /// there are many.
+    pub fn complete_jump_path(&mut self, tree_view: &TreeView<'_>, jump_pane: &mut JumpPane) {
+        let completion = if let Some(name) = jump_pane.input.strip_prefix('@') {
+            let mut completion =
+                complete_name(self.bookmarks.by_name().keys().map(String::as_str), name);
+            completion.input.insert(0, '@');
+            completion
+        } else {
+            let input = Self::path_relative_to_roots(&jump_pane.input);
+            complete_path(tree_view.tree(), self.navigation().tree_root, &input)
+        };
+        if completion.candidates.len() > 1 {
+            self.message = Some(completion.candidates.join("  "));
+        }
+        jump_pane.input = completion.input;
+    }
+
+    /// Bookmark the selected entry, asking for a name, or remove its bookmark if it has one.
+    pub fn bookmark_selected(&mut self, window: &mut MainWindow, tree_view: &TreeView<'_>) {
+        if self.selected_is_small_files() {
+            self.message = Some(SMALL_FILES_MESSAGE.into());
+            return;
+        }
+        let Some(selected) = self.navigation().selected else {
+            return;
+        };
+        let path = tree_view.path_of(selected);
+        if let Some(name) = self.bookmarks.name_of(&path).map(ToOwned::to_owned) {
+            self.message = Some(match self.bookmarks.remove(&name) {
+                Ok(()) => format!("Removed bookmark '{name}'"),
+                Err(err) => format!("{err:#}"),
+            });
+        } else {
+            window.jump = Some(JumpPane::name_bookmark(path));
+            self.focussed = Jump;
+        }
+    }
+
+    /// Bookmark `path` as `name`, replacing the bookmark of the same name.
+    pub fn add_bookmark(&mut self, name: &str, path: PathBuf) {
+        let name = name.trim().trim_start_matches('@');
+        if name.is_empty() {
+            self.message = Some("Bookmarks need a name".into());
+            return;
+        }
+        let message = format!("Bookmarked '{}' as '{name}'", path.display());
+        self.message = Some(match self.bookmarks.insert(name.to_owned(), path) {
+            Ok(()) => message,
+            Err(err) => format!("{err:#}"),
+        });
+    }
+
+    /// Navigate to `path` relative to the roots, or to the bookmark named after an `@`, as if
+    /// each directory on the way was entered, selecting the entry it points to if it's not a
+    /// directory with entries.
+    pub fn jump_to_path(&mut self, tree_view: &TreeView<'_>, path: &str) {
+        let path = if let Some(name) = path.strip_prefix('@') {
+            let Some(path) = self.bookmarks.get(name) else {
+                self.message = Some(format!("There is no bookmark named '{name}'"));
+                return;
+            };
+            path.to_string_lossy().into_owned()
+        } else {
+            Self::path_relative_to_roots(path)
+        };
+        let root = self.navigation().tree_root;
+        let mut directories = match resolve_path(tree_view.tree(), root, Path::new(&path)) {
+            Ok(directories) => directories,
+            Err(err) => {
+                self.message = Some(err.to_string());
+                return;
+            }
+        };
+        let mut entries = tree_view.sorted_entries(
+            directories.last().copied().unwrap_or(root),
+            self.sorting,
+            self.entry_check(),
+        );
+        let mut selected = None;
+        if entries.is_empty()
+            && let Some(target) = directories.pop()
+        {
+            selected = Some(target);
+            entries = tree_view.sorted_entries(
+                directories.last().copied().unwrap_or(root),
+                self.sorting,
+                self.entry_check(),
+            );
         }
+        self.navigation_mut()
+            .enter_path(&directories, selected, &entries);
+        self.entries = entries;
+        self.update_entry_annotations(tree_view);
+    }
+
+    /// Make an absolute `path` relative to the working directory, which the roots are
+    /// relative to unless they are absolute themselves.
+    fn path_relative_to_roots(path: &str) -> String {
+        std::env::current_dir()
+            .ok()
+            .and_then(|cwd| Path::new(path).strip_prefix(cwd).ok().map(Path::to_owned))
+            .map_or_else(
+                || path.to_owned(),
+                |path| path.to_string_lossy().into_owned(),
+            )
     }
 
     pub fn reset_message(&mut self) {
@@ -268,7 +730,7 @@ impl AppState {
 
     pub fn toggle_help_pane(&mut self, window: &mut MainWindow) {
         self.focussed = match self.focussed {
-            Main | Mark | Glob => {
+            Main | Mark | Glob | Jump | Treemap => {
                 window.help = Some(HelpPane::with_locale_from_env());
                 Help
             }
@@ -278,29 +740,47 @@ impl AppState {
             }
         }
     }
+    /// Move the focus to `pane`, unless a path is being typed into the jump prompt.
+    pub fn focus_pane(&mut self, pane: FocussedPane, window: &mut MainWindow) {
+        if self.focussed == pane || self.focussed == Jump {
+            return;
+        }
+        if let Some(p) = window.mark.as_mut() {
+            p.set_focus(pane == Mark);
+        }
+        self.focussed = pane;
+    }
+
     pub fn cycle_focus(&mut self, window: &mut MainWindow) {
+        if self.focussed == Jump {
+            return;
+        }
         if let Some(p) = window.mark.as_mut() {
             p.set_focus(false);
         }
         self.focussed = match (
             self.focussed,
+            &window.treemap,
             &window.help,
             &mut window.mark,
             &mut window.glob,
         ) {
-            (Main, Some(_), _, _) => Help,
-            (Help, _, Some(pane), _) | (Main, None, Some(pane), _) => {
+            (Main, Some(_), _, _, _) => Treemap,
+            (Main | Treemap, _, Some(_), _, _) => Help,
+            (Help, _, _, Some(pane), _) | (Main | Treemap, _, None, Some(pane), _) => {
                 pane.set_focus(true);
                 Mark
             }
-            (Help | Mark, _, _, Some(_)) | (Main, None, None, Some(_)) => Glob,
-            (Help, _, None, None) | (Mark | Glob, _, _, _) | (Main, None, None, None) => Main,
+            (Help | Mark, _, _, _, Some(_)) | (Main | Treemap, _, None, None, Some(_)) => Glob,
+            (Help, _, _, None, None)
+            | (Mark | Glob | Jump, _, _, _, _)
+            | (Main | Treemap, _, None, None, None) => Main,
         };
     }
 
     pub fn dispatch_to_mark_pane<B>(
         &mut self,
-        key: KeyEvent,
+        action: Action,
         window: &mut MainWindow,
         tree_view: &mut TreeView<'_>,
         display: DisplayOptions,
@@ -309,7 +789,7 @@ impl AppState {
     ) where
         B: Backend,
     {
-        let res = window.mark.take().and_then(|p| p.process_events(key));
+        let res = window.mark.take().and_then(|p| p.process_events(action));
         window.mark = match res {
             Some((pane, mode)) => match mode {
                 Some(MarkMode::Delete) => {
@@ -514,6 +994,14 @@ impl AppState {
         self.glob_navigation.as_ref().map(|e| e.tree_root)
     }
 
+    /// Return `true` if the selected entry stands for the small files of a directory.
+    pub fn selected_is_small_files(&self) -> bool {
+        self.navigation()
+            .selected
+            .and_then(|selected| self.entries.iter().find(|e| e.index == selected))
+            .is_some_and(|e| e.is_small_files)
+    }
+
     fn mark_entry_by_index(
         &mut self,
         index: TreeIndex,
@@ -521,12 +1009,12 @@ impl AppState {
         window: &mut MainWindow,
         tree_view: &TreeView<'_>,
     ) {
-        let is_dir = self
-            .entries
-            .iter()
-            .find(|e| e.index == index)
-            .unwrap()
-            .is_dir;
+        let entry = self.entries.iter().find(|e| e.index == index).unwrap();
+        if entry.is_small_files {
+            self.message = Some(SMALL_FILES_MESSAGE.into());
+            return;
+        }
+        let is_dir = entry.is_dir;
         let should_toggle = match mode {
             MarkEntryMode::Toggle => true,
             MarkEntryMode::MarkForDeletion => false,
@@ -631,14 +1119,26 @@ impl AppState {
     pub fn update_entry_annotations(&mut self, tree_view: &TreeView<'_>) {
         if self.glob_navigation.is_some() {
             if self.cleanup_candidates.is_some() {
-                self.cleanup_candidates = Some(BTreeSet::default());
+                self.cleanup_candidates = Some(if self.cleanup_sweep {
+                    super::cleanup::cleanup_candidates(
+                        &self.cleanup_rules,
+                        tree_view,
+                        &self.entries,
+                    )
+                } else {
+                    BTreeSet::default()
+                });
             }
             if self.gitignored_entries.is_some() {
                 self.gitignored_entries = Some(BTreeSet::default());
             }
         } else {
             if self.cleanup_candidates.is_some() {
-                self.cleanup_candidates = Some(super::cleanup::cleanup_candidates(&self.entries));
+                self.cleanup_candidates = Some(super::cleanup::cleanup_candidates(
+                    &self.cleanup_rules,
+                    tree_view,
+                    &self.entries,
+                ));
             }
             if self.gitignored_entries.is_some() {
                 self.gitignored_entries = Some(super::gitignore::gitignored_entries(
@@ -674,6 +1174,11 @@ fn annotation_message(cleanup_count: usize, gitignored_count: usize) -> Option<S
     }
 }
 
+/// Return `path` relative to the working directory as absolute path, or as is if that fails.
+fn absolute(path: PathBuf) -> PathBuf {
+    std::path::absolute(&path).unwrap_or(path)
+}
+
 fn io_err_to_usize(err: io::Error) -> usize {
     usize::from(err.kind() != io::ErrorKind::NotFound)
 }
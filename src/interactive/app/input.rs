@@ -1,8 +1,13 @@
 use crossbeam::channel::Receiver;
 pub use crossterm::event::{Event, KeyCode};
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
+use crossterm::event::{KeyEvent, KeyModifiers};
+use dua::keys::{Key, KeyChord};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
 };
 
 /// The latest known focus state of the terminal running the interactive UI.
@@ -37,6 +42,26 @@ impl TerminalFocus {
     }
 }
 
+/// Whether the input thread stops reading events, shared with the application state.
+///
+/// While another program runs in the terminal, it has to receive all input, so reading is
+/// paused until the program exits.
+#[derive(Clone, Default)]
+pub struct InputPause(Arc<AtomicBool>);
+
+impl InputPause {
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.0.store(paused, Ordering::Relaxed);
+    }
+}
+
+/// How often the input thread checks if it should read events again.
+const PAUSE_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Returns whether the input thread should continue reading events.
 ///
 /// Return `false` if the channel was disconnected, so forwarding should stop.
@@ -55,12 +80,25 @@ fn forward_event(
     }
 }
 
-pub fn input_channel(focus: TerminalFocus) -> Receiver<Event> {
+pub fn input_channel(focus: TerminalFocus, pause: InputPause) -> Receiver<Event> {
     // Keep reading while the event loop performs synchronous deletion or trash work so the
     // shared focus state stays current, without allowing user input to grow without bound.
     let (key_send, key_receive) = crossbeam::channel::bounded(32);
     std::thread::spawn(move || -> Result<(), std::io::Error> {
         loop {
+            if pause.is_paused() {
+                std::thread::sleep(PAUSE_CHECK_INTERVAL);
+                continue;
+            }
+            // Only wait for input for a while, so a pause is noticed before reading input meant
+            // for another program.
+            let has_event = match crossterm::event::poll(PAUSE_CHECK_INTERVAL) {
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => false,
+                result => result?,
+            };
+            if !has_event || pause.is_paused() {
+                continue;
+            }
             let event = loop {
                 match crossterm::event::read() {
                     Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
@@ -90,6 +128,34 @@ pub fn input_channel_from_chars(input: &str) -> Receiver<Event> {
     key_receive
 }
 
+/// Return the chord to look up key bindings with, or `None` if `key` can't be bound.
+pub fn key_chord(key: KeyEvent) -> Option<KeyChord> {
+    let key_code = match key.code {
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(n) => Key::F(n),
+        _ => return None,
+    };
+    Some(KeyChord {
+        key: key_code,
+        ctrl: key.modifiers.contains(KeyModifiers::CONTROL),
+        alt: key.modifiers.contains(KeyModifiers::ALT),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +186,18 @@ mod tests {
         assert!(matches!(receiver.try_recv(), Ok(Event::Key(_))));
         assert!(receiver.try_recv().is_err());
     }
+
+    #[test]
+    fn key_events_ignore_shift_in_their_chords() {
+        let chord = |code, modifiers| key_chord(KeyEvent::new(code, modifiers));
+        assert_eq!(
+            chord(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Some("G".parse().expect("valid"))
+        );
+        assert_eq!(
+            chord(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            Some("ctrl-d".parse().expect("valid"))
+        );
+        assert_eq!(chord(KeyCode::CapsLock, KeyModifiers::NONE), None);
+    }
 }
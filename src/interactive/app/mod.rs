@@ -1,12 +1,15 @@
 mod bytevis;
 mod cleanup;
+mod clipboard;
+pub mod command;
 mod common;
 mod eventloop;
-mod gitignore;
+pub mod gitignore;
 mod handlers;
 pub mod input;
 mod navigation;
 mod notification;
+pub mod split;
 pub mod state;
 pub mod terminal;
 pub mod tree_view;
@@ -16,4 +19,4 @@ pub use common::*;
 pub use handlers::*;
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
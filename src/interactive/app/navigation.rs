@@ -4,7 +4,7 @@ use std::collections::BTreeMap;
 
 use super::{CursorDirection, EntryDataBundle};
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Navigation {
     pub tree_root: TreeIndex,
     pub view_root: TreeIndex,
@@ -38,12 +38,32 @@ impl Navigation {
         self.selected = Some(new_selected);
     }
 
+    /// Enter each directory of `path` below the tree root in turn, as if they were entered one
+    /// by one, and select `selected` among `entries` of the last one, or whatever was selected
+    /// there before.
+    pub fn enter_path(
+        &mut self,
+        path: &[TreeIndex],
+        selected: Option<TreeIndex>,
+        entries: &[EntryDataBundle],
+    ) {
+        let mut view_root = self.tree_root;
+        for &directory in path {
+            self.bookmarks.insert(view_root, directory);
+            view_root = directory;
+        }
+        self.view_root = view_root;
+        let selected = selected.or_else(|| self.previously_selected_index(view_root, entries));
+        self.select(selected);
+    }
+
     pub fn exit_node(&mut self, parent_idx: TreeIndex, entries: &[EntryDataBundle]) {
         self.view_root = parent_idx;
         self.selected = self
             .bookmarks
             .get(&parent_idx)
             .copied()
+            .filter(|selected| entries.iter().any(|b| b.index == *selected))
             .or_else(|| entries.first().map(|b| b.index));
     }
 
@@ -0,0 +1,194 @@
+use super::{EntryDataBundle, navigation::Navigation, tree_view::TreeView};
+use dua::traverse::{Tree, TreeIndex};
+use petgraph::Direction;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+/// The side of a split view that isn't navigated, showing a second directory next to the
+/// navigated one to compare them.
+pub struct SplitView {
+    /// The navigation of this side, which is swapped with the main one when switching sides.
+    pub navigation: Navigation,
+    /// The entries of `navigation.view_root`.
+    pub entries: Vec<EntryDataBundle>,
+    /// If `true`, this side is shown to the left of the navigated one.
+    pub is_left: bool,
+    /// The directories on the navigated side and on this side that relative paths start at,
+    /// if this side follows the navigated one.
+    pub sync: Option<(TreeIndex, TreeIndex)>,
+}
+
+impl SplitView {
+    /// Show a second side to the right, starting out like `navigation`.
+    pub fn new(navigation: Navigation) -> Self {
+        SplitView {
+            navigation,
+            entries: Vec::new(),
+            is_left: false,
+            sync: None,
+        }
+    }
+
+    /// Make this side the navigated one, and the navigated one this side, without changing
+    /// where they are shown.
+    pub fn switch_sides(
+        &mut self,
+        navigation: &mut Navigation,
+        entries: &mut Vec<EntryDataBundle>,
+    ) {
+        std::mem::swap(&mut self.navigation, navigation);
+        std::mem::swap(&mut self.entries, entries);
+        self.is_left = !self.is_left;
+        if let Some((navigated, other)) = self.sync.as_mut() {
+            std::mem::swap(navigated, other);
+        }
+    }
+
+    /// If synced, enter the directory at the same path relative to this side's sync root as
+    /// `view_root` is to the navigated side's, as far as it exists, and select the entry named
+    /// like `selected`.
+    pub fn follow(
+        &mut self,
+        tree_view: &TreeView<'_>,
+        view_root: TreeIndex,
+        selected: Option<TreeIndex>,
+    ) {
+        let Some((navigated_root, root)) = self.sync else {
+            return;
+        };
+        let tree = tree_view.tree();
+        let mut names = Vec::new();
+        let mut directory = view_root;
+        while directory != navigated_root {
+            let Some(parent) = tree_view.fs_parent_of(directory) else {
+                // Outside of the synced directory, there is nothing to follow.
+                return;
+            };
+            names.push(tree[directory].name.as_path());
+            directory = parent;
+        }
+
+        let mut target = root;
+        for name in names.into_iter().rev() {
+            match child_named(tree, target, name) {
+                Some(child) => target = child,
+                None => break,
+            }
+        }
+        if self.navigation.view_root != target {
+            self.navigation.view_root = target;
+            self.navigation.selected = self.navigation.bookmarks.get(&target).copied();
+        }
+        if let Some(selected) =
+            selected.and_then(|selected| child_named(tree, target, &tree[selected].name))
+        {
+            self.navigation.select(Some(selected));
+        }
+    }
+}
+
+fn child_named(tree: &Tree, parent: TreeIndex, name: &Path) -> Option<TreeIndex> {
+    tree.neighbors_directed(parent, Direction::Outgoing)
+        .find(|child| tree[*child].name == name)
+}
+
+/// Return the entries of `lhs` and `rhs` whose size differs from the entry at the same path on
+/// the other side by more than `threshold_percent` of the larger one, along with those only
+/// present on one side.
+pub fn differing_entries(
+    lhs: &[EntryDataBundle],
+    rhs: &[EntryDataBundle],
+    threshold_percent: u32,
+) -> BTreeSet<TreeIndex> {
+    let (lhs, rhs) = (sizes_by_path(lhs), sizes_by_path(rhs));
+    let differs = |size: u128, other: Option<&(TreeIndex, u128)>| {
+        other.is_none_or(|&(_, other)| {
+            size.abs_diff(other) * 100 > u128::from(threshold_percent) * size.max(other)
+        })
+    };
+    let mut out = BTreeSet::new();
+    for (this, other) in [(&lhs, &rhs), (&rhs, &lhs)] {
+        out.extend(
+            this.iter()
+                .filter(|(path, (_, size))| differs(*size, other.get(*path)))
+                .map(|(_, (index, _))| *index),
+        );
+    }
+    out
+}
+
+/// Return the index and size of `entries` by their path relative to the directory they are in,
+/// which accounts for the entries of expanded directories.
+fn sizes_by_path(entries: &[EntryDataBundle]) -> HashMap<PathBuf, (TreeIndex, u128)> {
+    let mut parents: Vec<&Path> = Vec::new();
+    let mut out = HashMap::new();
+    for entry in entries {
+        parents.truncate(entry.depth);
+        let path: PathBuf = parents
+            .iter()
+            .copied()
+            .chain([entry.name.as_path()])
+            .collect();
+        out.insert(path, (entry.index, entry.size));
+        parents.push(&entry.name);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(entries: &[(usize, &str, u128, usize)]) -> Vec<EntryDataBundle> {
+        entries
+            .iter()
+            .map(|&(index, name, size, depth)| EntryDataBundle {
+                index: TreeIndex::new(index),
+                name: name.into(),
+                size,
+                alternate_size: size,
+                mtime: std::time::SystemTime::UNIX_EPOCH,
+                entry_count: None,
+                is_dir: false,
+                exists: true,
+                is_small_files: false,
+                ownership: None,
+                depth,
+                is_expanded: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_differ_by_relative_size_or_if_missing_on_one_side() {
+        let lhs = entries(&[(0, "same", 100, 0), (1, "grown", 100, 0), (2, "gone", 5, 0)]);
+        let rhs = entries(&[
+            (10, "same", 105, 0),
+            (11, "grown", 200, 0),
+            (12, "new", 1, 0),
+        ]);
+        assert_eq!(
+            differing_entries(&lhs, &rhs, 10),
+            [1, 2, 11, 12].into_iter().map(TreeIndex::new).collect(),
+            "a difference of 5% is within the threshold"
+        );
+        assert_eq!(
+            differing_entries(&lhs, &rhs, 4).len(),
+            6,
+            "everything differs with a lower threshold"
+        );
+    }
+
+    #[test]
+    fn expanded_entries_are_compared_by_their_path() {
+        let lhs = entries(&[(0, "a", 10, 0), (1, "x", 10, 1), (2, "b", 10, 0)]);
+        let rhs = entries(&[(10, "a", 10, 0), (11, "b", 10, 0), (12, "x", 10, 1)]);
+        assert_eq!(
+            differing_entries(&lhs, &rhs, 10),
+            [1, 12].into_iter().map(TreeIndex::new).collect(),
+            "a/x and b/x are different entries"
+        );
+    }
+}
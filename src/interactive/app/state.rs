@@ -1,12 +1,20 @@
 use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
+use std::time::Instant;
 
 use dua::WalkOptions;
+use dua::bookmarks::Bookmarks;
 use dua::traverse::{BackgroundTraversal, TraversalStats};
 
 use crate::interactive::widgets::Column;
 
-use super::{EntryDataBundle, SortMode, input::TerminalFocus, navigation::Navigation};
+use super::{
+    EntryDataBundle, SortMode,
+    command::TerminalModes,
+    input::{InputPause, TerminalFocus},
+    navigation::Navigation,
+    split::SplitView,
+};
 
 #[derive(Default, Copy, Clone, PartialEq)]
 pub enum FocussedPane {
@@ -15,6 +23,8 @@ pub enum FocussedPane {
     Help,
     Mark,
     Glob,
+    Jump,
+    Treemap,
 }
 
 #[derive(Default)]
@@ -30,31 +40,60 @@ pub struct FilesystemScan {
     pub previous_selection: Option<(PathBuf, usize)>,
 }
 
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag is independent view or session state"
+)]
 pub struct AppState {
     /// Navigation state for the main traversal view.
     pub navigation: Navigation,
     /// Navigation state for an active glob-filtered view, if one is open.
     pub glob_navigation: Option<Navigation>,
+    /// The second directory shown next to the navigated one, if the view is split.
+    pub split: Option<SplitView>,
     /// Entries currently displayed in the active view.
     pub entries: Vec<EntryDataBundle>,
-    /// Displayed entries that match known cleanup-directory names, or `None` if disabled.
+    /// Displayed entries that match one of `cleanup_rules`, or `None` if disabled.
     pub cleanup_candidates: Option<BTreeSet<dua::traverse::TreeIndex>>,
+    /// The rules for directories that are cleanup candidates.
+    pub cleanup_rules: Vec<dua::CleanupRule>,
+    /// Whether the search results are the cleanup candidates below the directory in view.
+    pub cleanup_sweep: bool,
+    /// The ordering to return to when the cleanup candidates, which are sorted by size, are left.
+    pub sorting_before_sweep: Option<SortMode>,
     /// Displayed entries ignored by the current git repository, or `None` if disabled.
     pub gitignored_entries: Option<BTreeSet<dua::traverse::TreeIndex>>,
     /// Active ordering for `entries`.
     pub sorting: SortMode,
     /// Optional columns explicitly enabled by the user.
     pub show_columns: HashSet<Column>,
+    /// Whether entries whose name starts with a dot are listed.
+    pub show_hidden: bool,
+    /// Whether entries are displayed as a tree in which directories can be expanded.
+    pub tree_mode: bool,
+    /// The directories whose entries are listed below them while in tree mode.
+    pub expanded: BTreeSet<dua::traverse::TreeIndex>,
+    /// The named bookmarks of the roots.
+    pub bookmarks: Bookmarks,
+    /// When and which entry was clicked last, to detect double-clicks.
+    pub last_click: Option<(Instant, dua::traverse::TreeIndex)>,
     /// Status message shown in the footer.
     pub message: Option<String>,
     /// Pane that currently receives keyboard input.
     pub focussed: FocussedPane,
     /// Focus state shared with the terminal input reader.
     pub terminal_focus: TerminalFocus,
+    /// Whether the terminal input reader is paused, shared with it.
+    pub input_pause: InputPause,
+    /// The modes the terminal was put into, to leave them while other programs run in it, or
+    /// `None` if it wasn't set up for interactive use.
+    pub terminal_modes: Option<TerminalModes>,
     /// Whether user input or terminal events have arrived since the current scan started.
     pub received_events: bool,
     /// Active background filesystem traversal, if a scan or refresh is running.
     pub scan: Option<FilesystemScan>,
+    /// Directories to refresh one after another once the active scan is done.
+    pub pending_refreshes: Vec<dua::traverse::TreeIndex>,
     /// Latest traversal progress and error counters.
     pub stats: TraversalStats,
     /// Options used when starting filesystem walks.
@@ -65,6 +104,8 @@ pub struct AppState {
     pub allow_entry_check: bool,
     /// Whether the next quit/back action should exit the app.
     pub pending_exit: bool,
+    /// The directory in view when quitting to change into it, if the app was quit that way.
+    pub quit_directory: Option<PathBuf>,
 }
 
 impl AppState {
@@ -72,21 +113,34 @@ impl AppState {
         AppState {
             navigation: Navigation::default(),
             glob_navigation: None,
+            split: None,
             entries: vec![],
             cleanup_candidates: Some(BTreeSet::new()),
+            cleanup_rules: dua::CleanupRule::defaults(),
+            cleanup_sweep: false,
+            sorting_before_sweep: None,
             gitignored_entries: Some(BTreeSet::new()),
             sorting: SortMode::default(),
             show_columns: HashSet::default(),
+            show_hidden: true,
+            tree_mode: false,
+            expanded: BTreeSet::new(),
+            bookmarks: Bookmarks::default(),
+            last_click: None,
             message: None,
             focussed: FocussedPane::default(),
             terminal_focus: TerminalFocus::default(),
+            input_pause: InputPause::default(),
+            terminal_modes: None,
             received_events: false,
             scan: None,
+            pending_refreshes: Vec::new(),
             stats: TraversalStats::default(),
             walk_options,
             root_paths: input,
             allow_entry_check: true,
             pending_exit: false,
+            quit_directory: None,
         }
     }
 }
@@ -45,7 +45,8 @@ impl TerminalApp {
             .map_err(|err| anyhow::Error::msg(err.to_string()))?;
 
         let display = DisplayOptions::new(byte_format);
-        let window = MainWindow::default();
+        let mut window = MainWindow::default();
+        window.theme = config.theme.theme(dua::theme::no_color());
 
         let mut state = AppState::new(walk_options, input);
         if config.gitignore == Some(false) {
@@ -54,6 +55,12 @@ impl TerminalApp {
         if config.cleanup_heuristics == Some(false) {
             state.cleanup_candidates = None;
         }
+        if let Some(rules) = &config.cleanup_rules {
+            state.cleanup_rules.clone_from(rules);
+        }
+        if config.show_hidden == Some(false) {
+            state.show_hidden = false;
+        }
         state.allow_entry_check = entry_check;
         let traversal = Traversal::new();
         #[cfg(test)]
@@ -62,9 +69,11 @@ impl TerminalApp {
         state.navigation_mut().view_root = traversal.root_index;
         state.entries = sorted_entries(
             &traversal.tree,
+            &traversal.ownerships,
             state.navigation().view_root,
             state.sorting,
             state.glob_root(),
+            state.show_hidden,
             EntryCheck::new(state.scan.is_some(), state.allow_entry_check),
         );
         state.navigation_mut().selected = state.entries.first().map(|b| b.index);
@@ -2,9 +2,10 @@ use anyhow::Result;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use pretty_assertions::assert_eq;
 use std::ffi::OsString;
+use std::path::Path;
 
-use crate::interactive::app::tests::utils::{into_codes, into_events};
-use crate::interactive::widgets::Column;
+use crate::interactive::app::tests::utils::{fixture, into_codes, into_events};
+use crate::interactive::widgets::{Column, PromptPurpose};
 use crate::interactive::{
     MTimeSort, SortMode,
     app::tests::{
@@ -566,3 +567,754 @@ fn quit_requires_two_presses_when_items_marked() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn remapped_keys_trigger_their_actions_in_place_of_the_defaults() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.config = toml::from_str(
+        r#"
+        [keys.main]
+        "ctrl-n" = "down"
+        "j" = "sort-by-name"
+        "#,
+    )?;
+    app.process_events(&mut terminal, into_codes("o"))?;
+    let first_selected = app.state.navigation().selected;
+
+    app.process_events(
+        &mut terminal,
+        into_events([Event::Key(KeyEvent::new(
+            KeyCode::Char('n'),
+            KeyModifiers::CONTROL,
+        ))]),
+    )?;
+    assert_ne!(
+        app.state.navigation().selected,
+        first_selected,
+        "the added binding moves the selection down"
+    );
+
+    app.process_events(&mut terminal, into_codes("j"))?;
+    assert_eq!(
+        app.state.sorting,
+        SortMode::NameAscending,
+        "the overridden binding now sorts by name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn hidden_entries_can_be_hidden_while_their_size_is_still_counted() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("o"))?;
+    let hidden_name = OsString::from(".hidden.666");
+    let is_listed = |app: &crate::TerminalApp| {
+        app.state
+            .entries
+            .iter()
+            .any(|e| e.name.as_os_str() == hidden_name)
+    };
+    assert!(is_listed(&app), "hidden entries are shown by default");
+    let view_root = app.state.navigation().view_root;
+    let size_before = node_by_index(&app, view_root).size;
+    let listed_size = |app: &crate::TerminalApp| app.state.entries.iter().map(|e| e.size).sum();
+    let listed_size_before: u128 = listed_size(&app);
+
+    app.process_events(&mut terminal, into_codes("."))?;
+    assert!(!app.state.show_hidden);
+    assert!(!is_listed(&app), "hidden entries are left out once toggled");
+    assert_eq!(
+        listed_size(&app),
+        listed_size_before - 666,
+        "only the hidden entry disappears from the listing"
+    );
+    assert_eq!(
+        node_by_index(&app, view_root).size,
+        size_before,
+        "the hidden entry is still counted in the size of its parent"
+    );
+
+    app.process_events(&mut terminal, into_codes("."))?;
+    assert!(is_listed(&app), "toggling again shows hidden entries");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn ownership_columns_can_be_shown_and_entries_sorted_by_owner() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("o"))?;
+    assert!(
+        app.state.entries.iter().all(|e| e.ownership.is_some()),
+        "the owner of each entry is known on Unix"
+    );
+
+    app.process_events(&mut terminal, into_codes("w"))?;
+    assert_eq!(app.state.sorting, SortMode::OwnerAscending);
+    let sizes: Vec<_> = app.state.entries.iter().map(|e| e.size).collect();
+    assert!(
+        sizes.is_sorted_by(|a, b| a >= b),
+        "entries of the same owner are sorted by size, largest first"
+    );
+    app.process_events(&mut terminal, into_codes("w"))?;
+    assert_eq!(app.state.sorting, SortMode::OwnerDescending);
+
+    app.process_events(&mut terminal, into_codes("WYP"))?;
+    for column in [Column::Owner, Column::Group, Column::Permissions] {
+        assert!(app.state.show_columns.contains(&column));
+    }
+    app.process_events(&mut terminal, into_codes("WYP"))?;
+    assert!(app.state.show_columns.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn owner_usage_pane_sums_up_the_current_directory() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("U"))?;
+    let usage = |app: &crate::TerminalApp| -> (u128, u64) {
+        let pane = app.window.owners.as_ref().expect("owners pane is shown");
+        pane.usage().iter().fold((0, 0), |(bytes, entries), owner| {
+            (bytes + owner.bytes, entries + owner.entries)
+        })
+    };
+    let root = app.traversal.tree[app.traversal.root_index].clone();
+    assert_eq!(
+        usage(&app),
+        (root.size, root.entry_count.get().unwrap_or_default()),
+        "all entries of the traversal are accounted for"
+    );
+
+    app.process_events(&mut terminal, into_codes("o"))?;
+    let view_root = app.traversal.tree[app.state.navigation().view_root].clone();
+    assert_eq!(
+        usage(&app).0,
+        app.state.entries.iter().map(|e| e.size).sum::<u128>(),
+        "entering a directory limits the usage to {:?}",
+        view_root.name
+    );
+
+    app.process_events(&mut terminal, into_codes("U"))?;
+    assert!(app.window.owners.is_none(), "the pane can be closed again");
+    Ok(())
+}
+
+#[test]
+fn preview_pane_follows_the_selection_and_shows_file_contents() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("op"))?;
+    let pane = app.window.preview.as_ref().expect("preview pane is shown");
+    assert_eq!(pane.path(), Some(fixture("sample-01/dir").as_path()));
+    assert_eq!(
+        pane.largest_files(),
+        [
+            Path::new("dir-a.1mb"),
+            Path::new("sub/dir-sub-a.256kb"),
+            Path::new("dir-a.kb"),
+            Path::new("1000bytes"),
+            Path::new("empty-dir/.gitkeep"),
+        ],
+        "the files below the directory are listed largest first"
+    );
+
+    while app.window.preview.as_ref().and_then(|pane| pane.path())
+        != Some(fixture("sample-01/a").as_path())
+    {
+        app.process_events(&mut terminal, into_codes("j"))?;
+    }
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect();
+    assert!(screen.contains("Preview"));
+    assert!(
+        screen.contains("aaaaaaaa"),
+        "the first lines of text files are shown"
+    );
+
+    app.process_events(&mut terminal, into_codes("p"))?;
+    assert!(app.window.preview.is_none(), "the pane can be closed again");
+    Ok(())
+}
+
+#[test]
+fn paths_are_not_reported_as_copied_without_an_interactive_terminal() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    let unavailable = Some("The clipboard is unavailable without an interactive terminal");
+    app.process_events(&mut terminal, into_codes("oy"))?;
+    assert_eq!(app.state.message.as_deref(), unavailable);
+
+    app.process_events(&mut terminal, into_codes("dd"))?;
+    app.process_events(
+        &mut terminal,
+        into_events([
+            Event::Key(KeyCode::Tab.into()),
+            Event::Key(KeyCode::Char('y').into()),
+        ]),
+    )?;
+    assert_eq!(
+        app.state.message.as_deref(),
+        unavailable,
+        "marked entries aren't copied either"
+    );
+    Ok(())
+}
+
+#[test]
+fn quitting_to_a_directory_remembers_the_one_in_view() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("oQ"))?;
+    assert_eq!(
+        app.state.quit_directory,
+        Some(std::path::absolute(fixture("sample-01"))?)
+    );
+    Ok(())
+}
+
+#[test]
+fn sizes_can_be_switched_between_apparent_size_and_disk_usage() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    assert!(app.state.walk_options.apparent_size);
+    let sizes = |app: &crate::TerminalApp| -> Vec<_> {
+        let mut sizes: Vec<_> = app
+            .state
+            .entries
+            .iter()
+            .map(|e| (e.index, e.size, e.alternate_size))
+            .collect();
+        sizes.sort();
+        sizes
+    };
+    let before = sizes(&app);
+
+    app.process_events(&mut terminal, into_codes("A"))?;
+    assert!(!app.state.walk_options.apparent_size);
+    assert_eq!(
+        sizes(&app),
+        before
+            .iter()
+            .map(|&(index, size, alternate)| (index, alternate, size))
+            .collect::<Vec<_>>(),
+        "disk usage is shown without traversing again"
+    );
+    assert_eq!(app.state.message.as_deref(), Some("Showing disk usage"));
+
+    app.process_events(&mut terminal, into_codes("A"))?;
+    assert_eq!(
+        sizes(&app),
+        before,
+        "switching back restores the apparent size"
+    );
+
+    app.process_events(&mut terminal, into_codes("Z"))?;
+    assert!(app.state.show_columns.contains(&Column::AlternateSize));
+    Ok(())
+}
+
+#[test]
+fn directories_can_be_expanded_and_collapsed_in_tree_mode() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("o"))?;
+    let listing = |app: &crate::TerminalApp| -> Vec<(String, usize)> {
+        app.state
+            .entries
+            .iter()
+            .map(|e| (e.name.to_string_lossy().into_owned(), e.depth))
+            .collect()
+    };
+    let selected_name = |app: &crate::TerminalApp| {
+        node_by_index(app, app.state.navigation().selected.unwrap())
+            .name
+            .to_string_lossy()
+            .into_owned()
+    };
+    let top_level = listing(&app);
+    assert_eq!(selected_name(&app), "dir", "the largest entry is selected");
+
+    app.process_events(&mut terminal, into_codes("e"))?;
+    assert!(app.state.tree_mode, "expanding turns on tree mode");
+    let expanded = listing(&app);
+    assert_eq!(
+        expanded[..3],
+        [
+            ("dir".to_string(), 0),
+            ("dir-a.1mb".to_string(), 1),
+            ("sub".to_string(), 1)
+        ],
+        "the entries of a directory are listed right below it"
+    );
+    assert_eq!(expanded.len(), top_level.len() + 5);
+
+    app.process_events(&mut terminal, into_codes("jje"))?;
+    assert_eq!(selected_name(&app), "sub");
+    assert_eq!(listing(&app)[3], ("dir-sub-a.256kb".to_string(), 2));
+
+    app.process_events(&mut terminal, into_codes("je"))?;
+    assert_eq!(
+        selected_name(&app),
+        "sub",
+        "collapsing from within a directory selects it"
+    );
+    assert_eq!(listing(&app), expanded);
+
+    app.process_events(&mut terminal, into_codes("T"))?;
+    assert!(!app.state.tree_mode);
+    assert_eq!(listing(&app), top_level);
+    assert_eq!(
+        selected_name(&app),
+        "dir",
+        "the selection moves to the directory that is still listed"
+    );
+
+    app.process_events(&mut terminal, into_codes("T"))?;
+    assert_eq!(
+        listing(&app),
+        expanded,
+        "expanded directories are remembered"
+    );
+    Ok(())
+}
+
+#[test]
+fn treemap_pane_follows_the_selection_and_navigation() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    let text_in = |terminal: &tui::Terminal<tui::backend::TestBackend>, area: tui::layout::Rect| {
+        let buffer = terminal.backend().buffer();
+        area.positions()
+            .map(|position| buffer[position].symbol().to_owned())
+            .collect::<String>()
+    };
+    app.process_events(&mut terminal, into_codes("v"))?;
+    assert!(app.window.treemap.is_some());
+
+    app.process_events(&mut terminal, into_codes("o"))?;
+    let view_root = app.state.navigation().view_root;
+    assert_ne!(
+        view_root, app.traversal.root_index,
+        "entering navigates into the selected rectangle"
+    );
+    let selected = app
+        .state
+        .navigation()
+        .selected
+        .expect("an entry is selected");
+    let name = app.traversal.tree[selected]
+        .name
+        .to_string_lossy()
+        .into_owned();
+    let rect = app
+        .window
+        .treemap
+        .as_ref()
+        .and_then(|pane| pane.rect_of(selected))
+        .expect("the selected entry has a rectangle");
+    assert!(
+        text_in(&terminal, rect).contains(&name),
+        "the rectangle of the selected entry {name:?} is labelled with its name"
+    );
+
+    app.process_events(&mut terminal, into_codes("v"))?;
+    assert!(app.window.treemap.is_none(), "the pane can be closed again");
+    Ok(())
+}
+
+#[test]
+fn treemap_pane_selects_rectangles_with_the_cursor_keys() -> Result<()> {
+    use crate::interactive::state::FocussedPane;
+
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.process_events(&mut terminal, into_codes("voo"))?;
+    app.process_events(&mut terminal, into_keys(Some(KeyCode::Tab)))?;
+    assert!(
+        app.state.focussed == FocussedPane::Treemap,
+        "the treemap is focussed first"
+    );
+
+    let first = app
+        .state
+        .navigation()
+        .selected
+        .expect("an entry is selected");
+    let rect_of = |app: &crate::TerminalApp, index| {
+        app.window
+            .treemap
+            .as_ref()
+            .and_then(|pane| pane.rect_of(index))
+            .expect("selected entries have a rectangle")
+    };
+    let first_rect = rect_of(&app, first);
+
+    app.process_events(&mut terminal, into_keys([KeyCode::Right, KeyCode::Down]))?;
+    let moved = app
+        .state
+        .navigation()
+        .selected
+        .expect("an entry is selected");
+    assert_ne!(moved, first, "the cursor keys move the selection");
+    let moved_rect = rect_of(&app, moved);
+    assert!(
+        moved_rect.left() >= first_rect.right() || moved_rect.top() >= first_rect.bottom(),
+        "{moved_rect:?} lies to the right of or below {first_rect:?}"
+    );
+
+    app.process_events(&mut terminal, into_keys([KeyCode::Up, KeyCode::Left]))?;
+    assert_eq!(
+        app.state.navigation().selected,
+        Some(first),
+        "moving back returns to the first rectangle"
+    );
+
+    app.process_events(
+        &mut terminal,
+        into_keys([KeyCode::Right, KeyCode::Down, KeyCode::Enter]),
+    )?;
+    assert_eq!(
+        app.state.navigation().view_root,
+        moved,
+        "enter navigates into the selected rectangle"
+    );
+
+    app.process_events(&mut terminal, into_codes("v"))?;
+    assert!(
+        app.state.focussed == FocussedPane::Main,
+        "closing the treemap moves the focus back to the entries"
+    );
+    Ok(())
+}
+
+#[test]
+fn treemap_and_prompt_keys_can_be_remapped() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    app.config = toml::from_str(
+        r#"
+        [keys.treemap]
+        "h" = "treemap-left"
+        "j" = "treemap-down"
+        "k" = "treemap-up"
+        "l" = "treemap-right"
+
+        [keys.prompt]
+        "enter" = "none"
+        "ctrl-o" = "submit"
+        "#,
+    )?;
+    app.process_events(&mut terminal, into_codes("voo"))?;
+    app.process_events(&mut terminal, into_keys(Some(KeyCode::Tab)))?;
+    let first = app.state.navigation().selected;
+
+    app.process_events(&mut terminal, into_codes("lj"))?;
+    assert_ne!(
+        app.state.navigation().selected,
+        first,
+        "the treemap bindings move the selection"
+    );
+    app.process_events(&mut terminal, into_codes("kh"))?;
+    assert_eq!(app.state.navigation().selected, first);
+
+    app.process_events(&mut terminal, into_codes(":quux"))?;
+    app.process_events(&mut terminal, into_keys(Some(KeyCode::Enter)))?;
+    assert!(
+        app.window.jump.is_some(),
+        "the unbound key doesn't submit the prompt"
+    );
+    app.process_events(
+        &mut terminal,
+        into_events([Event::Key(KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::CONTROL,
+        ))]),
+    )?;
+    assert!(app.window.jump.is_none(), "the bound chord submits it");
+    assert!(
+        app.state
+            .message
+            .as_deref()
+            .is_some_and(|message| message.contains("'quux'")),
+        "the input was used, got {:?}",
+        app.state.message
+    );
+    Ok(())
+}
+
+#[test]
+fn jump_prompt_navigates_to_paths_as_if_entered_level_by_level() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    let type_into_prompt = |input: &str| {
+        into_keys(input.chars().map(|c| match c {
+            '\t' => KeyCode::Tab,
+            '\n' => KeyCode::Enter,
+            c => KeyCode::Char(c),
+        }))
+    };
+    let name_of = |app: &crate::TerminalApp, index: Option<_>| {
+        index.map(|index| {
+            node_by_index(app, index)
+                .name
+                .to_string_lossy()
+                .into_owned()
+        })
+    };
+    let view_root = |app: &crate::TerminalApp| name_of(app, Some(app.state.navigation().view_root));
+    let selected = |app: &crate::TerminalApp| name_of(app, app.state.navigation().selected);
+
+    app.process_events(&mut terminal, type_into_prompt(":t\tdi\tsu\t"))?;
+    assert_eq!(
+        app.window.jump.as_ref().map(|pane| pane.input.as_str()),
+        Some("tests/fixtures/sample-01/dir/sub/"),
+        "names are completed from the entries of the tree"
+    );
+    app.process_events(&mut terminal, type_into_prompt("\n"))?;
+    assert!(app.window.jump.is_none());
+    assert_eq!(view_root(&app).as_deref(), Some("sub"));
+
+    app.process_events(&mut terminal, into_codes("u"))?;
+    assert_eq!(view_root(&app).as_deref(), Some("dir"));
+    assert_eq!(
+        selected(&app).as_deref(),
+        Some("sub"),
+        "directories on the way remember where they were left"
+    );
+    app.process_events(&mut terminal, into_codes("u"))?;
+    assert_eq!(selected(&app).as_deref(), Some("dir"));
+
+    app.process_events(
+        &mut terminal,
+        type_into_prompt(":./tests/fixtures/sample-01/dir/dir-a.1mb\n"),
+    )?;
+    assert_eq!(view_root(&app).as_deref(), Some("dir"));
+    assert_eq!(
+        selected(&app).as_deref(),
+        Some("dir-a.1mb"),
+        "files are selected in their directory"
+    );
+
+    app.process_events(&mut terminal, type_into_prompt(":quux\n"))?;
+    assert_eq!(view_root(&app).as_deref(), Some("dir"), "nothing changes");
+    assert_eq!(
+        app.state.message.as_deref(),
+        Some("There is no 'quux' in '.'")
+    );
+
+    app.process_events(&mut terminal, type_into_prompt(":sub"))?;
+    app.process_events(&mut terminal, into_keys(Some(KeyCode::Esc)))?;
+    assert!(app.window.jump.is_none(), "the prompt can be cancelled");
+    assert_eq!(view_root(&app).as_deref(), Some("dir"));
+    Ok(())
+}
+
+#[test]
+fn bookmarks_are_named_and_jumped_to() -> Result<()> {
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    let type_into_prompt = |input: &str| {
+        into_keys(input.chars().map(|c| match c {
+            '\t' => KeyCode::Tab,
+            '\n' => KeyCode::Enter,
+            c => KeyCode::Char(c),
+        }))
+    };
+    let view_root = |app: &crate::TerminalApp| {
+        node_by_index(app, app.state.navigation().view_root)
+            .name
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    app.process_events(
+        &mut terminal,
+        type_into_prompt(":tests/fixtures/sample-01/dir/sub\n"),
+    )?;
+    app.process_events(&mut terminal, into_codes("ub"))?;
+    assert!(
+        app.window
+            .jump
+            .as_ref()
+            .is_some_and(|pane| pane.purpose != PromptPurpose::JumpToPath),
+        "a name is asked for"
+    );
+    app.process_events(&mut terminal, type_into_prompt("the sub\n"))?;
+    let path = std::path::Path::new("tests/fixtures/sample-01/dir/sub");
+    assert_eq!(app.state.bookmarks.get("the sub"), Some(path));
+
+    app.process_events(&mut terminal, into_codes("uuB"))?;
+    assert!(app.window.bookmarks.is_some());
+    app.process_events(&mut terminal, type_into_prompt(":@t\t\n"))?;
+    assert_eq!(view_root(&app), "sub", "bookmarks can be jumped to by name");
+
+    app.process_events(&mut terminal, type_into_prompt(":@nothing\n"))?;
+    assert_eq!(
+        app.state.message.as_deref(),
+        Some("There is no bookmark named 'nothing'")
+    );
+
+    app.process_events(&mut terminal, into_codes("ub"))?;
+    assert!(app.window.jump.is_none());
+    assert!(
+        app.state.bookmarks.by_name().is_empty(),
+        "bookmarking a bookmarked entry again removes its bookmark"
+    );
+    Ok(())
+}
+
+#[test]
+fn mouse_selects_enters_sorts_and_scrolls() -> Result<()> {
+    use crate::interactive::state::FocussedPane;
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_fixture(&["sample-01"])?;
+    let mouse = |kind, column, row| {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    };
+    let click = |column, row| mouse(MouseEventKind::Down(MouseButton::Left), column, row);
+    let row_of = |position: usize| -> u16 {
+        // Below the header line, the top border of the entries pane and the column headers.
+        3 + u16::try_from(position).expect("few entries")
+    };
+
+    app.config.mouse = Some(true);
+    app.process_events(&mut terminal, into_codes("o"))?;
+    let header: Vec<_> = terminal.backend().buffer().content()[40 * 2..40 * 3]
+        .iter()
+        .map(|cell| cell.symbol().to_owned())
+        .collect();
+    let name_column = header
+        .windows(2)
+        .position(|cells| cells.concat() == "Na")
+        .expect("the column headers are shown");
+
+    let second = app.state.entries[1].index;
+    app.process_events(&mut terminal, into_events([click(10, row_of(1))]))?;
+    assert_eq!(
+        app.state.navigation().selected,
+        Some(second),
+        "clicking selects"
+    );
+
+    app.process_events(
+        &mut terminal,
+        into_events([mouse(MouseEventKind::ScrollDown, 10, row_of(0))]),
+    )?;
+    assert_eq!(
+        app.state.navigation().selected,
+        Some(app.state.entries[2].index),
+        "the wheel moves the selection"
+    );
+
+    app.process_events(
+        &mut terminal,
+        into_events([click(
+            u16::try_from(name_column).expect("within the terminal"),
+            2,
+        )]),
+    )?;
+    assert_eq!(
+        app.state.sorting,
+        SortMode::NameAscending,
+        "clicking a column header sorts by it"
+    );
+
+    let (position, directory) = app
+        .state
+        .entries
+        .iter()
+        .enumerate()
+        .find(|(_, entry)| entry.is_dir && entry.entry_count.unwrap_or_default() > 0)
+        .map(|(position, entry)| (position, entry.index))
+        .expect("a directory with entries");
+    let row = row_of(position);
+    app.process_events(&mut terminal, into_events([click(10, row), click(10, row)]))?;
+    assert_eq!(
+        app.state.navigation().view_root,
+        directory,
+        "double-clicking enters"
+    );
+
+    app.process_events(&mut terminal, into_codes("?"))?;
+    app.process_events(
+        &mut terminal,
+        into_events([mouse(MouseEventKind::ScrollDown, 30, 10)]),
+    )?;
+    assert_eq!(
+        app.window.help.as_ref().map(|pane| pane.scroll),
+        Some(1),
+        "the wheel scrolls the pane under the mouse"
+    );
+    app.process_events(&mut terminal, into_events([click(10, 10)]))?;
+    assert!(
+        app.state.focussed == FocussedPane::Main,
+        "clicking a pane focusses it"
+    );
+    Ok(())
+}
+
+#[test]
+fn split_view_compares_two_directories_and_follows_by_relative_path() -> Result<()> {
+    let (mut terminal, mut app) =
+        initialized_app_and_terminal_from_fixture(&["sample-01", "sample-02"])?;
+    let jump_to = |path: &str| {
+        into_keys(format!(":{path}\n").chars().map(|c| {
+            if c == '\n' {
+                KeyCode::Enter
+            } else {
+                KeyCode::Char(c)
+            }
+        }))
+    };
+    let path_of = |app: &crate::TerminalApp, index| {
+        crate::interactive::path_of(&app.traversal.tree, index, None)
+    };
+
+    app.process_events(&mut terminal, jump_to("tests/fixtures/sample-01"))?;
+    app.process_events(&mut terminal, into_codes("|"))?;
+    assert!(app.window.split.is_some());
+    let split = app.state.split.as_ref().expect("split view");
+    assert_eq!(
+        split.navigation.view_root,
+        app.state.navigation().view_root,
+        "both sides start out in the same directory"
+    );
+
+    app.process_events(&mut terminal, into_codes("\\"))?;
+    app.process_events(&mut terminal, jump_to("tests/fixtures/sample-02"))?;
+    app.process_events(&mut terminal, into_codes("=\\"))?;
+    let split = app.state.split.as_ref().expect("split view");
+    assert_eq!(
+        path_of(&app, split.navigation.view_root),
+        fixture("sample-02"),
+        "the other side stays where it was navigated to"
+    );
+    assert!(split.sync.is_some());
+
+    app.process_events(&mut terminal, jump_to("tests/fixtures/sample-01/dir/sub"))?;
+    let split = app.state.split.as_ref().expect("split view");
+    assert_eq!(
+        path_of(&app, split.navigation.view_root),
+        fixture("sample-02/dir/sub"),
+        "the other side follows by relative path"
+    );
+    assert_eq!(
+        split
+            .entries
+            .iter()
+            .map(|entry| entry.name.to_string_lossy().into_owned())
+            .collect::<Vec<_>>(),
+        ["e"]
+    );
+    let differing =
+        crate::interactive::split::differing_entries(&app.state.entries, &split.entries, 10);
+    assert_eq!(differing.len(), 2, "no entry has a counterpart");
+
+    app.process_events(&mut terminal, into_codes("|"))?;
+    assert!(app.state.split.is_none() && app.window.split.is_none());
+    Ok(())
+}
@@ -2,7 +2,7 @@ use crate::interactive::app::tests::utils::{
     WritableFixture, initialized_app_and_terminal_from_paths, into_codes, into_events,
     new_test_terminal,
 };
-use crate::interactive::terminal::TerminalApp;
+use crate::interactive::{SortMode, terminal::TerminalApp};
 use anyhow::Result;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use dua::{ByteFormat, Config, WalkOptions};
@@ -121,6 +121,9 @@ $precious.tmp
         apparent_size: true,
         count_hard_links: false,
         cross_filesystems: false,
+        follow_links: false,
+        max_depth: None,
+        collapse_files_below: None,
         ignore_dirs: BTreeSet::default(),
         ignore_patterns: None,
     };
@@ -258,6 +261,9 @@ fn cleanup_candidates_are_marked_with_one_key_after_entering_project_dir() -> Re
         apparent_size: true,
         count_hard_links: false,
         cross_filesystems: false,
+        follow_links: false,
+        max_depth: None,
+        collapse_files_below: None,
         ignore_dirs: BTreeSet::default(),
         ignore_patterns: None,
     };
@@ -350,3 +356,450 @@ fn cleanup_candidates_are_marked_with_one_key_after_entering_project_dir() -> Re
     );
     Ok(())
 }
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn cleanup_candidates_below_the_view_are_swept_into_search_results() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let root = fixture.path();
+    fs::create_dir_all(root.join("project/target/debug"))?;
+    fs::write(root.join("project/target/debug/app"), [0; 10])?;
+    fs::create_dir_all(root.join("web/node_modules/package/node_modules"))?;
+    fs::write(root.join("web/node_modules/package/index.js"), [0; 100])?;
+    fs::create_dir_all(root.join("web/src"))?;
+    fs::write(root.join("web/src/main.js"), [0; 1000])?;
+
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_paths(&[root.to_owned()])?;
+    app.process_events(&mut terminal, into_codes("on"))?;
+    app.process_events(&mut terminal, into_codes("F"))?;
+
+    assert!(
+        app.state.glob_navigation.is_some(),
+        "results are shown like search results"
+    );
+    assert_eq!(
+        app.state
+            .entries
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect::<Vec<_>>(),
+        [root.join("web/node_modules"), root.join("project/target")],
+        "candidates are sorted by size, and nothing inside them is listed"
+    );
+    assert_eq!(
+        app.state.message.as_deref(),
+        Some("2 cleanup candidates (X)")
+    );
+
+    app.process_events(&mut terminal, into_codes("X"))?;
+    assert_eq!(
+        marked_file_names(&app, "swept candidates are marked"),
+        BTreeSet::from(["node_modules".to_string(), "target".to_string()])
+    );
+
+    app.process_events(&mut terminal, into_codes("q"))?;
+    assert!(app.state.glob_navigation.is_none());
+    assert!(
+        !app.state.cleanup_sweep,
+        "leaving the results ends the sweep"
+    );
+    assert_eq!(
+        app.state.sorting,
+        SortMode::NameAscending,
+        "the ordering from before the sweep is restored"
+    );
+    Ok(())
+}
+
+/// Make `dir` the worktree of a git repository whose index contains `tracked`.
+#[cfg(unix)]
+fn init_repository(dir: &std::path::Path, tracked: &[&str]) -> Result<()> {
+    fs::create_dir_all(dir.join(".git/objects"))?;
+    fs::create_dir_all(dir.join(".git/refs/heads"))?;
+    fs::write(dir.join(".git/HEAD"), b"ref: refs/heads/main\n")?;
+    fs::write(
+        dir.join(".git/config"),
+        b"[core]\n\trepositoryformatversion = 0\n\tbare = false\n",
+    )?;
+    let mut state = gix::index::State::new(gix::hash::Kind::Sha1);
+    for path in tracked {
+        state.dangerously_push_entry(
+            gix::index::entry::Stat::default(),
+            gix::hash::Kind::Sha1.null(),
+            gix::index::entry::Flags::empty(),
+            gix::index::entry::Mode::FILE,
+            (*path).into(),
+        );
+    }
+    state.sort_entries();
+    gix::index::File::from_state(state, dir.join(".git/index"))
+        .write(gix::index::write::Options::default())?;
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn repositories_pane_sums_up_bytes_by_how_git_sees_them() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let root = fixture.path();
+    let app_repo = root.join("app");
+    init_repository(&app_repo, &[".gitignore", "src/main.rs", "target/keep"])?;
+    fs::write(app_repo.join(".gitignore"), b"target/\nbuild/\n$.env\n")?;
+    fs::create_dir_all(app_repo.join("src"))?;
+    fs::write(app_repo.join("src/main.rs"), [0; 100])?;
+    fs::write(app_repo.join("notes.txt"), [0; 10])?;
+    fs::write(app_repo.join(".env"), [0; 20])?;
+    fs::create_dir_all(app_repo.join("target"))?;
+    fs::write(app_repo.join("target/keep"), [0; 1])?;
+    fs::write(app_repo.join("target/out"), [0; 1000])?;
+    fs::create_dir_all(app_repo.join("build"))?;
+    fs::write(app_repo.join("build/out"), [0; 2000])?;
+    let vendored = app_repo.join("vendor/lib");
+    init_repository(&vendored, &[])?;
+    fs::write(vendored.join("lib.rs"), [0; 5])?;
+    fs::create_dir_all(root.join("plain"))?;
+    fs::write(root.join("plain/file"), [0; 50])?;
+
+    let build_dir_size = u128::from(fs::symlink_metadata(app_repo.join("build"))?.len());
+
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_paths(&[root.to_owned()])?;
+    app.process_events(&mut terminal, into_codes("E"))?;
+
+    let usage = app
+        .window
+        .repositories
+        .as_ref()
+        .expect("'E' shows the repositories pane")
+        .usage();
+    let sizes = |path: &std::path::Path| {
+        let usage = usage
+            .iter()
+            .find(|usage| usage.path.ends_with(path))
+            .expect("repository is listed");
+        (
+            usage.tracked,
+            usage.untracked,
+            usage.ignored,
+            usage.precious,
+        )
+    };
+    assert_eq!(
+        usage.len(),
+        2,
+        "directories outside of repositories are left out"
+    );
+    assert_eq!(
+        sizes(&app_repo),
+        (21 + 100 + 1, 10, 1000 + 2000 + build_dir_size, 20),
+        "tracked files in ignored directories count as tracked, and .git doesn't count"
+    );
+    assert_eq!(
+        sizes(&vendored),
+        (0, 5, 0, 0),
+        "nested repositories count on their own"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn repositories_pane_accounts_for_entries_of_a_worktree_given_as_roots() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let repo = fixture.path().join("app");
+    init_repository(&repo, &[".gitignore", "src/main.rs"])?;
+    fs::write(repo.join(".gitignore"), b"target/\n")?;
+    fs::create_dir_all(repo.join("src"))?;
+    fs::write(repo.join("src/main.rs"), [0; 100])?;
+    fs::write(repo.join("notes.txt"), [0; 10])?;
+    fs::create_dir_all(repo.join("target/debug"))?;
+    fs::write(repo.join("target/debug/app"), [0; 1000])?;
+
+    let target_size = u128::from(fs::symlink_metadata(repo.join("target"))?.len())
+        + u128::from(fs::symlink_metadata(repo.join("target/debug"))?.len());
+
+    // Running from inside the worktree makes its entries the roots.
+    let roots: Vec<_> = fs::read_dir(&repo)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_paths(&roots)?;
+    app.process_events(&mut terminal, into_codes("E"))?;
+
+    let usage = app
+        .window
+        .repositories
+        .as_ref()
+        .expect("'E' shows the repositories pane")
+        .usage();
+    assert_eq!(usage.len(), 1);
+    assert!(usage[0].path.ends_with("app"));
+    assert_eq!(
+        (
+            usage[0].tracked,
+            usage[0].untracked,
+            usage[0].ignored,
+            usage[0].precious
+        ),
+        (8 + 100, 10, 1000 + target_size, 0),
+        "files of the worktree count, and ignored directories count as a whole"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn repositories_pane_counts_directories_summarized_at_the_maximum_depth() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let repo = fixture.path().join("app");
+    init_repository(&repo, &["notes.txt"])?;
+    fs::write(repo.join("notes.txt"), [0; 10])?;
+    fs::create_dir_all(repo.join("docs/deep"))?;
+    fs::write(repo.join("docs/deep/guide"), [0; 300])?;
+
+    let docs_size = u128::from(fs::symlink_metadata(repo.join("docs"))?.len())
+        + u128::from(fs::symlink_metadata(repo.join("docs/deep"))?.len())
+        + 300;
+
+    let mut terminal = new_test_terminal()?;
+    let walk_options = WalkOptions {
+        threads: 1,
+        apparent_size: true,
+        count_hard_links: false,
+        cross_filesystems: false,
+        follow_links: false,
+        max_depth: Some(2),
+        collapse_files_below: None,
+        ignore_dirs: BTreeSet::default(),
+        ignore_patterns: None,
+    };
+    let (_key_send, key_receive) = crossbeam::channel::bounded(0);
+    let mut app = TerminalApp::initialize(
+        &mut terminal,
+        walk_options,
+        ByteFormat::Metric,
+        true,
+        vec![fixture.path().to_owned()],
+        Config::default(),
+    )?;
+    app.traverse()?;
+    app.run_until_traversed(&mut terminal, key_receive)?;
+    app.process_events(&mut terminal, into_codes("E"))?;
+
+    let usage = app
+        .window
+        .repositories
+        .as_ref()
+        .expect("'E' shows the repositories pane")
+        .usage();
+    assert_eq!(usage.len(), 1);
+    assert_eq!(
+        (
+            usage[0].tracked,
+            usage[0].untracked,
+            usage[0].ignored,
+            usage[0].precious
+        ),
+        (10, docs_size, 0, 0),
+        "the contents of summarized directories can't be told apart and count as untracked"
+    );
+    Ok(())
+}
+
+#[test]
+fn small_files_are_collapsed_and_listed_on_enter() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let root = fixture.path();
+    fs::create_dir_all(root.join("nested"))?;
+    fs::write(root.join("nested/tiny"), b"0")?;
+    fs::write(root.join("a"), b"0")?;
+    fs::write(root.join("b"), b"01")?;
+    fs::write(root.join("large"), [0; 100])?;
+
+    let mut terminal = new_test_terminal()?;
+    let walk_options = WalkOptions {
+        threads: 1,
+        apparent_size: true,
+        count_hard_links: false,
+        cross_filesystems: false,
+        follow_links: false,
+        max_depth: None,
+        collapse_files_below: Some(10),
+        ignore_dirs: BTreeSet::default(),
+        ignore_patterns: None,
+    };
+    let (_key_send, key_receive) = crossbeam::channel::bounded(0);
+    let mut app = TerminalApp::initialize(
+        &mut terminal,
+        walk_options,
+        ByteFormat::Metric,
+        true,
+        vec![root.to_owned()],
+        Config::default(),
+    )?;
+    app.traverse()?;
+    app.run_until_traversed(&mut terminal, key_receive.clone())?;
+
+    let entry_names = |app: &TerminalApp| {
+        app.state
+            .entries
+            .iter()
+            .map(|entry| entry.name.to_string_lossy().into_owned())
+            .collect::<BTreeSet<_>>()
+    };
+
+    // The smallest entry is the one standing for all small files.
+    app.process_events(&mut terminal, into_codes("oG"))?;
+    assert_eq!(
+        entry_names(&app),
+        BTreeSet::from(["2 small files".into(), "large".into(), "nested".into()])
+    );
+
+    app.process_events(&mut terminal, into_codes(" "))?;
+    assert!(
+        app.window.mark.is_none(),
+        "small files can't be marked as they don't have a path of their own"
+    );
+
+    app.process_events(
+        &mut terminal,
+        into_events([Event::Key(KeyCode::Enter.into())]),
+    )?;
+    app.run_until_traversed(&mut terminal, key_receive)?;
+    assert_eq!(
+        entry_names(&app),
+        BTreeSet::from(["a".into(), "b".into(), "large".into(), "nested".into()]),
+        "entering small files lists them"
+    );
+
+    let (nested, nested_entry_count) = app
+        .state
+        .entries
+        .iter()
+        .find(|entry| entry.name == std::path::Path::new("nested"))
+        .map(|entry| (entry.index, entry.entry_count))
+        .expect("nested is still present");
+    assert_eq!(nested_entry_count, Some(2), "nested and its small file");
+    app.state.navigation_mut().select(Some(nested));
+    app.process_events(&mut terminal, into_codes("o"))?;
+    assert_eq!(
+        entry_names(&app),
+        BTreeSet::from(["1 small file".into()]),
+        "only the selected directory is listed, not its subdirectories"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn configured_commands_run_on_selected_or_marked_entries_and_refresh_the_view() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let root = fixture.path();
+    fs::write(root.join("large"), [0; 100])?;
+    fs::write(root.join("small"), [0; 10])?;
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_paths(&[root.to_owned()])?;
+    let listed = root.join("listed");
+    app.config.commands = [
+        ("copy".to_owned(), "cp {path} {path}.copy".to_owned()),
+        (
+            "list".to_owned(),
+            format!("printf '%s\\n' {{paths}} > '{}'", listed.display()),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let (_key_send, key_receive) = crossbeam::channel::bounded(0);
+    let enter = || Event::Key(KeyCode::Enter.into());
+
+    app.process_events(&mut terminal, into_codes("o!c"))?;
+    app.process_events(
+        &mut terminal,
+        into_events([Event::Key(KeyCode::Tab.into()), enter()]),
+    )?;
+    app.run_until_traversed(&mut terminal, key_receive.clone())?;
+    assert_eq!(
+        fs::read(root.join("large.copy"))?,
+        [0; 100],
+        "{{path}} is the selected entry"
+    );
+    assert!(
+        app.state
+            .entries
+            .iter()
+            .any(|entry| entry.name == std::path::Path::new("large.copy")),
+        "the view is refreshed afterwards"
+    );
+
+    app.process_events(&mut terminal, into_codes("ddd!list"))?;
+    app.process_events(&mut terminal, into_events([enter()]))?;
+    app.run_until_traversed(&mut terminal, key_receive)?;
+    assert_eq!(
+        fs::read_to_string(listed)?.lines().count(),
+        3,
+        "{{paths}} are all marked entries"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn commands_on_marked_entries_refresh_their_directories_and_unmark_removed_ones() -> Result<()> {
+    let fixture = TempDir::new()?;
+    let root = fixture.path();
+    fs::create_dir(root.join("a"))?;
+    fs::write(root.join("a").join("removed"), [0; 100])?;
+    fs::create_dir(root.join("b"))?;
+    fs::write(root.join("b").join("kept"), [0; 10])?;
+    let (mut terminal, mut app) = initialized_app_and_terminal_from_paths(&[root.to_owned()])?;
+    app.config.commands = [(
+        "change".to_owned(),
+        "for p in {paths}; do case $p in *removed) rm \"$p\";; *) touch \"$p.new\";; esac; done"
+            .to_owned(),
+    )]
+    .into_iter()
+    .collect();
+    let (_key_send, key_receive) = crossbeam::channel::bounded(0);
+    let has_entry = |app: &crate::TerminalApp, name: &str| {
+        app.traversal
+            .tree
+            .node_weights()
+            .any(|entry| entry.name == std::path::Path::new(name))
+    };
+
+    // Mark a file in `a`, and one in `b` which stays in view.
+    app.process_events(&mut terminal, into_codes("oodujod"))?;
+    assert_eq!(
+        app.window.mark.as_ref().map(|pane| pane.marked().len()),
+        Some(2)
+    );
+    app.process_events(&mut terminal, into_codes("!change"))?;
+    app.process_events(
+        &mut terminal,
+        into_events([Event::Key(KeyCode::Enter.into())]),
+    )?;
+    app.run_until_traversed(&mut terminal, key_receive)?;
+
+    assert!(
+        !has_entry(&app, "removed"),
+        "the directory of marked entries out of view is refreshed"
+    );
+    assert!(
+        has_entry(&app, "kept.new"),
+        "the directory of marked entries in view is refreshed"
+    );
+    let marked: Vec<_> = app
+        .window
+        .mark
+        .as_ref()
+        .map(|pane| {
+            pane.marked()
+                .values()
+                .map(|mark| mark.path.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    assert_eq!(
+        marked,
+        [root.join("b").join("kept")],
+        "entries that don't exist anymore are unmarked"
+    );
+    Ok(())
+}
@@ -3,4 +3,4 @@ pub const FIXTURE_PATH: &str = "tests/fixtures";
 mod journeys_readonly;
 mod journeys_with_writes;
 mod unit;
-mod utils;
+pub(crate) mod utils;
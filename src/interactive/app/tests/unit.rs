@@ -6,6 +6,7 @@ use crate::interactive::widgets::glob_search;
 use crate::interactive::{EntryCheck, MTimeSort, SortMode, sorted_entries};
 use anyhow::Result;
 use dua::WalkOptions;
+use dua::ownership::Ownerships;
 use dua::traverse::{EntryData, Traversal, Tree, TreeIndex};
 use gix::glob::pattern::Case;
 use petgraph::algo::is_isomorphic_matching;
@@ -28,7 +29,7 @@ fn trees_match(left: &Tree, right: &Tree) -> bool {
             left.name == right.name
                 && left.entry_count == right.entry_count
                 && left.metadata_io_error == right.metadata_io_error
-                && (left.entry_count.is_some() || left.size == right.size)
+                && (left.entry_count.get().is_some() || left.size == right.size)
         },
         |(), ()| true,
     )
@@ -120,9 +121,11 @@ fn it_can_sort_directory_mtimes_by_recursive_entries() {
 
     let recursive = sorted_entries(
         &tree,
+        &Ownerships::default(),
         root,
         SortMode::MTimeDescending(MTimeSort::RecursiveChildrenNewest),
         None,
+        true,
         EntryCheck::Disabled,
     );
     assert_eq!(
@@ -148,9 +151,11 @@ fn it_can_sort_directory_mtimes_by_recursive_entries() {
 
     let recursive_oldest = sorted_entries(
         &tree,
+        &Ownerships::default(),
         root,
         SortMode::MTimeDescending(MTimeSort::RecursiveChildrenOldest),
         None,
+        true,
         EntryCheck::Disabled,
     );
     assert_eq!(
@@ -179,6 +184,7 @@ fn it_can_sort_directory_mtimes_by_recursive_entries() {
         root_index: root,
         start_time: Instant::now(),
         cost: None,
+        ownerships: Ownerships::default(),
     };
     let mut state = AppState::new(
         WalkOptions {
@@ -186,6 +192,9 @@ fn it_can_sort_directory_mtimes_by_recursive_entries() {
             apparent_size: true,
             count_hard_links: false,
             cross_filesystems: false,
+            follow_links: false,
+            max_depth: None,
+            collapse_files_below: None,
             ignore_dirs: BTreeSet::default(),
             ignore_patterns: None,
         },
@@ -195,15 +204,19 @@ fn it_can_sort_directory_mtimes_by_recursive_entries() {
     state.sorting = SortMode::MTimeDescending(MTimeSort::Entry);
     state.entries = sorted_entries(
         &traversal.tree,
+        &traversal.ownerships,
         root,
         state.sorting,
         None,
+        true,
         EntryCheck::Disabled,
     );
 
     let tree_view = TreeView {
         traversal: &mut traversal,
         glob_tree_root: None,
+        show_hidden: true,
+        expanded: None,
     };
     state.cycle_mtime_sort_mode(&tree_view);
     assert_eq!(
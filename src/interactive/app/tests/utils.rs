@@ -3,7 +3,7 @@ use crossbeam::channel::Receiver;
 use crossterm::event::{Event, KeyCode};
 use dua::{
     ByteFormat, Config, WalkOptions,
-    traverse::{EntryData, Tree, TreeIndex},
+    traverse::{EntryCount, EntryData, Tree, TreeIndex},
 };
 use itertools::Itertools;
 use petgraph::prelude::NodeIndex;
@@ -191,6 +191,9 @@ pub fn untraversed_app_and_terminal_with_closure(
         apparent_size: true,
         count_hard_links: false,
         cross_filesystems: false,
+        follow_links: false,
+        max_depth: None,
+        collapse_files_below: None,
         ignore_dirs: BTreeSet::default(),
         ignore_patterns: None,
     };
@@ -347,7 +350,7 @@ pub fn make_add_node(
         let n = t.add_node(EntryData {
             name: PathBuf::from(name),
             size,
-            entry_count: (entry_count > 0).then_some(entry_count),
+            entry_count: EntryCount::new((entry_count > 0).then_some(entry_count)),
             ..Default::default()
         });
         if let Some(from) = maybe_from_idx {
@@ -356,3 +359,17 @@ pub fn make_add_node(
         n
     }
 }
+
+/// Add an entry called `name` with `size` bytes to `tree` using [`make_add_node()`], below
+/// `parent` if there is one, and return its index.
+pub fn add_entry(
+    tree: &mut Tree,
+    parent: Option<TreeIndex>,
+    name: &str,
+    size: u128,
+    is_dir: bool,
+) -> TreeIndex {
+    let index = make_add_node(tree)(name, size, 0, parent);
+    tree[index].is_dir = is_dir;
+    index
+}
@@ -1,12 +1,18 @@
-use super::{EntryDataBundle, SortMode, sorted_entries};
+use super::{EntryDataBundle, SortMode, is_hidden, sorted_entries};
 use crate::interactive::{EntryCheck, path_of};
-use dua::traverse::{EntryData, Traversal, Tree, TreeIndex};
+use dua::traverse::{EntryCount, EntryData, Traversal, Tree, TreeIndex};
 use petgraph::{Direction, visit::Bfs};
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 pub struct TreeView<'a> {
     pub traversal: &'a mut Traversal,
     pub glob_tree_root: Option<TreeIndex>,
+    /// If `false`, hidden entries are left out of [`sorted_entries()`](Self::sorted_entries()).
+    pub show_hidden: bool,
+    /// The directories to list the entries of right below them, or `None` if entries aren't
+    /// displayed as a tree.
+    pub expanded: Option<BTreeSet<TreeIndex>>,
 }
 
 impl TreeView<'_> {
@@ -46,21 +52,68 @@ impl TreeView<'_> {
         path_of(&self.traversal.tree, node_idx, self.glob_tree_root)
     }
 
+    /// Return the entries of `view_root`, each followed by its own entries if it's an expanded
+    /// directory.
     pub fn sorted_entries(
         &self,
         view_root: TreeIndex,
         sorting: SortMode,
         check: EntryCheck,
+    ) -> Vec<EntryDataBundle> {
+        let entries = self.sorted_entries_of(view_root, sorting, check);
+        let Some(expanded) = self
+            .expanded
+            .as_ref()
+            .filter(|expanded| !expanded.is_empty())
+        else {
+            return entries;
+        };
+        let mut out = Vec::with_capacity(entries.len());
+        let mut stack: Vec<_> = entries.into_iter().rev().collect();
+        while let Some(mut entry) = stack.pop() {
+            if entry.is_dir && expanded.contains(&entry.index) {
+                entry.is_expanded = true;
+                let depth = entry.depth + 1;
+                stack.extend(
+                    self.sorted_entries_of(entry.index, sorting, check)
+                        .into_iter()
+                        .rev()
+                        .map(|child| EntryDataBundle { depth, ..child }),
+                );
+            }
+            out.push(entry);
+        }
+        out
+    }
+
+    fn sorted_entries_of(
+        &self,
+        node_idx: TreeIndex,
+        sorting: SortMode,
+        check: EntryCheck,
     ) -> Vec<EntryDataBundle> {
         sorted_entries(
             &self.traversal.tree,
-            view_root,
+            &self.traversal.ownerships,
+            node_idx,
             sorting,
             self.glob_tree_root,
+            self.show_hidden,
             check,
         )
     }
 
+    /// Return the amount of hidden entries directly within `view_root` along with their total size.
+    pub fn hidden_entries(&self, view_root: TreeIndex) -> (u64, u128) {
+        self.tree()
+            .neighbors_directed(view_root, Direction::Outgoing)
+            .filter_map(|idx| self.tree().node_weight(idx))
+            .filter(|entry| is_hidden(entry))
+            .fold((0, 0), |(count, size), entry| {
+                (count + 1, size + entry.size)
+            })
+    }
+
     pub fn current_path(&self, view_root: TreeIndex) -> PathBuf {
         current_path(&self.traversal.tree, view_root, self.glob_tree_root)
     }
@@ -92,15 +145,15 @@ impl TreeView<'_> {
 
     pub fn recompute_sizes_recursively(&mut self, mut index: TreeIndex) {
         loop {
-            let (size_of_children, item_count) = self
+            let (size_of_children, alternate_size_of_children, item_count) = self
                 .tree()
                 .neighbors_directed(index, Direction::Outgoing)
                 .filter_map(|idx| {
                     self.tree()
                         .node_weight(idx)
-                        .map(|w| (w.size, w.entry_count.unwrap_or(1)))
+                        .map(|w| (w.size, w.alternate_size, w.entry_count.get().unwrap_or(1)))
                 })
-                .reduce(|a, b| (a.0 + b.0, a.1 + b.1))
+                .reduce(|a, b| (a.0 + b.0, a.1.saturating_add(b.1), a.2 + b.2))
                 .unwrap_or_default();
 
             let node = self
@@ -110,7 +163,8 @@ impl TreeView<'_> {
                 .expect("valid index");
 
             node.size = size_of_children;
-            node.entry_count = Some(item_count);
+            node.alternate_size = alternate_size_of_children;
+            node.entry_count = EntryCount::new(Some(item_count));
 
             match self.fs_parent_of(index) {
                 None => break,
@@ -0,0 +1,58 @@
+use crate::interactive::widgets::tui_style;
+use dua::theme::Theme;
+use std::{borrow::Borrow, collections::BTreeMap, path::PathBuf};
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Lists the bookmarks of the current roots, which can be jumped to with `:@name`.
+#[derive(Default)]
+pub struct BookmarksPane;
+
+pub struct BookmarksPaneProps<'a> {
+    pub border_style: Style,
+    pub bookmarks: &'a BTreeMap<String, PathBuf>,
+    pub theme: &'a Theme,
+}
+
+impl BookmarksPane {
+    pub fn render<'a>(props: impl Borrow<BookmarksPaneProps<'a>>, area: Rect, buf: &mut Buffer) {
+        let BookmarksPaneProps {
+            border_style,
+            bookmarks,
+            theme,
+        } = props.borrow();
+
+        let block = Block::default()
+            .title("Bookmarks (jump with :@name) ")
+            .border_style(*border_style)
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let name_width = bookmarks
+            .keys()
+            .map(|name| name.width())
+            .max()
+            .unwrap_or_default();
+        let lines: Vec<_> = if bookmarks.is_empty() {
+            vec![Line::from("Bookmark the selected entry to list it here.")]
+        } else {
+            bookmarks
+                .iter()
+                .map(|(name, path)| {
+                    Line::from(vec![
+                        Span::styled(format!("{name:<name_width$}  "), tui_style(theme.highlight)),
+                        Span::raw(path.display().to_string()),
+                    ])
+                })
+                .collect()
+        };
+        Paragraph::new(Text::from(lines)).render(inner_area, buf);
+    }
+}
@@ -5,11 +5,16 @@ use crate::interactive::widgets::tui_ext::{
     util::{block_width, rect},
 };
 use crate::interactive::{
-    DisplayOptions, EntryDataBundle, SortMode,
-    widgets::{EntryMarkMap, entry_color},
+    DisplayOptions, EntryDataBundle, SortMode, apparent_and_disk_size, group_name,
+    is_sparse_or_compressed, owner_name,
+    widgets::{EntryMarkMap, entry_style, tui_style},
 };
 use chrono::DateTime;
-use dua::traverse::TreeIndex;
+use dua::{
+    ownership::{Ownership, Ownerships},
+    theme::Theme,
+    traverse::TreeIndex,
+};
 use itertools::Itertools;
 use std::borrow::{Borrow, Cow};
 use std::collections::{BTreeSet, HashSet};
@@ -17,15 +22,21 @@ use std::path::{Component, Path, PathBuf};
 use std::time::SystemTime;
 use tui::{
     buffer::Buffer,
-    layout::{Margin, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Margin, Position, Rect},
+    style::{Modifier, Style},
     text::Span,
-    widgets::{Block, Borders, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget},
+    widgets::{
+        Block, Borders, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
+    },
 };
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Inputs used to render the entries pane.
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag is an independent display option"
+)]
 pub struct EntriesProps<'a> {
     /// Path shown in the entries pane title.
     pub current_path: PathBuf,
@@ -41,6 +52,8 @@ pub struct EntriesProps<'a> {
     pub cleanup_candidates: Option<&'a BTreeSet<TreeIndex>>,
     /// Entry indices ignored by the current git repository, if enabled.
     pub gitignored_entries: Option<&'a BTreeSet<TreeIndex>>,
+    /// Entry indices whose size differs from their counterpart on the other side of a split view.
+    pub differing_entries: Option<&'a BTreeSet<TreeIndex>>,
     /// Border style for the entries pane.
     pub border_style: Style,
     /// Whether this pane currently owns keyboard focus.
@@ -49,11 +62,35 @@ pub struct EntriesProps<'a> {
     pub sort_mode: SortMode,
     /// Columns explicitly enabled in addition to columns implied by sorting.
     pub show_columns: &'a HashSet<Column>,
+    /// Styles to render entries with.
+    pub theme: &'a Theme,
+    /// The names of owners and groups shown in their columns.
+    pub ownerships: &'a Ownerships,
+    /// If `true`, the size of entries is their apparent size, otherwise it's their disk usage.
+    pub apparent_size: bool,
+    /// If `true`, entries are indented by their depth and directories show if they are expanded.
+    pub tree_mode: bool,
+    /// If `true`, a row with the name of each column is shown above the entries.
+    pub column_headers: bool,
+}
+
+/// A column whose header sorts the entries by it when clicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Size,
+    MTime,
+    Count,
+    Owner,
+    Name,
 }
 
 #[derive(Default)]
 pub struct Entries {
     pub list: List,
+    /// The area entries were last drawn in, one per row, starting with the one at the list offset.
+    rows_area: Rect,
+    /// The areas the column headers were last drawn in, along with the column they sort by.
+    headers: Vec<(Rect, Option<SortColumn>)>,
 }
 
 impl Entries {
@@ -71,16 +108,26 @@ impl Entries {
             marked,
             cleanup_candidates,
             gitignored_entries,
+            differing_entries,
             border_style,
             is_focussed,
             sort_mode,
             show_columns,
+            theme,
+            ownerships,
+            apparent_size,
+            tree_mode,
+            column_headers,
         } = props.borrow();
-        let list = &mut self.list;
-
-        let total: u128 = entries.iter().map(|b| b.size).sum();
-        let (recursive_item_count, item_size): (u64, u128) = entries
-            .iter()
+        let owner_width = show_column(Column::Owner, *sort_mode, show_columns)
+            .then(|| name_column_width(entries, |ownership| owner_name(ownerships, ownership)));
+        let group_width = show_column(Column::Group, *sort_mode, show_columns)
+            .then(|| name_column_width(entries, |ownership| group_name(ownerships, ownership)));
+
+        // Entries of expanded directories are already part of the size of their directory.
+        let top_level_entries = || entries.iter().filter(|b| b.depth == 0);
+        let total: u128 = top_level_entries().map(|b| b.size).sum();
+        let (recursive_item_count, item_size): (u64, u128) = top_level_entries()
             .map(|f| (f.entry_count.unwrap_or(1), f.size))
             .reduce(|a, b| (a.0 + b.0, a.1 + b.1))
             .unwrap_or_default();
@@ -97,10 +144,44 @@ impl Entries {
         let inner_area = title_block.inner(area);
         let entry_in_view = entry_in_view(*selected, entries);
 
-        let props = ListProps {
-            block: Some(title_block),
-            entry_in_view,
+        self.headers.clear();
+        let (props, rows_area) = if *column_headers && inner_area.height > 0 {
+            title_block.render(area, buf);
+            let header_area = Rect {
+                height: 1,
+                ..inner_area
+            };
+            let columns = header_columns(
+                *display,
+                *sort_mode,
+                show_columns,
+                *apparent_size,
+                owner_width,
+                group_width,
+            );
+            self.headers = draw_header(columns, *sort_mode, header_area, buf, theme);
+            let rows_area = Rect {
+                y: inner_area.y + 1,
+                height: inner_area.height - 1,
+                ..inner_area
+            };
+            (
+                ListProps {
+                    block: None,
+                    entry_in_view,
+                },
+                rows_area,
+            )
+        } else {
+            (
+                ListProps {
+                    block: Some(title_block),
+                    entry_in_view,
+                },
+                inner_area,
+            )
         };
+        self.rows_area = rows_area;
         let mut scroll_offset = None;
         let lines = entries.iter().enumerate().map(|(idx, bundle)| {
             let node_idx = &bundle.index;
@@ -111,31 +192,58 @@ impl Entries {
             let is_marked = marked.is_some_and(|m| m.contains_key(node_idx));
             let is_cleanup_candidate = cleanup_candidates.is_some_and(|c| c.contains(node_idx));
             let is_gitignored = gitignored_entries.is_some_and(|g| g.contains(node_idx));
+            let differs = differing_entries.is_some_and(|d| d.contains(node_idx));
             let is_selected = selected == &Some(*node_idx);
             if is_selected {
                 scroll_offset = Some(idx);
             }
             let fraction = bundle.size as f32 / total as f32;
-            let text_style = style(is_selected, *is_focussed);
-            let percentage_style = percentage_style(fraction, text_style);
+            let text_style = style(is_selected, *is_focussed, theme);
+            let percentage_style = percentage_style(fraction, text_style, theme);
 
             let mut columns = Vec::new();
             if show_mtime_column(*sort_mode, show_columns) {
                 columns.push(mtime_column(
                     bundle.mtime,
-                    column_style(Column::MTime, *sort_mode, text_style),
+                    column_style(Column::MTime, *sort_mode, text_style, theme),
                 ));
             }
             columns.push(bytes_column(
                 *display,
                 bundle.size,
-                column_style(Column::Bytes, *sort_mode, text_style),
+                column_style(Column::Bytes, *sort_mode, text_style, theme),
             ));
             columns.push(percentage_column(*display, fraction, percentage_style));
+            if show_column(Column::AlternateSize, *sort_mode, show_columns) {
+                columns.push(alternate_size_column(
+                    *display,
+                    bundle,
+                    *apparent_size,
+                    text_style,
+                    theme,
+                ));
+            }
             if show_count_column(*sort_mode, show_columns) {
                 columns.push(count_column(
                     bundle.entry_count,
-                    column_style(Column::Count, *sort_mode, text_style),
+                    column_style(Column::Count, *sort_mode, text_style, theme),
+                ));
+            }
+            if show_column(Column::Permissions, *sort_mode, show_columns) {
+                columns.push(permissions_column(bundle.ownership, text_style));
+            }
+            if let Some(width) = owner_width {
+                columns.push(text_column(
+                    bundle.ownership.map(|o| owner_name(ownerships, o)),
+                    width,
+                    column_style(Column::Owner, *sort_mode, text_style, theme),
+                ));
+            }
+            if let Some(width) = group_width {
+                columns.push(text_column(
+                    bundle.ownership.map(|o| group_name(ownerships, o)),
+                    width,
+                    text_style,
                 ));
             }
 
@@ -146,11 +254,15 @@ impl Entries {
                     .sum(),
             ) as usize;
 
-            let name = shorten_input(
-                name_with_prefix(name.to_string_lossy(), *is_dir),
-                available_width,
-            );
-            let style = name_style(
+            let name = name_with_prefix(name.to_string_lossy(), *is_dir);
+            let name = if *tree_mode {
+                Cow::Owned(tree_prefix(bundle) + name.as_ref())
+            } else {
+                name
+            };
+            let name = shorten_input(name, available_width);
+            let mut style = name_style(
+                theme,
                 is_marked,
                 is_cleanup_candidate,
                 is_gitignored,
@@ -158,20 +270,31 @@ impl Entries {
                 *is_dir,
                 text_style,
             );
+            if bundle.is_small_files {
+                style = style.add_modifier(Modifier::ITALIC);
+            }
+            if differs {
+                style = style.patch(tui_style(theme.difference));
+            }
             columns.push(name_column(name, area, style));
 
             columns_with_separators(columns, percentage_style, false)
         });
 
         let line_count = lines.len();
-        list.render(props, lines, area, buf);
+        let list_area = if props.block.is_some() {
+            area
+        } else {
+            rows_area
+        };
+        self.list.render(props, lines, list_area, buf);
 
         let scrollbar = Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
             .begin_symbol(None)
             .end_symbol(None);
         let mut scrollbar_state =
-            ScrollbarState::new(line_count).position(scroll_offset.unwrap_or(list.offset));
+            ScrollbarState::new(line_count).position(scroll_offset.unwrap_or(self.list.offset));
 
         scrollbar.render(area.inner(Margin::new(0, 1)), buf, &mut scrollbar_state);
 
@@ -182,6 +305,24 @@ impl Entries {
     }
 }
 
+impl Entries {
+    /// Return the position within the displayed entries of the row at `column` and `row`,
+    /// which may be past the last entry.
+    pub fn entry_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.rows_area
+            .contains(Position::new(column, row))
+            .then(|| self.list.offset + usize::from(row - self.rows_area.y))
+    }
+
+    /// Return the column whose header is at `column` and `row`, if it can be sorted by.
+    pub fn sort_column_at(&self, column: u16, row: u16) -> Option<SortColumn> {
+        self.headers
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .and_then(|(_, sort_column)| *sort_column)
+    }
+}
+
 fn entry_in_view(
     selected: Option<petgraph::stable_graph::NodeIndex>,
     entries: &[EntryDataBundle],
@@ -436,6 +577,117 @@ impl<'a> DisplayPath<'a> {
     }
 }
 
+/// Return the header text, width and the column to sort by of each column shown, in the order
+/// of the columns of each entry.
+fn header_columns(
+    display: DisplayOptions,
+    sort_mode: SortMode,
+    show_columns: &HashSet<Column>,
+    apparent_size: bool,
+    owner_width: Option<usize>,
+    group_width: Option<usize>,
+) -> Vec<(&'static str, usize, Option<SortColumn>)> {
+    let mut columns = Vec::new();
+    if show_mtime_column(sort_mode, show_columns) {
+        columns.push(("Modified", 20, Some(SortColumn::MTime)));
+    }
+    let byte_column_width = display.byte_format.width();
+    columns.push(("Size", byte_column_width, Some(SortColumn::Size)));
+    columns.push((
+        "%",
+        display.byte_vis.display(0.0).to_string().width(),
+        Some(SortColumn::Size),
+    ));
+    if show_column(Column::AlternateSize, sort_mode, show_columns) {
+        let header = if apparent_size { "On disk" } else { "Apparent" };
+        columns.push((header, byte_column_width + 6, None));
+    }
+    if show_count_column(sort_mode, show_columns) {
+        columns.push(("#", 4, Some(SortColumn::Count)));
+    }
+    if show_column(Column::Permissions, sort_mode, show_columns) {
+        columns.push(("Mode", 10, None));
+    }
+    if let Some(width) = owner_width {
+        columns.push(("Owner", width, Some(SortColumn::Owner)));
+    }
+    if let Some(width) = group_width {
+        columns.push(("Group", width, None));
+    }
+    columns.push(("Name", usize::MAX, Some(SortColumn::Name)));
+    columns
+}
+
+/// Draw the header of each column into `area`, separated like the columns of entries, and
+/// return where each was drawn.
+fn draw_header(
+    columns: Vec<(&'static str, usize, Option<SortColumn>)>,
+    sort_mode: SortMode,
+    area: Rect,
+    buf: &mut Buffer,
+    theme: &Theme,
+) -> Vec<(Rect, Option<SortColumn>)> {
+    let style = Style::default().add_modifier(Modifier::BOLD);
+    let separator = " | ";
+    let mut headers = Vec::new();
+    let mut x = area.x;
+    for (idx, (header, width, sort_column)) in columns.into_iter().enumerate() {
+        if idx > 0 {
+            let bound = Rect {
+                x,
+                width: area.right().saturating_sub(x),
+                ..area
+            };
+            draw_text_nowrap_fn(bound, buf, separator, |_, _, _| Style::default());
+            x = x.saturating_add(block_width(separator)).min(area.right());
+        }
+        let column_area = Rect {
+            x,
+            width: u16::try_from(width)
+                .unwrap_or(u16::MAX)
+                .min(area.right() - x),
+            ..area
+        };
+        let is_sorted_by = sort_column.is_some_and(|column| column.is_sorted_by(sort_mode));
+        let style = if is_sorted_by {
+            style.patch(tui_style(theme.highlight))
+        } else {
+            style
+        };
+        draw_text_nowrap_fn(column_area, buf, header, |_, _, _| style);
+        headers.push((column_area, sort_column));
+        x = column_area.right();
+    }
+    headers
+}
+
+impl SortColumn {
+    fn is_sorted_by(self, sort_mode: SortMode) -> bool {
+        match self {
+            SortColumn::Size => matches!(
+                sort_mode,
+                SortMode::SizeAscending | SortMode::SizeDescending
+            ),
+            SortColumn::MTime => matches!(
+                sort_mode,
+                SortMode::MTimeAscending(_) | SortMode::MTimeDescending(_)
+            ),
+            SortColumn::Count => matches!(
+                sort_mode,
+                SortMode::CountAscending | SortMode::CountDescending
+            ),
+            SortColumn::Owner => matches!(
+                sort_mode,
+                SortMode::OwnerAscending | SortMode::OwnerDescending
+            ),
+            SortColumn::Name => matches!(
+                sort_mode,
+                SortMode::NameAscending | SortMode::NameDescending
+            ),
+        }
+    }
+}
+
 fn draw_bottom_right_help(bound: Rect, buf: &mut Buffer) {
     let bound = line_bound(bound, bound.height.saturating_sub(1) as usize);
     let mut help_text = " mark-move = d | mark-toggle = space | cleanup = X".to_string();
@@ -471,10 +723,10 @@ fn draw_top_right_help(area: Rect, title: &str, buf: &mut Buffer) -> Rect {
     bound
 }
 
-fn style(is_selected: bool, is_focussed: bool) -> Style {
+fn style(is_selected: bool, is_focussed: bool, theme: &Theme) -> Style {
     let mut style = Style::default();
     if is_selected {
-        style.add_modifier.insert(Modifier::REVERSED);
+        style = style.patch(tui_style(theme.selection));
     }
     if is_focussed & is_selected {
         style.add_modifier.insert(Modifier::BOLD);
@@ -482,7 +734,8 @@ fn style(is_selected: bool, is_focussed: bool) -> Style {
     style
 }
 
-fn percentage_style(fraction: f32, style: Style) -> Style {
+fn percentage_style(fraction: f32, style: Style, theme: &Theme) -> Style {
+    let style = style.patch(tui_style(theme.bar));
     let avoid_big_reversed_bar = fraction > 0.9;
     if avoid_big_reversed_bar {
         style.remove_modifier(Modifier::REVERSED)
@@ -528,6 +781,32 @@ fn count_column(entry_count: Option<u64>, style: Style) -> Span<'static> {
     )
 }
 
+fn permissions_column(ownership: Option<Ownership>, style: Style) -> Span<'static> {
+    Span::styled(
+        ownership.map_or_else(|| " ".repeat(10), |o| o.permissions()),
+        style,
+    )
+}
+
+fn text_column(text: Option<Cow<'_, str>>, width: usize, style: Style) -> Span<'static> {
+    let text = text.unwrap_or_default();
+    let padding = width.saturating_sub(text.width());
+    Span::styled(format!("{text}{:padding$}", ""), style)
+}
+
+/// Return the width of the widest name `name_of` returns for the ownership of `entries`.
+fn name_column_width<'a>(
+    entries: &[EntryDataBundle],
+    name_of: impl Fn(Ownership) -> Cow<'a, str>,
+) -> usize {
+    entries
+        .iter()
+        .filter_map(|e| e.ownership)
+        .map(|o| name_of(o).width())
+        .max()
+        .unwrap_or_default()
+}
+
 fn name_column(name: Cow<'_, str>, area: Rect, style: Style) -> Span<'_> {
     Span::styled(fill_background_to_right(name, area.width), style)
 }
@@ -542,6 +821,18 @@ fn fill_background_to_right(mut s: Cow<'_, str>, entire_width: u16) -> Cow<'_, s
     }
 }
 
+/// Indent `entry` by its depth and show if it's an expanded directory.
+fn tree_prefix(entry: &EntryDataBundle) -> String {
+    let marker = match (entry.is_dir && !entry.is_small_files, entry.is_expanded) {
+        (true, true) => '▾',
+        (true, false) => '▸',
+        (false, _) => ' ',
+    };
+    let mut prefix = "  ".repeat(entry.depth);
+    prefix.push(marker);
+    prefix
+}
+
 fn name_with_prefix(mut name: Cow<'_, str>, is_dir: bool) -> Cow<'_, str> {
     let prefix = if is_dir {
         // Note that these names never happen on non-root items, so this is a root-item special case.
@@ -573,6 +864,7 @@ fn name_with_prefix(mut name: Cow<'_, str>, is_dir: bool) -> Cow<'_, str> {
 }
 
 fn name_style(
+    theme: &Theme,
     is_marked: bool,
     is_cleanup_candidate: bool,
     is_gitignored: bool,
@@ -580,19 +872,18 @@ fn name_style(
     is_dir: bool,
     style: Style,
 ) -> Style {
-    let mut style = style;
-    let fg = if !exists {
-        // non-existing - always red!
-        Some(Color::Red)
+    let style = style.patch(if !exists {
+        tui_style(theme.missing)
     } else if is_cleanup_candidate && !is_marked {
-        Some(Color::Magenta)
+        tui_style(theme.cleanup_candidate)
     } else {
-        entry_color(style.fg, !is_dir, is_marked)
-    };
+        entry_style(theme, !is_dir, is_marked)
+    });
     if is_gitignored && !is_marked && exists {
-        style.add_modifier.insert(Modifier::DIM);
+        style.patch(tui_style(theme.gitignored))
+    } else {
+        style
     }
-    Style { fg, ..style }
 }
 
 fn percentage_column(display: DisplayOptions, fraction: f32, style: Style) -> Span<'static> {
@@ -610,24 +901,59 @@ fn bytes_column(display: DisplayOptions, entry_size: u128, style: Style) -> Span
     )
 }
 
+/// The size not in use along with the disk usage in percent of the apparent size, highlighted
+/// for files that are sparse or compressed.
+fn alternate_size_column(
+    display: DisplayOptions,
+    entry: &EntryDataBundle,
+    apparent_size: bool,
+    style: Style,
+    theme: &Theme,
+) -> Span<'static> {
+    let (apparent, disk) = apparent_and_disk_size(entry, apparent_size);
+    let ratio = match (disk * 100).checked_div(apparent) {
+        None => "-".into(),
+        Some(percent) if percent > 999 => ">999%".into(),
+        Some(percent) => format!("{percent}%"),
+    };
+    let style = if !entry.is_dir && is_sparse_or_compressed(apparent, disk) {
+        style.patch(tui_style(theme.sparse))
+    } else {
+        style
+    };
+    Span::styled(
+        format!(
+            "{:>byte_column_width$} {ratio:>5}",
+            display
+                .byte_format
+                .display(entry.alternate_size)
+                .to_string(),
+            byte_column_width = display.byte_format.width()
+        ),
+        style,
+    )
+}
+
 #[derive(PartialEq, Eq, Hash)]
 pub enum Column {
     Bytes,
+    AlternateSize,
     MTime,
     Count,
+    Owner,
+    Group,
+    Permissions,
 }
 
-fn column_style(column: Column, sort_mode: SortMode, style: Style) -> Style {
-    Style {
-        fg: match (sort_mode, column) {
-            (SortMode::SizeAscending | SortMode::SizeDescending, Column::Bytes)
-            | (SortMode::MTimeAscending(_) | SortMode::MTimeDescending(_), Column::MTime)
-            | (SortMode::CountAscending | SortMode::CountDescending, Column::Count) => {
-                Color::Green.into()
-            }
-            _ => style.fg,
-        },
-        ..style
+fn column_style(column: Column, sort_mode: SortMode, style: Style, theme: &Theme) -> Style {
+    match (sort_mode, column) {
+        (SortMode::SizeAscending | SortMode::SizeDescending, Column::Bytes)
+        | (SortMode::MTimeAscending(_) | SortMode::MTimeDescending(_), Column::MTime)
+        | (SortMode::CountAscending | SortMode::CountDescending, Column::Count)
+        | (SortMode::OwnerAscending | SortMode::OwnerDescending, Column::Owner) => {
+            style.patch(tui_style(theme.highlight))
+        }
+        _ => style,
     }
 }
 
@@ -645,6 +971,15 @@ fn show_count_column(sort_mode: SortMode, show_columns: &HashSet<Column>) -> boo
     ) || show_columns.contains(&Column::Count)
 }
 
+fn show_column(column: Column, sort_mode: SortMode, show_columns: &HashSet<Column>) -> bool {
+    let implied_by_sorting = column == Column::Owner
+        && matches!(
+            sort_mode,
+            SortMode::OwnerAscending | SortMode::OwnerDescending
+        );
+    implied_by_sorting || show_columns.contains(&column)
+}
+
 /// Shorten arbitrary text by keeping terminal-cell-budgeted pieces from the start and end.
 ///
 /// This is the path-agnostic fallback used when structured compaction via
@@ -751,6 +1086,7 @@ mod entries_test {
     use crate::interactive::widgets::Column;
     use crate::interactive::{MTimeSort, SortMode};
     use dua::ByteFormat;
+    use dua::theme::{Theme, ThemeName};
     use tui::style::{Color, Modifier, Style};
     use unicode_width::UnicodeWidthStr;
 
@@ -878,9 +1214,11 @@ mod entries_test {
         let is_gitignored = true;
         let exists = true;
         let is_dir = true;
+        let theme = Theme::builtin(ThemeName::Default);
 
         assert_eq!(
             name_style(
+                &theme,
                 is_marked,
                 is_cleanup_candidate,
                 is_gitignored,
@@ -894,6 +1232,7 @@ mod entries_test {
         );
 
         let gitignored = name_style(
+            &theme,
             is_marked,
             !is_cleanup_candidate,
             is_gitignored,
@@ -909,6 +1248,7 @@ mod entries_test {
         assert!(gitignored.add_modifier.contains(Modifier::DIM));
 
         let cleanup = name_style(
+            &theme,
             is_marked,
             is_cleanup_candidate,
             !is_gitignored,
@@ -1,42 +1,48 @@
-use dua::ByteFormat;
+use dua::{ByteFormat, theme::Theme};
 use std::borrow::Borrow;
 use tui::{
     buffer::Buffer,
     layout::Rect,
     style::Modifier,
-    style::{Color, Style},
     text::{Line, Span, Text},
     widgets::{Paragraph, Widget},
 };
 
-use crate::interactive::{MTimeSort, SortMode};
+use crate::interactive::{MTimeSort, SortMode, widgets::tui_style};
 
 pub struct Footer;
 
-pub struct FooterProps {
+pub struct FooterProps<'a> {
     pub total_bytes: u128,
+    pub hidden_entries: Option<(u64, u128)>,
     pub entries_traversed: u64,
     pub traversal_start: std::time::Instant,
     pub elapsed: Option<std::time::Duration>,
     pub format: ByteFormat,
     pub message: Option<String>,
     pub sort_mode: SortMode,
+    /// If `true`, sizes are apparent sizes, otherwise they are disk usage.
+    pub apparent_size: bool,
     pub pending_exit: bool,
     pub esc_navigates_back: bool,
+    pub theme: &'a Theme,
 }
 
 impl Footer {
-    pub fn render(props: impl Borrow<FooterProps>, area: Rect, buf: &mut Buffer) {
+    pub fn render<'a>(props: impl Borrow<FooterProps<'a>>, area: Rect, buf: &mut Buffer) {
         let FooterProps {
             total_bytes,
+            hidden_entries,
             entries_traversed,
             elapsed,
             traversal_start,
             format,
             message,
             sort_mode,
+            apparent_size,
             pending_exit,
             esc_navigates_back,
+            theme,
         } = props.borrow();
 
         if *pending_exit {
@@ -46,20 +52,20 @@ impl Footer {
                 "Press esc or q again to exit..."
             };
             Paragraph::new(Text::from(exit_msg))
-                .style(
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )
+                .style(tui_style(theme.prompt))
                 .render(area, buf);
             return;
         }
 
         let spans = vec![
             Span::from(format!(
-                "Sort mode: {}  Total disk usage: {}  Processed {} entries {progress}  ",
+                "Sort mode: {}  Total {}: {}  Processed {} entries {progress}  ",
                 sort_mode_label(*sort_mode),
+                if *apparent_size {
+                    "apparent size"
+                } else {
+                    "disk usage"
+                },
                 format.display(*total_bytes),
                 entries_traversed,
                 progress = if let Some(elapsed) = elapsed {
@@ -75,22 +81,26 @@ impl Footer {
                 }
             ))
             .into(),
+            hidden_entries
+                .filter(|(count, _)| *count > 0)
+                .map(|(count, size)| {
+                    Span::from(format!(
+                        "Hidden: {count} {entries} ({size})  ",
+                        entries = if count == 1 { "entry" } else { "entries" },
+                        size = format.display(size)
+                    ))
+                }),
             message.as_ref().map(|m| {
                 Span::styled(
                     m,
-                    Style {
-                        fg: Color::Yellow.into(),
-                        bg: Color::Reset.into(),
-                        add_modifier: Modifier::BOLD | Modifier::RAPID_BLINK,
-                        ..Style::default()
-                    },
+                    tui_style(theme.message).add_modifier(Modifier::RAPID_BLINK),
                 )
             }),
         ];
         Paragraph::new(Text::from(Line::from(
             spans.into_iter().flatten().collect::<Vec<_>>(),
         )))
-        .style(Style::default().add_modifier(Modifier::REVERSED))
+        .style(tui_style(theme.footer))
         .render(area, buf);
     }
 }
@@ -106,6 +116,8 @@ fn sort_mode_label(sort_mode: SortMode) -> String {
         CountDescending => "items, most first".into(),
         NameAscending => "name, A-Z".into(),
         NameDescending => "name, Z-A".into(),
+        OwnerAscending => "owner, A-Z".into(),
+        OwnerDescending => "owner, Z-A".into(),
     }
 }
 
@@ -202,7 +202,9 @@ fn glob_search_neighbours(
     case: Case,
 ) {
     for node_index in tree.neighbors_directed(root_index, Direction::Outgoing) {
-        if let Some(node) = tree.node_weight(node_index) {
+        if let Some(node) = tree.node_weight(node_index)
+            && !node.is_small_files
+        {
             let previous_len = path.len();
             let basename_start = if path.is_empty() {
                 None
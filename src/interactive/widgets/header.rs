@@ -1,7 +1,7 @@
 use tui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Paragraph, Widget},
 };
@@ -9,22 +9,9 @@ use tui::{
 pub struct Header;
 
 impl Header {
-    pub fn render(bg_color: Color, area: Rect, buf: &mut Buffer) {
-        let standard = Style {
-            fg: Color::Black.into(),
-            bg: bg_color.into(),
-            ..Default::default()
-        };
-        debug_assert_ne!(standard.bg, standard.fg);
-        let modified = |text: &'static str, modifier| {
-            Span::styled(
-                text,
-                Style {
-                    add_modifier: modifier,
-                    ..standard
-                },
-            )
-        };
+    pub fn render(standard: Style, area: Rect, buf: &mut Buffer) {
+        let modified =
+            |text: &'static str, modifier| Span::styled(text, standard.add_modifier(modifier));
         let bold = |text: &'static str| modified(text, Modifier::BOLD);
         let italic = |text: &'static str| modified(text, Modifier::UNDERLINED);
         let text = |text: &'static str| Span::styled(text, standard);
@@ -43,10 +30,7 @@ impl Header {
             italic(" for help)"),
         ];
         Paragraph::new(Text::from(Line::from(spans)))
-            .style(Style {
-                bg: bg_color.into(),
-                ..Default::default()
-            })
+            .style(standard)
             .render(area, buf);
     }
 }
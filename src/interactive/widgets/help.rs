@@ -4,13 +4,16 @@ use crate::interactive::widgets::tui_ext::{
     draw_text_nowrap_fn,
     util::{block_width, rect},
 };
-use crossterm::event::KeyCode::{Char, Down, PageDown, PageUp, Up};
-use crossterm::event::{KeyEvent, KeyEventKind, KeyModifiers};
+use crate::interactive::widgets::tui_style;
+use dua::{
+    keys::{Action, Key, KeyBindings, KeyChord, Pane},
+    theme::Theme,
+};
 use std::{borrow::Borrow, cell::RefCell};
 use tui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph, Widget},
 };
@@ -21,10 +24,53 @@ pub struct HelpPane {
     pub language: Language,
 }
 
-pub struct HelpPaneProps {
+pub struct HelpPaneProps<'a> {
     pub border_style: Style,
     pub has_focus: bool,
     pub esc_navigates_back: bool,
+    pub bindings: &'a KeyBindings,
+    pub theme: &'a Theme,
+}
+
+/// The widest chords of one line in the key column, wider single chords get a line of their own.
+const KEY_COLUMN_WIDTH: u16 = 11;
+
+/// Join `chords` with `/` into lines that fit into the key column of the help pane.
+fn key_lines(chords: impl IntoIterator<Item = KeyChord>) -> Vec<String> {
+    let mut lines = Vec::<String>::new();
+    for chord in chords {
+        let chord = chord.to_string();
+        match lines.last_mut() {
+            Some(line) if block_width(line) + 1 + block_width(&chord) <= KEY_COLUMN_WIDTH => {
+                line.push('/');
+                line.push_str(&chord);
+            }
+            _ => lines.push(chord),
+        }
+    }
+    lines
+}
+
+/// The first chord bound to `action` in `pane` in its compact form, like `Ctrl+d`, or `-` if unbound.
+pub fn compact_chord(bindings: &KeyBindings, pane: Pane, action: Action) -> String {
+    bindings
+        .chords(pane, action)
+        .next()
+        .map_or_else(|| "-".into(), |chord| chord.to_string().replace(" + ", "+"))
+}
+
+/// The legend of navigation keys shown in the border of a focussed `pane`.
+pub fn navigation_legend(bindings: &KeyBindings, pane: Pane) -> String {
+    let chord = |pane, action| compact_chord(bindings, pane, action);
+    format!(
+        " . = {}|.. = {} ── ⇊ = {}|↓ = {}|⇈ = {}|↑ = {} ",
+        chord(Pane::Main, Action::Enter),
+        chord(Pane::Main, Action::Ascend),
+        chord(pane, Action::PageDown),
+        chord(pane, Action::Down),
+        chord(pane, Action::PageUp),
+        chord(pane, Action::Up),
+    )
 }
 
 fn margin(r: Rect, margin: u16) -> Rect {
@@ -44,23 +90,14 @@ impl HelpPane {
         }
     }
 
-    pub fn process_events(&mut self, key: KeyEvent) {
-        if key.kind == KeyEventKind::Release {
-            return;
-        }
-        match key.code {
-            Char('H') => self.scroll_help(CursorDirection::ToTop),
-            Char('G') => self.scroll_help(CursorDirection::ToBottom),
-            Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.scroll_help(CursorDirection::PageUp);
-            }
-            Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.scroll_help(CursorDirection::PageDown);
-            }
-            PageUp => self.scroll_help(CursorDirection::PageUp),
-            PageDown => self.scroll_help(CursorDirection::PageDown),
-            Char('k') | Up => self.scroll_help(CursorDirection::Up),
-            Char('j') | Down => self.scroll_help(CursorDirection::Down),
+    pub fn process_events(&mut self, action: Action) {
+        match action {
+            Action::ToTop => self.scroll_help(CursorDirection::ToTop),
+            Action::ToBottom => self.scroll_help(CursorDirection::ToBottom),
+            Action::PageUp => self.scroll_help(CursorDirection::PageUp),
+            Action::PageDown => self.scroll_help(CursorDirection::PageDown),
+            Action::Up => self.scroll_help(CursorDirection::Up),
+            Action::Down => self.scroll_help(CursorDirection::Down),
             _ => {}
         }
     }
@@ -72,8 +109,19 @@ impl HelpPane {
         clippy::cast_possible_truncation,
         reason = "scroll coordinates are bounded by terminal areas"
     )]
-    pub fn render(&mut self, props: impl Borrow<HelpPaneProps>, area: Rect, buf: &mut Buffer) {
-        let esc_navigates_back = props.borrow().esc_navigates_back;
+    pub fn render<'a>(
+        &mut self,
+        props: impl Borrow<HelpPaneProps<'a>>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let HelpPaneProps {
+            border_style,
+            has_focus,
+            esc_navigates_back,
+            bindings,
+            theme,
+        } = props.borrow();
         let t = self.language.help_text();
         let build_lines = || {
             let lines = RefCell::new(Vec::<Line<'_>>::with_capacity(30));
@@ -94,7 +142,7 @@ impl HelpPane {
                 )));
                 add_newlines(1);
             };
-            let hotkey = |keys, description, other_line: Option<&str>| {
+            let hotkey = |keys: &str, description: &str, other_line: Option<&str>| {
                 let separator_size = 3;
                 let column_size = 11 + separator_size;
                 lines.borrow_mut().push(Line::from(vec![
@@ -104,10 +152,7 @@ impl HelpPane {
                             keys,
                             column_size = column_size - separator_size
                         ),
-                        Style {
-                            fg: Color::Green.into(),
-                            ..Default::default()
-                        },
+                        tui_style(theme.highlight),
                     ),
                     Span::from(format!(" => {description}")),
                 ]));
@@ -120,86 +165,188 @@ impl HelpPane {
                     ))));
                 }
             };
+            let hotkeys = |keys: Vec<String>, description, other_line| {
+                let mut keys = keys.iter();
+                if let Some(first) = keys.next() {
+                    hotkey(first, description, other_line);
+                }
+                for continued in keys {
+                    hotkey(continued, "^", None);
+                }
+            };
+            let bound = |pane, action, description, other_line| {
+                hotkeys(
+                    key_lines(bindings.chords(pane, action)),
+                    description,
+                    other_line,
+                );
+            };
+            let is_esc = |chord: &KeyChord| chord.key == Key::Esc && !chord.ctrl && !chord.alt;
 
             title(t.pane_control_title);
             {
-                if esc_navigates_back {
-                    hotkey("q", t.pane_q_quit, None);
+                use Action::*;
+                if *esc_navigates_back {
+                    hotkeys(
+                        key_lines(bindings.chords(Pane::Global, Quit).filter(|c| !is_esc(c))),
+                        t.pane_q_quit,
+                        None,
+                    );
                     hotkey("<Esc>", t.pane_esc_close, Some(t.pane_esc_close_2));
                 } else {
-                    hotkey("q/<Esc>", t.pane_qesc_close, Some(t.pane_qesc_close_2));
+                    bound(
+                        Pane::Global,
+                        Quit,
+                        t.pane_qesc_close,
+                        Some(t.pane_qesc_close_2),
+                    );
                 }
-                hotkey("<Tab>", t.pane_tab, Some(t.pane_tab_2));
-                hotkey("?", t.pane_help_toggle, None);
+                bound(Pane::Global, CycleFocus, t.pane_tab, Some(t.pane_tab_2));
+                bound(Pane::Global, ToggleHelp, t.pane_help_toggle, None);
                 spacer();
             }
             title(t.nav_title);
             {
-                hotkey("j/<Down>", t.nav_down, None);
-                hotkey("k/<Up>", t.nav_up, None);
-                hotkey("o/l/<Enter>", t.nav_descend, None);
-                hotkey("<Right>", "^", None);
-                hotkey("u/h/<Left>", t.nav_ascend, None);
-                hotkey("<Backspace>", "^", None);
-                hotkey("Ctrl + d", t.nav_down10, None);
-                hotkey("<Page Down>", "^", None);
-                hotkey("Ctrl + u", t.nav_up10, None);
-                hotkey("<Page Up>", "^", None);
-                hotkey("H/<Home>", t.nav_top, None);
-                hotkey("G/<End>", t.nav_bottom, None);
+                use Action::*;
+                bound(Pane::Main, Down, t.nav_down, None);
+                bound(Pane::Main, Up, t.nav_up, None);
+                bound(Pane::Main, Enter, t.nav_descend, None);
+                bound(Pane::Main, Ascend, t.nav_ascend, None);
+                bound(Pane::Main, PageDown, t.nav_down10, None);
+                bound(Pane::Main, PageUp, t.nav_up10, None);
+                bound(Pane::Main, ToTop, t.nav_top, None);
+                bound(Pane::Main, ToBottom, t.nav_bottom, None);
+                bound(Pane::Main, JumpToPath, t.nav_jump, None);
+                bound(Pane::Main, Bookmark, t.nav_bookmark, None);
+                bound(Pane::Main, ToggleBookmarks, t.nav_toggle_bookmarks, None);
+                bound(Pane::Main, ToggleSplit, t.nav_toggle_split, None);
+                bound(Pane::Main, SwitchSplitSide, t.nav_switch_split_side, None);
+                bound(Pane::Main, ToggleSplitSync, t.nav_toggle_split_sync, None);
                 spacer();
             }
             title(t.disp_title);
             {
-                hotkey("s", t.disp_sort_size, None);
-                hotkey("m", t.disp_sort_mtime, None);
-                hotkey("M", t.disp_show_mtime, Some(t.disp_show_mtime_2));
-                hotkey("c", t.disp_sort_count, None);
-                hotkey("C", t.disp_show_count, None);
-                hotkey("n", t.disp_sort_name, None);
-                hotkey("g/S", t.disp_cycle_bar, None);
+                use Action::*;
+                bound(Pane::Main, SortBySize, t.disp_sort_size, None);
+                bound(Pane::Main, SortByMtime, t.disp_sort_mtime, None);
+                bound(
+                    Pane::Main,
+                    CycleMtimeSortMode,
+                    t.disp_show_mtime,
+                    Some(t.disp_show_mtime_2),
+                );
+                bound(Pane::Main, SortByCount, t.disp_sort_count, None);
+                bound(Pane::Main, ToggleCountColumn, t.disp_show_count, None);
+                bound(
+                    Pane::Main,
+                    ToggleAlternateSizeColumn,
+                    t.disp_show_alternate_size,
+                    None,
+                );
+                bound(
+                    Pane::Main,
+                    ToggleApparentSize,
+                    t.disp_toggle_apparent_size,
+                    None,
+                );
+                bound(Pane::Main, SortByName, t.disp_sort_name, None);
+                bound(Pane::Main, SortByOwner, t.disp_sort_owner, None);
+                bound(Pane::Main, ToggleOwnerColumn, t.disp_show_owner, None);
+                bound(Pane::Main, ToggleGroupColumn, t.disp_show_group, None);
+                bound(
+                    Pane::Main,
+                    TogglePermissionsColumn,
+                    t.disp_show_permissions,
+                    None,
+                );
+                bound(Pane::Main, ToggleOwnerUsage, t.disp_owner_usage, None);
+                bound(Pane::Main, ToggleTreemap, t.disp_toggle_treemap, None);
+                bound(Pane::Main, TogglePreview, t.disp_toggle_preview, None);
+                bound(
+                    Pane::Main,
+                    ToggleRepositories,
+                    t.disp_toggle_repositories,
+                    None,
+                );
+                bound(Pane::Main, CycleByteVisualization, t.disp_cycle_bar, None);
+                bound(Pane::Main, ToggleHidden, t.disp_toggle_hidden, None);
+                bound(Pane::Main, ToggleTreeMode, t.disp_toggle_tree_mode, None);
+                bound(Pane::Main, ToggleExpansion, t.disp_toggle_expansion, None);
                 spacer();
             }
             title(t.oms_title);
             {
-                hotkey("O", t.oms_open, None);
-                hotkey("d", t.oms_toggle_down, None);
-                hotkey("x", t.oms_mark_down, None);
-                hotkey("<Space>", t.oms_toggle, None);
-                hotkey("X", t.oms_mark_cleanup, None);
-                hotkey("t", t.oms_toggle_cleanup, None);
-                hotkey("I", t.oms_mark_gitignored, None);
-                hotkey("i", t.oms_toggle_gitignored, None);
-                hotkey("a", t.oms_toggle_all, None);
-                hotkey("/", t.oms_search, Some(t.oms_search_2));
-                hotkey("r", t.oms_refresh_one, None);
-                hotkey("R", t.oms_refresh_all, None);
+                use Action::*;
+                bound(Pane::Main, Open, t.oms_open, None);
+                bound(Pane::Main, RunCommand, t.oms_run_command, None);
+                bound(Pane::Main, OpenShell, t.oms_open_shell, None);
+                bound(Pane::Main, CopyPath, t.oms_copy_path, None);
+                bound(Pane::Main, ToggleMarkAndAdvance, t.oms_toggle_down, None);
+                bound(Pane::Main, MarkAndAdvance, t.oms_mark_down, None);
+                bound(Pane::Main, ToggleMark, t.oms_toggle, None);
+                bound(Pane::Main, MarkCleanupCandidates, t.oms_mark_cleanup, None);
+                bound(
+                    Pane::Main,
+                    SweepCleanupCandidates,
+                    t.oms_sweep_cleanup,
+                    None,
+                );
+                bound(
+                    Pane::Main,
+                    ToggleCleanupCandidates,
+                    t.oms_toggle_cleanup,
+                    None,
+                );
+                bound(Pane::Main, MarkGitignored, t.oms_mark_gitignored, None);
+                bound(Pane::Main, ToggleGitignored, t.oms_toggle_gitignored, None);
+                bound(Pane::Main, ToggleMarkAll, t.oms_toggle_all, None);
+                bound(
+                    Pane::Global,
+                    ToggleSearch,
+                    t.oms_search,
+                    Some(t.oms_search_2),
+                );
+                bound(Pane::Main, Refresh, t.oms_refresh_one, None);
+                bound(Pane::Main, RefreshAll, t.oms_refresh_all, None);
                 spacer();
             }
             title(t.mark_title);
             {
-                hotkey("x/d/<Space>", t.mark_remove, None);
-                hotkey("a", t.mark_remove_all, None);
-                hotkey("Ctrl + r", t.mark_delete, Some(t.mark_delete_2));
+                use Action::*;
+                bound(Pane::Mark, Unmark, t.mark_remove, None);
+                bound(Pane::Mark, UnmarkAll, t.mark_remove_all, None);
+                bound(Pane::Mark, CopyMarked, t.mark_copy, None);
+                bound(Pane::Mark, Delete, t.mark_delete, Some(t.mark_delete_2));
                 #[cfg(feature = "trash-move")]
-                hotkey("Ctrl + t", t.mark_trash, Some(t.mark_trash_2));
+                bound(Pane::Mark, Trash, t.mark_trash, Some(t.mark_trash_2));
+                spacer();
+            }
+            title(t.treemap_title);
+            {
+                use Action::*;
+                bound(Pane::Treemap, TreemapUp, t.treemap_up, None);
+                bound(Pane::Treemap, TreemapDown, t.treemap_down, None);
+                bound(Pane::Treemap, TreemapLeft, t.treemap_left, None);
+                bound(Pane::Treemap, TreemapRight, t.treemap_right, None);
+                bound(Pane::Prompt, Submit, t.prompt_submit, None);
+                bound(Pane::Prompt, Complete, t.prompt_complete, None);
                 spacer();
             }
             title(t.app_title);
             {
-                hotkey("Ctrl + c", t.app_quit, None);
+                bound(Pane::Global, Action::ForceQuit, t.app_quit, None);
+                bound(
+                    Pane::Main,
+                    Action::QuitToDirectory,
+                    t.app_quit_to_directory,
+                    None,
+                );
                 spacer();
             }
             lines.into_inner()
         };
         let lines = build_lines();
 
-        let HelpPaneProps {
-            border_style,
-            has_focus,
-            ..
-        } = props.borrow();
-
         let title = t.block_title;
         let block = Block::default()
             .title(title)
@@ -209,8 +356,8 @@ impl HelpPane {
         block.render(area, buf);
 
         if *has_focus {
-            let help_text = " . = o|.. = u ── ⇊ = Ctrl+d|↓ = j|⇈ = Ctrl+u|↑ = k ";
-            let help_text_block_width = block_width(help_text);
+            let help_text = navigation_legend(bindings, Pane::Help);
+            let help_text_block_width = block_width(&help_text);
             let bound = Rect {
                 width: area.width.saturating_sub(1),
                 ..area
@@ -219,7 +366,7 @@ impl HelpPane {
                 draw_text_nowrap_fn(
                     rect::snap_to_right(bound, help_text_block_width),
                     buf,
-                    help_text,
+                    &help_text,
                     |_, _, _| Style::default(),
                 );
             }
@@ -238,10 +385,15 @@ impl HelpPane {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use dua::theme::ThemeName;
     use tui::buffer::Cell;
 
     fn rendered(language: Language) -> String {
-        let area = Rect::new(0, 0, 120, 80);
+        rendered_with(language, &KeyBindings::default())
+    }
+
+    fn rendered_with(language: Language, bindings: &KeyBindings) -> String {
+        let area = Rect::new(0, 0, 120, 120);
         let mut buf = Buffer::empty(area);
         HelpPane {
             language,
@@ -252,6 +404,8 @@ mod tests {
                 border_style: Style::default(),
                 has_focus: false,
                 esc_navigates_back: false,
+                bindings,
+                theme: &Theme::builtin(ThemeName::Default),
             },
             area,
             &mut buf,
@@ -288,4 +442,55 @@ mod tests {
         );
         assert!(ja_collapsed.contains("ナビゲーション"));
     }
+
+    #[test]
+    fn hotkeys_are_rendered_from_the_active_bindings() {
+        let config: dua::Config = toml::from_str(
+            r#"
+            [keys.main]
+            "ctrl-n" = "down"
+            "z" = "sort-by-size"
+            "s" = "sort-by-name"
+            "#,
+        )
+        .expect("valid config");
+        let text = rendered_with(Language::English, &config.keys.bindings);
+        let line_of = |description: &str| {
+            let cells: Vec<char> = text.chars().collect();
+            cells
+                .chunks(120)
+                .map(|line| {
+                    let line: String = line.iter().collect();
+                    line.trim_matches(|c: char| c == '│' || c.is_whitespace())
+                        .to_owned()
+                })
+                .find(|line| line.ends_with(description))
+                .unwrap_or_else(|| panic!("{description:?} is rendered"))
+        };
+
+        assert_eq!(
+            line_of("Move down 1 entry."),
+            "j/<Down> => Move down 1 entry."
+        );
+        assert!(
+            text.contains("Ctrl + n => ^"),
+            "extra chords continue on the next line"
+        );
+        assert_eq!(
+            line_of("Toggle sort by size descending/ascending."),
+            "z => Toggle sort by size descending/ascending."
+        );
+        assert_eq!(
+            line_of("Toggle sort by name ascending/descending."),
+            "s/n => Toggle sort by name ascending/descending."
+        );
+        assert_eq!(
+            line_of("Select the rectangle above in the treemap."),
+            "<Up> => Select the rectangle above in the treemap."
+        );
+        assert_eq!(
+            line_of("Complete the path or command name in the prompt."),
+            "<Tab> => Complete the path or command name in the prompt."
+        );
+    }
 }
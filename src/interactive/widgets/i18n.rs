@@ -95,6 +95,12 @@ pub struct HelpText {
     pub nav_up: &'static str,
     pub nav_descend: &'static str,
     pub nav_ascend: &'static str,
+    pub nav_jump: &'static str,
+    pub nav_bookmark: &'static str,
+    pub nav_toggle_bookmarks: &'static str,
+    pub nav_toggle_split: &'static str,
+    pub nav_switch_split_side: &'static str,
+    pub nav_toggle_split_sync: &'static str,
     pub nav_down10: &'static str,
     pub nav_up10: &'static str,
     pub nav_top: &'static str,
@@ -107,15 +113,32 @@ pub struct HelpText {
     pub disp_show_mtime_2: &'static str,
     pub disp_sort_count: &'static str,
     pub disp_show_count: &'static str,
+    pub disp_show_alternate_size: &'static str,
+    pub disp_toggle_apparent_size: &'static str,
     pub disp_sort_name: &'static str,
+    pub disp_sort_owner: &'static str,
+    pub disp_show_owner: &'static str,
+    pub disp_show_group: &'static str,
+    pub disp_show_permissions: &'static str,
+    pub disp_owner_usage: &'static str,
+    pub disp_toggle_treemap: &'static str,
+    pub disp_toggle_preview: &'static str,
+    pub disp_toggle_repositories: &'static str,
     pub disp_cycle_bar: &'static str,
+    pub disp_toggle_hidden: &'static str,
+    pub disp_toggle_tree_mode: &'static str,
+    pub disp_toggle_expansion: &'static str,
 
     pub oms_title: &'static str,
     pub oms_open: &'static str,
+    pub oms_run_command: &'static str,
+    pub oms_open_shell: &'static str,
+    pub oms_copy_path: &'static str,
     pub oms_toggle_down: &'static str,
     pub oms_mark_down: &'static str,
     pub oms_toggle: &'static str,
     pub oms_mark_cleanup: &'static str,
+    pub oms_sweep_cleanup: &'static str,
     pub oms_toggle_cleanup: &'static str,
     pub oms_mark_gitignored: &'static str,
     pub oms_toggle_gitignored: &'static str,
@@ -128,6 +151,7 @@ pub struct HelpText {
     pub mark_title: &'static str,
     pub mark_remove: &'static str,
     pub mark_remove_all: &'static str,
+    pub mark_copy: &'static str,
     pub mark_delete: &'static str,
     pub mark_delete_2: &'static str,
     #[cfg(feature = "trash-move")]
@@ -135,8 +159,17 @@ pub struct HelpText {
     #[cfg(feature = "trash-move")]
     pub mark_trash_2: &'static str,
 
+    pub treemap_title: &'static str,
+    pub treemap_up: &'static str,
+    pub treemap_down: &'static str,
+    pub treemap_left: &'static str,
+    pub treemap_right: &'static str,
+    pub prompt_submit: &'static str,
+    pub prompt_complete: &'static str,
+
     pub app_title: &'static str,
     pub app_quit: &'static str,
+    pub app_quit_to_directory: &'static str,
 }
 
 const EN: HelpText = HelpText {
@@ -155,8 +188,14 @@ const EN: HelpText = HelpText {
     nav_title: "Navigation",
     nav_down: "Move down 1 entry.",
     nav_up: "Move up 1 entry.",
-    nav_descend: "Descent into the selected directory.",
+    nav_descend: "Descent into the selected directory, or list the selected small files.",
     nav_ascend: "Ascent one level into the parent directory.",
+    nav_jump: "Jump to a path relative to the roots.",
+    nav_bookmark: "Bookmark the selected entry under a name, or remove its bookmark.",
+    nav_toggle_bookmarks: "Show/hide the bookmarks, which are jumped to with @name.",
+    nav_toggle_split: "Split the view to compare with a second directory, or unsplit it.",
+    nav_switch_split_side: "Navigate the other side of the split view.",
+    nav_toggle_split_sync: "Make the other side follow this one by relative path, or stop.",
     nav_down10: "Move down 10 entries.",
     nav_up10: "Move up 10 entries.",
     nav_top: "Move to the top of the list.",
@@ -169,15 +208,32 @@ const EN: HelpText = HelpText {
     disp_show_mtime_2: "While sorting by mtime: entry, deep newest, deep oldest.",
     disp_sort_count: "Toggle sort by entries descending/ascending.",
     disp_show_count: "Show/hide entry count.",
+    disp_show_alternate_size: "Show/hide the other size and disk usage in % of apparent size.",
+    disp_toggle_apparent_size: "Switch between apparent size and disk usage.",
     disp_sort_name: "Toggle sort by name ascending/descending.",
+    disp_sort_owner: "Toggle sort by owner ascending/descending.",
+    disp_show_owner: "Show/hide owner.",
+    disp_show_group: "Show/hide group.",
+    disp_show_permissions: "Show/hide permissions.",
+    disp_owner_usage: "Show/hide bytes and entries per owner below this directory.",
+    disp_toggle_treemap: "Show/hide a treemap of this directory.",
+    disp_toggle_preview: "Show/hide metadata and contents of the selected entry.",
+    disp_toggle_repositories: "Show/hide tracked, untracked and ignored bytes per git repository.",
     disp_cycle_bar: "Cycle through percentage display and bar options.",
+    disp_toggle_hidden: "Show or hide entries whose name starts with a dot.",
+    disp_toggle_tree_mode: "Switch between a list and a tree of expandable directories.",
+    disp_toggle_expansion: "Expand/collapse the selected directory in the tree.",
 
     oms_title: "Open/Mark/Search",
     oms_open: "Open the selected entry with the associated program.",
+    oms_run_command: "Run a configured command on the selected or marked entries.",
+    oms_open_shell: "Open a shell in the selected directory, refreshing it on exit.",
+    oms_copy_path: "Copy the absolute path of the selected entry to the clipboard.",
     oms_toggle_down: "Toggle the currently selected entry and move down.",
     oms_mark_down: "Mark the currently selected entry for deletion and move down.",
     oms_toggle: "Toggle the currently selected entry.",
     oms_mark_cleanup: "Mark cleanup candidates in the current view.",
+    oms_sweep_cleanup: "List all cleanup candidates below the current directory.",
     oms_toggle_cleanup: "Toggle cleanup-candidate detection.",
     oms_mark_gitignored: "Mark Git-ignored entries in the current view.",
     oms_toggle_gitignored: "Toggle Git-ignored entry detection.",
@@ -190,6 +246,7 @@ const EN: HelpText = HelpText {
     mark_title: "Mark entries pane",
     mark_remove: "Remove the selected entry from the list.",
     mark_remove_all: "Remove all entries from the list.",
+    mark_copy: "Copy the size and path of all entries to the clipboard.",
     mark_delete: "Permanently delete all marked entries without prompt.",
     mark_delete_2: "This operation cannot be undone!",
     #[cfg(feature = "trash-move")]
@@ -197,8 +254,17 @@ const EN: HelpText = HelpText {
     #[cfg(feature = "trash-move")]
     mark_trash_2: "The entries can be restored from the trash bin.",
 
+    treemap_title: "Treemap & prompts",
+    treemap_up: "Select the rectangle above in the treemap.",
+    treemap_down: "Select the rectangle below in the treemap.",
+    treemap_left: "Select the rectangle to the left in the treemap.",
+    treemap_right: "Select the rectangle to the right in the treemap.",
+    prompt_submit: "Jump, bookmark, run or search with the input of the prompt.",
+    prompt_complete: "Complete the path or command name in the prompt.",
+
     app_title: "Application control",
     app_quit: "Close the application. No questions asked!",
+    app_quit_to_directory: "Close the application to change into this directory, see --cwd-file.",
 };
 
 const JA: HelpText = HelpText {
@@ -217,8 +283,14 @@ const JA: HelpText = HelpText {
     nav_title: "ナビゲーション",
     nav_down: "1 件下へ移動する。",
     nav_up: "1 件上へ移動する。",
-    nav_descend: "選択中のディレクトリへ入る。",
+    nav_descend: "選択中のディレクトリへ入る。または選択中の小さなファイルを一覧表示する。",
     nav_ascend: "親ディレクトリへ 1 階層戻る。",
+    nav_jump: "ルートからの相対パスへ直接移動する。",
+    nav_bookmark: "選択したエントリに名前を付けてブックマークする、またはブックマークを外す。",
+    nav_toggle_bookmarks: "ブックマークの表示を切り替える。@名前 で移動できる。",
+    nav_toggle_split: "画面を分割して別のディレクトリと比較する、または分割を解除する。",
+    nav_switch_split_side: "分割画面のもう一方を操作する。",
+    nav_toggle_split_sync: "もう一方を相対パスで連動させる、または連動を止める。",
     nav_down10: "10 件下へ移動する。",
     nav_up10: "10 件上へ移動する。",
     nav_top: "リストの先頭へ移動する。",
@@ -231,15 +303,32 @@ const JA: HelpText = HelpText {
     disp_show_mtime_2: "mtime で並べ替え中: エントリ、子孫の最新、子孫の最古。",
     disp_sort_count: "エントリ数順（降順/昇順）の並べ替えを切り替える。",
     disp_show_count: "エントリ数の表示/非表示を切り替える。",
+    disp_show_alternate_size: "もう一方のサイズと見かけのサイズに対するディスク使用量の割合の表示を切り替える。",
+    disp_toggle_apparent_size: "見かけのサイズとディスク使用量を切り替える。",
     disp_sort_name: "名前順（昇順/降順）の並べ替えを切り替える。",
+    disp_sort_owner: "所有者順（昇順/降順）の並べ替えを切り替える。",
+    disp_show_owner: "所有者の表示/非表示を切り替える。",
+    disp_show_group: "グループの表示/非表示を切り替える。",
+    disp_show_permissions: "パーミッションの表示/非表示を切り替える。",
+    disp_owner_usage: "このディレクトリ以下の所有者ごとの容量とエントリ数の表示を切り替える。",
+    disp_toggle_treemap: "このディレクトリのツリーマップの表示を切り替える。",
+    disp_toggle_preview: "選択中の項目のメタデータと内容の表示を切り替える。",
+    disp_toggle_repositories: "Gitリポジトリごとの追跡・未追跡・無視された容量の表示を切り替える。",
     disp_cycle_bar: "割合表示とバー表示の形式を順に切り替える。",
+    disp_toggle_hidden: "名前がドットで始まるエントリの表示を切り替える。",
+    disp_toggle_tree_mode: "一覧表示と展開可能なツリー表示を切り替える。",
+    disp_toggle_expansion: "ツリー内で選択したディレクトリを展開/折りたたむ。",
 
     oms_title: "開く / マーク / 検索",
     oms_open: "選択中のエントリを関連付けられたプログラムで開く。",
+    oms_run_command: "設定したコマンドを選択中またはマーク済みのエントリに対して実行する。",
+    oms_open_shell: "選択中のディレクトリでシェルを開き、終了時に再スキャンする。",
+    oms_copy_path: "選択中のエントリの絶対パスをクリップボードにコピーする。",
     oms_toggle_down: "選択中のエントリを切り替えて下へ移動する。",
     oms_mark_down: "選択中のエントリを削除対象にマークして下へ移動する。",
     oms_toggle: "選択中のエントリを切り替える。",
     oms_mark_cleanup: "現在のビューのクリーンアップ候補をマークする。",
+    oms_sweep_cleanup: "現在のディレクトリ以下のクリーンアップ候補をすべて一覧表示する。",
     oms_toggle_cleanup: "クリーンアップ候補の検出を切り替える。",
     oms_mark_gitignored: "現在のビューの Git 無視エントリをマークする。",
     oms_toggle_gitignored: "Git 無視エントリの検出を切り替える。",
@@ -252,6 +341,7 @@ const JA: HelpText = HelpText {
     mark_title: "マーク済みペイン",
     mark_remove: "選択中のエントリをリストから外す。",
     mark_remove_all: "すべてのエントリをリストから外す。",
+    mark_copy: "すべてのエントリのサイズとパスをクリップボードにコピーする。",
     mark_delete: "マークしたすべてのエントリを確認なしで完全に削除する。",
     mark_delete_2: "この操作は取り消せません！",
     #[cfg(feature = "trash-move")]
@@ -259,8 +349,17 @@ const JA: HelpText = HelpText {
     #[cfg(feature = "trash-move")]
     mark_trash_2: "エントリはゴミ箱から復元できる。",
 
+    treemap_title: "ツリーマップとプロンプト",
+    treemap_up: "ツリーマップで上の矩形を選択する。",
+    treemap_down: "ツリーマップで下の矩形を選択する。",
+    treemap_left: "ツリーマップで左の矩形を選択する。",
+    treemap_right: "ツリーマップで右の矩形を選択する。",
+    prompt_submit: "プロンプトの入力で移動・ブックマーク・実行・検索する。",
+    prompt_complete: "プロンプトでパスやコマンド名を補完する。",
+
     app_title: "アプリ操作",
     app_quit: "アプリケーションを終了する。確認なし！",
+    app_quit_to_directory: "終了してこのディレクトリに移動する。--cwd-file を参照。",
 };
 
 #[cfg(test)]
@@ -0,0 +1,390 @@
+use crate::interactive::{
+    state::Cursor,
+    widgets::tui_ext::{
+        draw_text_nowrap_fn,
+        util::{block_width, rect},
+    },
+};
+use anyhow::{Result, bail};
+use crossterm::event::{KeyEvent, KeyEventKind, KeyModifiers};
+use dua::traverse::{Tree, TreeIndex};
+use petgraph::Direction;
+use std::{
+    borrow::Borrow,
+    path::{Component, Path, PathBuf},
+};
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+use unicode_width::UnicodeWidthStr;
+
+pub struct JumpPaneProps {
+    pub border_style: Style,
+    pub has_focus: bool,
+}
+
+/// What the text typed into a [`JumpPane`] is used for.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum PromptPurpose {
+    /// Navigate to the typed path relative to the roots, or to the bookmark named after an `@`.
+    #[default]
+    JumpToPath,
+    /// Bookmark the given path under the typed name.
+    NameBookmark(PathBuf),
+    /// Run the configured command with the typed name.
+    RunCommand,
+}
+
+/// A prompt for a path to navigate to directly, or for the name of a bookmark or command.
+#[derive(Default)]
+pub struct JumpPane {
+    /// The text typed so far. The cursor is always at its end.
+    pub input: String,
+    pub purpose: PromptPurpose,
+}
+
+/// The result of completing a partially typed path.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// The input, extended by as much as all candidates have in common.
+    pub input: String,
+    /// The names of all entries that start with the last component of the input.
+    pub candidates: Vec<String>,
+}
+
+impl JumpPane {
+    /// Prompt for the name of a bookmark of `path`.
+    pub fn name_bookmark(path: PathBuf) -> Self {
+        JumpPane {
+            input: String::new(),
+            purpose: PromptPurpose::NameBookmark(path),
+        }
+    }
+
+    /// Prompt for the name of a command to run.
+    pub fn run_command() -> Self {
+        JumpPane {
+            input: String::new(),
+            purpose: PromptPurpose::RunCommand,
+        }
+    }
+
+    pub fn process_events(&mut self, key: KeyEvent) {
+        use crossterm::event::KeyCode::{Backspace, Char};
+        if key.kind == KeyEventKind::Release {
+            return;
+        }
+        match key.code {
+            Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => self.input.clear(),
+            Char(to_insert) => self.input.push(to_insert),
+            Backspace => {
+                self.input.pop();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn render(
+        &self,
+        props: impl Borrow<JumpPaneProps>,
+        area: Rect,
+        buffer: &mut Buffer,
+        cursor: &mut Cursor,
+    ) {
+        let JumpPaneProps {
+            border_style,
+            has_focus,
+        } = props.borrow();
+
+        let (title, help_text) = match &self.purpose {
+            PromptPurpose::JumpToPath => (
+                "Jump to path or @bookmark".to_owned(),
+                " jump = enter | complete = tab | cancel = esc ",
+            ),
+            PromptPurpose::NameBookmark(path) => (
+                format!("Name the bookmark of '{}'", path.display()),
+                " bookmark = enter | cancel = esc ",
+            ),
+            PromptPurpose::RunCommand => (
+                "Run command".to_owned(),
+                " run = enter | complete = tab | cancel = esc ",
+            ),
+        };
+        let block = Block::default()
+            .title(title.as_str())
+            .border_style(*border_style)
+            .borders(Borders::ALL);
+        let inner_block_area = block.inner(area);
+        block.render(area, buffer);
+
+        let input_area = Rect {
+            x: inner_block_area.x + 1,
+            width: inner_block_area.width.saturating_sub(2),
+            ..inner_block_area
+        };
+        Paragraph::new(Line::from(self.input.as_str())).render(input_area, buffer);
+
+        if *has_focus {
+            draw_top_right_help(area, &title, help_text, buffer);
+
+            cursor.show = true;
+            cursor.x = input_area.x
+                + u16::try_from(self.input.width())
+                    .unwrap_or(u16::MAX)
+                    .min(input_area.width);
+            cursor.y = input_area.y;
+        }
+    }
+}
+
+fn draw_top_right_help(area: Rect, title: &str, help_text: &str, buf: &mut Buffer) {
+    let help_text_block_width = block_width(help_text);
+    let bound = Rect {
+        width: area.width.saturating_sub(1),
+        ..area
+    };
+    if block_width(title) + help_text_block_width <= bound.width {
+        draw_text_nowrap_fn(
+            rect::snap_to_right(bound, help_text_block_width),
+            buf,
+            help_text,
+            |_, _, _| Style::default(),
+        );
+    }
+}
+
+/// Return the entries along `path` below `root_index`, the last of which is the entry `path`
+/// points to, or an empty list if it points to `root_index` itself.
+///
+/// Entries whose name consists of multiple components, like the roots of a traversal, match
+/// all of them at once.
+pub fn resolve_path(tree: &Tree, root_index: TreeIndex, path: &Path) -> Result<Vec<TreeIndex>> {
+    let mut remaining = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => bail!("Paths must not contain '..'"),
+            component => remaining.push(component),
+        }
+    }
+
+    let mut entries: Vec<TreeIndex> = Vec::new();
+    let mut parent = root_index;
+    while !remaining.as_os_str().is_empty() {
+        let Some((index, rest)) = tree
+            .neighbors_directed(parent, Direction::Outgoing)
+            .filter_map(|index| {
+                let entry = &tree[index];
+                if entry.is_small_files {
+                    return None;
+                }
+                let rest = remaining.strip_prefix(&entry.name).ok()?;
+                Some((index, rest.to_owned(), entry.name.components().count()))
+            })
+            .max_by_key(|(_, _, num_components)| *num_components)
+            .map(|(index, rest, _)| (index, rest))
+        else {
+            let path_so_far: PathBuf = entries.iter().map(|index| &tree[*index].name).collect();
+            bail!(
+                "There is no '{}' in '{}'",
+                Path::new(remaining.iter().next().unwrap_or_default()).display(),
+                if entries.is_empty() {
+                    Path::new(".")
+                } else {
+                    &path_so_far
+                }
+                .display()
+            );
+        };
+        entries.push(index);
+        parent = index;
+        remaining = rest;
+    }
+    Ok(entries)
+}
+
+/// Complete the last component of `input` with the names of the entries in the directory it
+/// is in, relative to `root_index`.
+///
+/// A `/` is appended once the input names a single directory with entries.
+pub fn complete_path(tree: &Tree, root_index: TreeIndex, input: &str) -> Completion {
+    let name_of = |index: TreeIndex| tree[index].name.to_string_lossy().into_owned();
+    let entries_of = |parent: TreeIndex| {
+        tree.neighbors_directed(parent, Direction::Outgoing)
+            .filter(|index| !tree[*index].is_small_files)
+    };
+
+    // Names may consist of multiple components, so directories are matched by name instead
+    // of splitting the input at each `/`.
+    let mut parent = root_index;
+    let mut directory_len = 0;
+    'descend: loop {
+        let rest = &input[directory_len..];
+        if let Some(after) = rest.strip_prefix("./") {
+            directory_len = input.len() - after.len();
+            continue;
+        }
+        for index in entries_of(parent) {
+            if let Some(after) = rest
+                .strip_prefix(name_of(index).as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                directory_len = input.len() - after.len();
+                parent = index;
+                continue 'descend;
+            }
+        }
+        break;
+    }
+
+    let (directory, prefix) = input.split_at(directory_len);
+    let mut candidates: Vec<_> = entries_of(parent)
+        .map(|index| (name_of(index), index))
+        .filter(|(name, _)| name.starts_with(prefix))
+        .collect();
+    candidates.sort();
+    let Some(mut common) = common_prefix(candidates.iter().map(|(name, _)| name.as_str())) else {
+        return Completion {
+            input: input.to_owned(),
+            candidates: Vec::new(),
+        };
+    };
+    if let [(_, index)] = candidates.as_slice()
+        && entries_of(*index).next().is_some()
+    {
+        common.push('/');
+    }
+
+    Completion {
+        input: format!("{directory}{common}"),
+        candidates: candidates.into_iter().map(|(name, _)| name).collect(),
+    }
+}
+
+/// Complete `prefix` with the `names` starting with it, which are expected to be sorted.
+pub fn complete_name<'a>(names: impl IntoIterator<Item = &'a str>, prefix: &str) -> Completion {
+    let candidates: Vec<_> = names
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .map(ToOwned::to_owned)
+        .collect();
+    Completion {
+        input: common_prefix(candidates.iter().map(String::as_str))
+            .unwrap_or_else(|| prefix.to_owned()),
+        candidates,
+    }
+}
+
+/// Return the longest prefix all `names` have in common, or `None` if there are no names.
+fn common_prefix<'a>(mut names: impl Iterator<Item = &'a str>) -> Option<String> {
+    let mut common = names.next()?.to_owned();
+    for name in names {
+        let common_len = common
+            .char_indices()
+            .zip(name.chars())
+            .find_map(|((pos, lhs), rhs)| (lhs != rhs).then_some(pos))
+            .unwrap_or_else(|| common.len().min(name.len()));
+        common.truncate(common_len);
+    }
+    Some(common)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interactive::app::tests::utils::add_entry;
+
+    fn tree() -> (Tree, TreeIndex) {
+        let mut tree = Tree::new();
+        let add = |tree: &mut Tree, parent, name| add_entry(tree, parent, name, 0, true);
+        let root = add(&mut tree, None, "");
+        let src = add(&mut tree, Some(root), "src");
+        let interactive = add(&mut tree, Some(src), "interactive");
+        add(&mut tree, Some(interactive), "widgets");
+        add(&mut tree, Some(src), "interesting");
+        add(&mut tree, Some(root), "target");
+        (tree, root)
+    }
+
+    #[test]
+    fn paths_resolve_to_the_entries_along_them() -> Result<()> {
+        let (tree, root) = tree();
+        let names = |entries: Vec<TreeIndex>| -> Vec<String> {
+            entries
+                .into_iter()
+                .map(|index| tree[index].name.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(
+            names(resolve_path(
+                &tree,
+                root,
+                Path::new("src/interactive/widgets")
+            )?),
+            ["src", "interactive", "widgets"]
+        );
+        assert_eq!(
+            names(resolve_path(&tree, root, Path::new("./src/"))?),
+            ["src"]
+        );
+        assert!(resolve_path(&tree, root, Path::new(""))?.is_empty());
+        assert_eq!(
+            resolve_path(&tree, root, Path::new("src/missing/widgets"))
+                .unwrap_err()
+                .to_string(),
+            "There is no 'missing' in 'src'"
+        );
+        assert!(resolve_path(&tree, root, Path::new("src/..")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn completion_extends_the_input_by_the_common_prefix() {
+        let (tree, root) = tree();
+        let complete = |input| complete_path(&tree, root, input);
+        assert_eq!(
+            complete("s"),
+            Completion {
+                input: "src/".into(),
+                candidates: vec!["src".into()]
+            },
+            "directories with entries get a trailing slash"
+        );
+        assert_eq!(
+            complete("src/int"),
+            Completion {
+                input: "src/inter".into(),
+                candidates: vec!["interactive".into(), "interesting".into()]
+            }
+        );
+        assert_eq!(
+            complete("src/interes").input,
+            "src/interesting",
+            "empty directories can't be descended into"
+        );
+        assert_eq!(
+            complete("").candidates,
+            ["src", "target"],
+            "all entries are candidates"
+        );
+        assert_eq!(
+            complete_name(["build", "bundle", "cache"], "b"),
+            Completion {
+                input: "bu".into(),
+                candidates: vec!["build".into(), "bundle".into()]
+            },
+            "names are completed the same way"
+        );
+        assert_eq!(
+            complete("nothing/here"),
+            Completion {
+                input: "nothing/here".into(),
+                candidates: Vec::new()
+            }
+        );
+    }
+}
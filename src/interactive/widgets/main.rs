@@ -1,30 +1,38 @@
 use crate::interactive::{
     DisplayOptions,
+    split::differing_entries,
     state::{AppState, Cursor, FocussedPane},
     widgets::{
-        COLOR_MARKED, Entries, EntriesProps, Footer, FooterProps, GlobPane, GlobPaneProps, Header,
-        HelpPane, HelpPaneProps, MarkPane, MarkPaneProps,
+        BookmarksPane, BookmarksPaneProps, Entries, EntriesProps, Footer, FooterProps, GlobPane,
+        GlobPaneProps, Header, HelpPane, HelpPaneProps, JumpPane, JumpPaneProps, MarkPane,
+        MarkPaneProps, OwnersPane, OwnersPaneProps, PreviewPane, PreviewPaneProps,
+        RepositoriesPane, RepositoriesPaneProps, TreemapPane, TreemapPaneProps, tui_style,
     },
 };
-use Constraint::{Length, Max, Percentage};
-use FocussedPane::{Glob, Help, Main, Mark};
+use Constraint::{Length, Max, Percentage, Ratio};
+use FocussedPane::{Glob, Help, Jump, Main, Mark, Treemap};
+use dua::theme::Theme;
 use std::borrow::Borrow;
 use std::path::PathBuf;
 use tui::buffer::Buffer;
 use tui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::Modifier,
-    style::{Color, Style},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::Style,
 };
 
 pub struct MainWindowProps<'a> {
     pub current_path: PathBuf,
+    /// The path shown on the other side of a split view, if the view is split.
+    pub split_path: Option<PathBuf>,
     pub entries_traversed: u64,
     pub total_bytes: u128,
+    /// The amount and total size of hidden entries in view, if hidden entries aren't shown.
+    pub hidden_entries: Option<(u64, u128)>,
     pub start: std::time::Instant,
     pub elapsed: Option<std::time::Duration>,
     pub display: DisplayOptions,
     pub state: &'a AppState,
+    pub ownerships: &'a dua::ownership::Ownerships,
     pub config: &'a dua::Config,
 }
 
@@ -32,8 +40,20 @@ pub struct MainWindowProps<'a> {
 pub struct MainWindow {
     pub help: Option<HelpPane>,
     pub entries: Entries,
+    /// The entries of the other side of a split view, if the view is split.
+    pub split: Option<Entries>,
     pub mark: Option<MarkPane>,
     pub glob: Option<GlobPane>,
+    pub jump: Option<JumpPane>,
+    pub owners: Option<OwnersPane>,
+    pub treemap: Option<TreemapPane>,
+    pub bookmarks: Option<BookmarksPane>,
+    pub preview: Option<PreviewPane>,
+    pub repositories: Option<RepositoriesPane>,
+    /// The styles to draw with, built from the configuration once.
+    pub theme: Theme,
+    /// The areas of the focussable panes as last drawn, to find the one under the mouse.
+    pane_areas: Vec<(Rect, FocussedPane)>,
 }
 
 impl MainWindow {
@@ -46,63 +66,201 @@ impl MainWindow {
     ) {
         let MainWindowProps {
             current_path,
+            split_path,
             entries_traversed,
             total_bytes,
+            hidden_entries,
             start,
             elapsed,
             display,
             state,
+            ownerships,
             config,
         } = props.borrow();
 
-        let (entries_style, help_style, mark_style, glob_style) = pane_border_style(state.focussed);
+        let theme = &self.theme;
+        let (entries_style, help_style, mark_style, glob_style, jump_style, treemap_style) =
+            pane_border_style(state.focussed, theme);
         let (header_area, content_area, footer_area) = main_window_layout(area);
 
-        let header_bg_color = header_background_color(self.is_anything_marked(), state.focussed);
-        Header::render(header_bg_color, header_area, buffer);
+        let header_style = header_style(self.is_anything_marked(), state.focussed, theme);
+        Header::render(header_style, header_area, buffer);
 
-        let (entries_area, help_pane, mark_pane) = {
+        let (
+            entries_area,
+            help_pane,
+            mark_pane,
+            owners_pane,
+            treemap_pane,
+            bookmarks_pane,
+            preview_pane,
+            repositories_pane,
+        ) = {
             let (left_pane, right_pane) = content_layout(content_area);
-            match (&mut self.help, &mut self.mark) {
-                (Some(pane), None) => (left_pane, Some((right_pane, pane)), None),
-                (None, Some(pane)) => (left_pane, None, Some((right_pane, pane))),
-                (Some(help), Some(mark)) => {
-                    let (top_area, bottom_area) = right_pane_layout(right_pane);
-                    (left_pane, Some((top_area, help)), Some((bottom_area, mark)))
-                }
-                (None, None) => (content_area, None, None),
+            let num_right_panes = usize::from(self.help.is_some())
+                + usize::from(self.mark.is_some())
+                + usize::from(self.owners.is_some())
+                + usize::from(self.treemap.is_some())
+                + usize::from(self.bookmarks.is_some())
+                + usize::from(self.preview.is_some())
+                + usize::from(self.repositories.is_some());
+            if num_right_panes == 0 {
+                (content_area, None, None, None, None, None, None, None)
+            } else {
+                let mut areas = right_pane_layout(right_pane, num_right_panes).into_iter();
+                let mut next_area = || areas.next().expect("one area per right pane");
+                (
+                    left_pane,
+                    self.help.as_mut().map(|pane| (next_area(), pane)),
+                    self.mark.as_mut().map(|pane| (next_area(), pane)),
+                    self.owners.as_ref().map(|pane| (next_area(), pane)),
+                    self.treemap.as_mut().map(|pane| (next_area(), pane)),
+                    self.bookmarks.as_ref().map(|_| next_area()),
+                    self.preview.as_ref().map(|pane| (next_area(), pane)),
+                    self.repositories.as_ref().map(|pane| (next_area(), pane)),
+                )
             }
         };
 
-        let (entries_area, glob_pane) = match &mut self.glob {
-            Some(glob_pane) => {
-                let regions = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Max(256), Length(3)].as_ref())
-                    .split(entries_area);
-                (regions[0], Some((regions[1], glob_pane)))
-            }
-            None => (entries_area, None),
+        let (entries_area, glob_pane, jump_pane) = {
+            let num_prompts = usize::from(self.glob.is_some()) + usize::from(self.jump.is_some());
+            let mut constraints = vec![Max(256)];
+            constraints.extend(std::iter::repeat_n(Length(3), num_prompts));
+            let regions = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(constraints)
+                .split(entries_area);
+            let mut prompt_areas = regions.iter().skip(1).copied();
+            (
+                regions[0],
+                self.glob
+                    .as_mut()
+                    .map(|pane| (prompt_areas.next().expect("one area per prompt"), pane)),
+                self.jump
+                    .as_ref()
+                    .map(|pane| (prompt_areas.next().expect("one area per prompt"), pane)),
+            )
         };
 
+        let help_pane_area = help_pane.as_ref().map(|(area, _)| *area);
+        let mark_pane_area = mark_pane.as_ref().map(|(area, _)| *area);
+        let glob_pane_area = glob_pane.as_ref().map(|(area, _)| *area);
+        let jump_pane_area = jump_pane.as_ref().map(|(area, _)| *area);
+
         if let Some((mark_area, pane)) = mark_pane {
             let props = MarkPaneProps {
                 border_style: mark_style,
                 format: display.byte_format,
+                bindings: &config.keys.bindings,
+                theme,
             };
             pane.render(props, mark_area, buffer);
         }
 
+        if let Some((owners_area, pane)) = owners_pane {
+            let props = OwnersPaneProps {
+                border_style: tui_style(theme.border),
+                format: display.byte_format,
+                ownerships,
+                theme,
+            };
+            pane.render(props, owners_area, buffer);
+        }
+
+        let treemap_pane_area = treemap_pane.as_ref().map(|(area, _)| *area);
+        if let Some((treemap_area, pane)) = treemap_pane {
+            let props = TreemapPaneProps {
+                border_style: treemap_style,
+                format: display.byte_format,
+                entries: &state.entries,
+                selected: state.navigation().selected,
+                theme,
+            };
+            pane.render(props, treemap_area, buffer);
+        }
+
+        if let Some(bookmarks_area) = bookmarks_pane {
+            let props = BookmarksPaneProps {
+                border_style: tui_style(theme.border),
+                bookmarks: state.bookmarks.by_name(),
+                theme,
+            };
+            BookmarksPane::render(props, bookmarks_area, buffer);
+        }
+
+        if let Some((preview_area, pane)) = preview_pane {
+            let props = PreviewPaneProps {
+                border_style: tui_style(theme.border),
+                format: display.byte_format,
+                theme,
+            };
+            pane.render(props, preview_area, buffer);
+        }
+
+        if let Some((repositories_area, pane)) = repositories_pane {
+            let props = RepositoriesPaneProps {
+                border_style: tui_style(theme.border),
+                format: display.byte_format,
+                theme,
+            };
+            pane.render(props, repositories_area, buffer);
+        }
+
         if let Some((help_area, pane)) = help_pane {
             let props = HelpPaneProps {
                 border_style: help_style,
                 has_focus: matches!(state.focussed, Help),
                 esc_navigates_back: config.keys.esc_navigates_back,
+                bindings: &config.keys.bindings,
+                theme,
             };
             pane.render(props, help_area, buffer);
         }
 
+        let split = state.split.as_ref().zip(self.split.as_mut());
+        let differing_entries = split.as_ref().map(|(split, _)| {
+            differing_entries(
+                &state.entries,
+                &split.entries,
+                config.split_threshold.unwrap_or(10),
+            )
+        });
+        let (entries_area, split_area) = match split.as_ref() {
+            Some((split, _)) => {
+                let (left, right) = content_layout(entries_area);
+                if split.is_left {
+                    (right, Some(left))
+                } else {
+                    (left, Some(right))
+                }
+            }
+            None => (entries_area, None),
+        };
+
         let marked = self.mark.as_ref().map(|pane| pane.marked());
+        if let (Some((split, entries)), Some(split_area)) = (split, split_area) {
+            let props = EntriesProps {
+                current_path: split_path.clone().unwrap_or_default(),
+                display: *display,
+                entries: &split.entries,
+                marked,
+                cleanup_candidates: None,
+                gitignored_entries: None,
+                differing_entries: differing_entries.as_ref(),
+                selected: split.navigation.selected,
+                border_style: tui_style(theme.border),
+                is_focussed: false,
+                sort_mode: state.sorting,
+                show_columns: &state.show_columns,
+                theme,
+                ownerships,
+                apparent_size: state.walk_options.apparent_size,
+                tree_mode: state.tree_mode,
+                column_headers: config.mouse == Some(true),
+            };
+            entries.render(props, split_area, buffer);
+        }
+
         let props = EntriesProps {
             current_path: current_path.clone(),
             display: *display,
@@ -110,14 +268,32 @@ impl MainWindow {
             marked,
             cleanup_candidates: state.cleanup_candidates.as_ref(),
             gitignored_entries: state.gitignored_entries.as_ref(),
+            differing_entries: differing_entries.as_ref(),
             selected: state.navigation().selected,
             border_style: entries_style,
             is_focussed: matches!(state.focussed, Main),
             sort_mode: state.sorting,
             show_columns: &state.show_columns,
+            theme,
+            ownerships,
+            apparent_size: state.walk_options.apparent_size,
+            tree_mode: state.tree_mode,
+            column_headers: config.mouse == Some(true),
         };
         self.entries.render(props, entries_area, buffer);
 
+        self.pane_areas = [
+            Some((entries_area, Main)),
+            help_pane_area.map(|area| (area, Help)),
+            mark_pane_area.map(|area| (area, Mark)),
+            treemap_pane_area.map(|area| (area, Treemap)),
+            glob_pane_area.map(|area| (area, Glob)),
+            jump_pane_area.map(|area| (area, Jump)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
         if let Some((glob_area, pane)) = glob_pane {
             let props = GlobPaneProps {
                 border_style: glob_style,
@@ -126,23 +302,82 @@ impl MainWindow {
             pane.render(props, glob_area, buffer, cursor);
         }
 
+        if let Some((jump_area, pane)) = jump_pane {
+            let props = JumpPaneProps {
+                border_style: jump_style,
+                has_focus: matches!(state.focussed, Jump),
+            };
+            pane.render(props, jump_area, buffer, cursor);
+        }
+
         Footer::render(
             FooterProps {
                 total_bytes: *total_bytes,
+                hidden_entries: *hidden_entries,
                 format: display.byte_format,
                 entries_traversed: *entries_traversed,
                 message: state.message.clone(),
                 traversal_start: *start,
                 elapsed: *elapsed,
                 sort_mode: state.sorting,
+                apparent_size: state.walk_options.apparent_size,
                 pending_exit: state.pending_exit,
                 esc_navigates_back: config.keys.esc_navigates_back,
+                theme,
             },
             footer_area,
             buffer,
         );
     }
 
+    /// Show the owners pane, or hide it if it's shown.
+    pub fn toggle_owners_pane(&mut self) {
+        self.owners = match self.owners {
+            Some(_) => None,
+            None => Some(OwnersPane::default()),
+        };
+    }
+
+    /// Show the treemap pane, or hide it if it's shown.
+    pub fn toggle_treemap_pane(&mut self) {
+        self.treemap = match self.treemap {
+            Some(_) => None,
+            None => Some(TreemapPane::default()),
+        };
+    }
+
+    /// Show the bookmarks pane, or hide it if it's shown.
+    pub fn toggle_bookmarks_pane(&mut self) {
+        self.bookmarks = match self.bookmarks {
+            Some(_) => None,
+            None => Some(BookmarksPane),
+        };
+    }
+
+    /// Show the preview pane, or hide it if it's shown.
+    pub fn toggle_preview_pane(&mut self) {
+        self.preview = match self.preview {
+            Some(_) => None,
+            None => Some(PreviewPane::default()),
+        };
+    }
+
+    /// Show the repositories pane, or hide it if it's shown.
+    pub fn toggle_repositories_pane(&mut self) {
+        self.repositories = match self.repositories {
+            Some(_) => None,
+            None => Some(RepositoriesPane::default()),
+        };
+    }
+
+    /// Return the pane drawn at `column` and `row`, if it can be focussed.
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<FocussedPane> {
+        self.pane_areas
+            .iter()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, pane)| *pane)
+    }
+
     fn is_anything_marked(&self) -> bool {
         self.mark
             .as_ref()
@@ -151,12 +386,13 @@ impl MainWindow {
     }
 }
 
-fn right_pane_layout(right_pane: Rect) -> (Rect, Rect) {
-    let regions = Layout::default()
+fn right_pane_layout(right_pane: Rect, num_panes: usize) -> Vec<Rect> {
+    let num_panes = u32::try_from(num_panes).expect("only a few panes");
+    Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Percentage(50), Percentage(50)].as_ref())
-        .split(right_pane);
-    (regions[0], regions[1])
+        .constraints(vec![Ratio(1, num_panes); num_panes as usize])
+        .split(right_pane)
+        .to_vec()
 }
 
 fn content_layout(content_area: Rect) -> (Rect, Rect) {
@@ -167,12 +403,12 @@ fn content_layout(content_area: Rect) -> (Rect, Rect) {
     (regions[0], regions[1])
 }
 
-fn header_background_color(is_marked: bool, focused_pane: FocussedPane) -> Color {
-    match (is_marked, focused_pane) {
-        (false, Mark) => Color::LightRed,
-        (false, _) => COLOR_MARKED,
-        (_, _) => Color::White,
-    }
+fn header_style(is_marked: bool, focused_pane: FocussedPane, theme: &Theme) -> Style {
+    tui_style(match (is_marked, focused_pane) {
+        (false, Mark) => theme.header_danger,
+        (false, _) => theme.header_marked,
+        (_, _) => theme.header,
+    })
 }
 
 fn main_window_layout(area: Rect) -> (Rect, Rect, Rect) {
@@ -183,18 +419,25 @@ fn main_window_layout(area: Rect) -> (Rect, Rect, Rect) {
     (regions[0], regions[1], regions[2])
 }
 
-fn pane_border_style(focused_pane: FocussedPane) -> (Style, Style, Style, Style) {
-    let grey = Style {
-        fg: Color::DarkGray.into(),
-        bg: Color::Reset.into(),
-        add_modifier: Modifier::empty(),
-        ..Style::default()
+fn pane_border_style(
+    focused_pane: FocussedPane,
+    theme: &Theme,
+) -> (Style, Style, Style, Style, Style, Style) {
+    let unfocussed = tui_style(theme.border);
+    let focussed = tui_style(theme.border_focussed);
+    let style = |pane| {
+        if pane == focused_pane {
+            focussed
+        } else {
+            unfocussed
+        }
     };
-    let bold = Style::default().add_modifier(Modifier::BOLD);
-    match focused_pane {
-        Main => (bold, grey, grey, grey),
-        Help => (grey, bold, grey, grey),
-        Mark => (grey, grey, bold, grey),
-        Glob => (grey, grey, grey, bold),
-    }
+    (
+        style(Main),
+        style(Help),
+        style(Mark),
+        style(Glob),
+        style(Jump),
+        style(Treemap),
+    )
 }
@@ -1,14 +1,20 @@
-use crate::interactive::widgets::COUNT;
 use crate::interactive::widgets::tui_ext::{
     List, ListProps, draw_text_nowrap_fn,
     util::{block_width, rect, rect::line_bound},
 };
+use crate::interactive::widgets::{COUNT, compact_chord, navigation_legend};
 use crate::interactive::{
-    CursorDirection, app::tree_view::TreeView, fit_string_graphemes_with_ellipsis,
-    widgets::entry_color,
+    CursorDirection,
+    app::tree_view::TreeView,
+    fit_string_graphemes_with_ellipsis,
+    widgets::{entry_style, tui_style},
+};
+use dua::{
+    ByteFormat,
+    keys::{Action, KeyBindings, Pane},
+    theme::Theme,
+    traverse::{Tree, TreeIndex},
 };
-use crossterm::event::{KeyEvent, KeyEventKind, KeyModifiers};
-use dua::{ByteFormat, traverse::TreeIndex};
 use itertools::Itertools;
 use std::{
     borrow::Borrow,
@@ -18,7 +24,7 @@ use std::{
 use tui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{
         Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
@@ -56,9 +62,11 @@ pub struct MarkPane {
     item_count: u64,
 }
 
-pub struct MarkPaneProps {
+pub struct MarkPaneProps<'a> {
     pub border_style: Style,
     pub format: ByteFormat,
+    pub bindings: &'a KeyBindings,
+    pub theme: &'a Theme,
 }
 
 impl MarkPane {
@@ -92,7 +100,7 @@ impl MarkPane {
                         index: sorting_index,
                         num_errors_during_deletion: 0,
                         is_dir,
-                        entry_count: e.entry_count,
+                        entry_count: e.entry_count.get(),
                     });
                 }
             }
@@ -109,45 +117,53 @@ impl MarkPane {
             Some(self)
         }
     }
+    /// Read the size of all marked entries from `tree` again, after they changed.
+    pub fn update_sizes(&mut self, tree: &Tree) {
+        for (index, mark) in &mut self.marked {
+            if let Some(entry) = tree.node_weight(*index) {
+                mark.size = entry.size;
+            }
+        }
+        (self.total_size, self.item_count) = calculate_size_and_count(&self.marked);
+    }
+    /// Unmark all entries whose path doesn't exist anymore, returning `None` if none are left.
+    pub fn retain_existing_paths(mut self) -> Option<Self> {
+        self.marked
+            .retain(|_, mark| std::fs::symlink_metadata(&mark.path).is_ok());
+        if self.marked.is_empty() {
+            None
+        } else {
+            self.selected = self
+                .selected
+                .map(|selected| selected.min(self.marked.len() - 1));
+            (self.total_size, self.item_count) = calculate_size_and_count(&self.marked);
+            Some(self)
+        }
+    }
     pub fn marked(&self) -> &EntryMarkMap {
         &self.marked
     }
     pub fn into_paths(self) -> impl Iterator<Item = PathBuf> {
         self.marked.into_values().map(|v| v.path)
     }
-    pub fn process_events(mut self, key: KeyEvent) -> Option<(Self, Option<MarkMode>)> {
-        use crossterm::event::KeyCode::{Char, Down, PageDown, PageUp, Up};
-        let action = None;
-        if key.kind == KeyEventKind::Release {
-            return Some((self, action));
-        }
-        match key.code {
-            Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                return Some(self.prepare_deletion(MarkMode::Delete));
-            }
+    pub fn process_events(mut self, action: Action) -> Option<(Self, Option<MarkMode>)> {
+        match action {
+            Action::Delete => return Some(self.prepare_deletion(MarkMode::Delete)),
             #[cfg(feature = "trash-move")]
-            Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                return Some(self.prepare_deletion(MarkMode::Trash));
-            }
-            Char('a') => return None,
-            Char('H') => self.change_selection(CursorDirection::ToTop),
-            Char('G') => self.change_selection(CursorDirection::ToBottom),
-            PageUp => self.change_selection(CursorDirection::PageUp),
-            Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.change_selection(CursorDirection::PageUp);
-            }
-            Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.change_selection(CursorDirection::PageDown);
-            }
-            PageDown => self.change_selection(CursorDirection::PageDown),
-            Char('k') | Up => self.change_selection(CursorDirection::Up),
-            Char('j') | Down => self.change_selection(CursorDirection::Down),
-            Char('x' | 'd' | ' ') => {
-                return self.remove_selected().map(|s| (s, action));
+            Action::Trash => return Some(self.prepare_deletion(MarkMode::Trash)),
+            Action::UnmarkAll => return None,
+            Action::ToTop => self.change_selection(CursorDirection::ToTop),
+            Action::ToBottom => self.change_selection(CursorDirection::ToBottom),
+            Action::PageUp => self.change_selection(CursorDirection::PageUp),
+            Action::PageDown => self.change_selection(CursorDirection::PageDown),
+            Action::Up => self.change_selection(CursorDirection::Up),
+            Action::Down => self.change_selection(CursorDirection::Down),
+            Action::Unmark => {
+                return self.remove_selected().map(|s| (s, None));
             }
             _ => {}
         }
-        Some((self, action))
+        Some((self, None))
     }
 
     pub fn iterate_deletable_items(
@@ -249,10 +265,17 @@ impl MarkPane {
         });
     }
 
-    pub fn render(&mut self, props: impl Borrow<MarkPaneProps>, area: Rect, buf: &mut Buffer) {
+    pub fn render<'a>(
+        &mut self,
+        props: impl Borrow<MarkPaneProps<'a>>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
         let MarkPaneProps {
             border_style,
             format,
+            bindings,
+            theme,
         } = props.borrow();
 
         let marked: &_ = &self.marked;
@@ -267,13 +290,11 @@ impl MarkPane {
             |(idx, v): (usize, &EntryMark)| {
                 let base_style = match selected {
                     Some(selected) if idx == selected => {
-                        let mut modifier = Modifier::REVERSED;
+                        let style = tui_style(theme.selection);
                         if has_focus {
-                            modifier.insert(Modifier::BOLD);
-                        }
-                        Style {
-                            add_modifier: modifier,
-                            ..Default::default()
+                            style.add_modifier(Modifier::BOLD)
+                        } else {
+                            style
                         }
                     }
                     _ => Style::default(),
@@ -302,24 +323,15 @@ impl MarkPane {
                         _ => (path, num_path_graphemes),
                     }
                 };
-                let fg_path = entry_color(None, !v.is_dir, true);
-                let path = Span::styled(
-                    path,
-                    Style {
-                        fg: fg_path,
-                        ..base_style
-                    },
-                );
+                let path_style = base_style.patch(entry_style(theme, !v.is_dir, true));
+                let path = Span::styled(path, path_style);
                 let bytes = Span::styled(
                     format!(
                         "{:>byte_column_width$} ",
                         format.display(v.size).to_string(), // we would have to impl alignment/padding ourselves otherwise...
                         byte_column_width = format.width()
                     ),
-                    Style {
-                        fg: Color::Green.into(),
-                        ..base_style
-                    },
+                    base_style.patch(tui_style(theme.highlight)),
                 );
                 let spacer = Span::styled(
                     format!(
@@ -329,10 +341,7 @@ impl MarkPane {
                             .saturating_sub(path_len)
                             .saturating_sub(format.total_width())
                     ),
-                    Style {
-                        fg: fg_path,
-                        ..base_style
-                    },
+                    path_style,
                 );
                 vec![path, spacer, bytes]
             },
@@ -375,33 +384,26 @@ impl MarkPane {
                 }
             };
 
-            let default_style = Style {
-                fg: Color::Black.into(),
-                bg: Color::Yellow.into(),
-                add_modifier: Modifier::BOLD,
-                sub_modifier: Modifier::empty(),
-                ..Style::default()
+            let first_chord = |action| {
+                bindings
+                    .chords(Pane::Mark, action)
+                    .next()
+                    .map_or_else(|| "-".into(), |chord| chord.to_string())
             };
+            let default_style = tui_style(theme.prompt);
             Paragraph::new(Text::from(Line::from(vec![
                 #[cfg(feature = "trash-move")]
                 Span::styled(
-                    " Ctrl + t ",
-                    Style {
-                        fg: Color::White.into(),
-                        bg: Color::Black.into(),
-                        ..default_style
-                    },
+                    format!(" {} ", first_chord(Action::Trash)),
+                    default_style.patch(tui_style(theme.prompt_key)),
                 ),
                 #[cfg(feature = "trash-move")]
                 Span::styled(" to trash or ", default_style),
                 Span::styled(
-                    " Ctrl + r ",
-                    Style {
-                        fg: Color::LightRed.into(),
-                        bg: Color::Black.into(),
-                        add_modifier: default_style.add_modifier | Modifier::RAPID_BLINK,
-                        ..default_style
-                    },
+                    format!(" {} ", first_chord(Action::Delete)),
+                    default_style
+                        .patch(tui_style(theme.danger))
+                        .add_modifier(Modifier::RAPID_BLINK),
                 ),
                 Span::styled(" to delete without prompt", default_style),
             ])))
@@ -440,8 +442,8 @@ impl MarkPane {
         );
 
         if has_focus {
-            let help_text = " . = o|.. = u ── ⇊ = Ctrl+d|↓ = j|⇈ = Ctrl+u|↑ = k ";
-            let help_text_block_width = block_width(help_text);
+            let help_text = navigation_legend(bindings, Pane::Mark);
+            let help_text_block_width = block_width(&help_text);
             let bound = Rect {
                 width: area.width.saturating_sub(1),
                 ..area
@@ -450,18 +452,25 @@ impl MarkPane {
                 draw_text_nowrap_fn(
                     rect::snap_to_right(bound, help_text_block_width),
                     buf,
-                    help_text,
+                    &help_text,
                     |_, _, _| Style::default(),
                 );
             }
             let bound = line_bound(bound, bound.height.saturating_sub(1) as usize);
-            let help_text = " mark-toggle = space,d | remove-all = a";
-            let help_text_block_width = block_width(help_text);
+            let help_text = format!(
+                " mark-toggle = {} | remove-all = {}",
+                bindings
+                    .chords(Pane::Mark, Action::Unmark)
+                    .map(|chord| chord.to_string().replace(" + ", "+"))
+                    .join(","),
+                compact_chord(bindings, Pane::Mark, Action::UnmarkAll)
+            );
+            let help_text_block_width = block_width(&help_text);
             if help_text_block_width <= bound.width {
                 draw_text_nowrap_fn(
                     rect::snap_to_right(bound, help_text_block_width),
                     buf,
-                    help_text,
+                    &help_text,
                     |_, _, _| Style::default(),
                 );
             }
@@ -1,26 +1,36 @@
+mod bookmarks;
 mod entries;
 mod footer;
 mod glob;
 mod header;
 mod help;
 mod i18n;
+mod jump;
 mod main;
 mod mark;
+mod owners;
+mod preview;
+mod repositories;
+mod treemap;
 mod tui_ext;
 
+pub use bookmarks::*;
 pub use entries::*;
 pub use footer::*;
 pub use glob::*;
 pub use header::*;
 pub use help::*;
 pub use i18n::*;
+pub use jump::*;
 pub use main::*;
 pub use mark::*;
+pub use owners::*;
+pub use preview::*;
+pub use repositories::*;
+pub use treemap::*;
 
-use tui::style::Color;
-
-pub const COLOR_MARKED: Color = Color::Yellow;
-pub const COLOR_MARKED_DARK: Color = Color::Rgb(176, 126, 0);
+use dua::theme::{StyleSpec, Theme, ThemeColor};
+use tui::style::{Color, Modifier, Style};
 
 static COUNT: std::sync::LazyLock<human_format::Formatter> = std::sync::LazyLock::new(|| {
     let mut formatter = human_format::Formatter::new();
@@ -28,11 +38,56 @@ static COUNT: std::sync::LazyLock<human_format::Formatter> = std::sync::LazyLock
     formatter
 });
 
-fn entry_color(fg: Option<Color>, is_file: bool, is_marked: bool) -> Option<Color> {
-    match (is_file, is_marked) {
-        (true, false) => fg,
-        (true, true) => COLOR_MARKED_DARK.into(),
-        (false, true) => COLOR_MARKED.into(),
-        (false, false) => Color::Cyan.into(),
+/// Convert a style of the configured theme into one to render with.
+pub fn tui_style(spec: StyleSpec) -> Style {
+    let mut modifiers = Modifier::empty();
+    for (enabled, modifier) in [
+        (spec.bold, Modifier::BOLD),
+        (spec.dim, Modifier::DIM),
+        (spec.italic, Modifier::ITALIC),
+        (spec.underlined, Modifier::UNDERLINED),
+        (spec.reversed, Modifier::REVERSED),
+        (spec.crossed_out, Modifier::CROSSED_OUT),
+    ] {
+        modifiers.set(modifier, enabled);
+    }
+    Style {
+        fg: spec.fg.map(tui_color),
+        bg: spec.bg.map(tui_color),
+        add_modifier: modifiers,
+        ..Style::default()
     }
 }
+
+fn tui_color(color: ThemeColor) -> Color {
+    match color {
+        ThemeColor::Reset => Color::Reset,
+        ThemeColor::Black => Color::Black,
+        ThemeColor::Red => Color::Red,
+        ThemeColor::Green => Color::Green,
+        ThemeColor::Yellow => Color::Yellow,
+        ThemeColor::Blue => Color::Blue,
+        ThemeColor::Magenta => Color::Magenta,
+        ThemeColor::Cyan => Color::Cyan,
+        ThemeColor::Gray => Color::Gray,
+        ThemeColor::DarkGray => Color::DarkGray,
+        ThemeColor::LightRed => Color::LightRed,
+        ThemeColor::LightGreen => Color::LightGreen,
+        ThemeColor::LightYellow => Color::LightYellow,
+        ThemeColor::LightBlue => Color::LightBlue,
+        ThemeColor::LightMagenta => Color::LightMagenta,
+        ThemeColor::LightCyan => Color::LightCyan,
+        ThemeColor::White => Color::White,
+        ThemeColor::Rgb(r, g, b) => Color::Rgb(r, g, b),
+        ThemeColor::Indexed(index) => Color::Indexed(index),
+    }
+}
+
+fn entry_style(theme: &Theme, is_file: bool, is_marked: bool) -> Style {
+    tui_style(match (is_file, is_marked) {
+        (true, false) => theme.file,
+        (true, true) => theme.marked_file,
+        (false, true) => theme.marked,
+        (false, false) => theme.directory,
+    })
+}
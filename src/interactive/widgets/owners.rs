@@ -0,0 +1,131 @@
+use crate::interactive::widgets::{COUNT, tui_style};
+use dua::{
+    ByteFormat,
+    ownership::{OwnerUsage, Ownerships},
+    theme::Theme,
+    traverse::{Traversal, TreeIndex},
+};
+use std::borrow::Borrow;
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+/// Shows how many bytes and entries each user owns below the current directory.
+#[derive(Default)]
+pub struct OwnersPane {
+    /// The directory along with its size and entry count the usage was computed for.
+    computed_for: Option<(TreeIndex, u128, Option<u64>)>,
+    /// The usage of each owner, largest first.
+    usage: Vec<OwnerUsage>,
+}
+
+pub struct OwnersPaneProps<'a> {
+    pub border_style: Style,
+    pub format: ByteFormat,
+    pub ownerships: &'a Ownerships,
+    pub theme: &'a Theme,
+}
+
+impl OwnersPane {
+    /// Recompute the usage below `view_root` if it changed since the last call.
+    pub fn update(&mut self, traversal: &Traversal, view_root: TreeIndex) {
+        let Some(entry) = traversal.tree.node_weight(view_root) else {
+            return;
+        };
+        let key = (view_root, entry.size, entry.entry_count.get());
+        if self.computed_for == Some(key) {
+            return;
+        }
+        self.usage = traversal.usage_by_owner(view_root).into_sorted_by_size();
+        self.usage.reverse();
+        self.computed_for = Some(key);
+    }
+
+    /// The usage of each owner, largest first.
+    #[cfg(test)]
+    pub fn usage(&self) -> &[OwnerUsage] {
+        &self.usage
+    }
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "percentages and counts are only displayed approximately"
+    )]
+    pub fn render<'a>(
+        &self,
+        props: impl Borrow<OwnersPaneProps<'a>>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let OwnersPaneProps {
+            border_style,
+            format,
+            ownerships,
+            theme,
+        } = props.borrow();
+
+        let total: u128 = self.usage.iter().map(|owner| owner.bytes).sum();
+        let block = Block::default()
+            .title(format!(
+                "Owners below current directory ({}) ",
+                format.display(total)
+            ))
+            .border_style(*border_style)
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let height = usize::from(inner_area.height);
+        let (shown, omitted) = if self.usage.len() > height {
+            self.usage.split_at(height.saturating_sub(1))
+        } else {
+            (self.usage.as_slice(), &[][..])
+        };
+        let mut lines: Vec<_> = shown
+            .iter()
+            .map(|owner| {
+                let name = owner.uid.map_or_else(
+                    || "<unknown>".to_string(),
+                    |uid| {
+                        ownerships
+                            .user_name(uid)
+                            .map_or_else(|| uid.to_string(), ToOwned::to_owned)
+                    },
+                );
+                let percentage = if total == 0 {
+                    0.0
+                } else {
+                    owner.bytes as f64 / total as f64 * 100.0
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "{:>width$} ",
+                            format.display(owner.bytes).to_string(),
+                            width = format.width()
+                        ),
+                        tui_style(theme.highlight),
+                    ),
+                    Span::raw(format!(
+                        "{percentage:>5.1}% {:>6} entries  {name}",
+                        COUNT.format(owner.entries as f64)
+                    )),
+                ])
+            })
+            .collect();
+        if !omitted.is_empty() {
+            let bytes: u128 = omitted.iter().map(|owner| owner.bytes).sum();
+            lines.push(Line::from(format!(
+                "{:>width$}  …and {} more owners",
+                format.display(bytes).to_string(),
+                omitted.len(),
+                width = format.width()
+            )));
+        }
+        Paragraph::new(Text::from(lines)).render(inner_area, buf);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::interactive::{path_of, widgets::tui_style};
+use chrono::DateTime;
+use dua::{
+    ByteFormat,
+    theme::Theme,
+    traverse::{Traversal, Tree, TreeIndex},
+};
+use petgraph::Direction;
+use std::{
+    borrow::Borrow,
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+/// How many of the largest files below a directory are shown.
+const LARGEST_FILES: usize = 10;
+/// How much of a file is read to preview it as text.
+const TEXT_PREVIEW_BYTES: u64 = 16 * 1024;
+
+/// Shows the metadata of the selected entry, along with the first lines of text files and the
+/// largest files below directories.
+#[derive(Default)]
+pub struct PreviewPane {
+    /// The entry along with its size and whether it was the apparent size the preview was
+    /// computed for.
+    computed_for: Option<(TreeIndex, u128, bool)>,
+    preview: Option<Preview>,
+}
+
+pub struct PreviewPaneProps<'a> {
+    pub border_style: Style,
+    pub format: ByteFormat,
+    pub theme: &'a Theme,
+}
+
+struct Preview {
+    path: PathBuf,
+    apparent_size: u128,
+    disk_usage: u128,
+    entry_count: Option<u64>,
+    metadata: Result<fs::Metadata, String>,
+    content: Content,
+}
+
+enum Content {
+    None,
+    Text(Vec<String>),
+    Binary,
+    /// A file that isn't read as it's not a regular one, described by its kind.
+    Special(&'static str),
+    Unreadable(String),
+    LargestFiles(Vec<(PathBuf, u128)>),
+}
+
+impl PreviewPane {
+    /// Preview `selected` if it changed since the last call, with `glob_root` being the root of
+    /// the search results, if they are shown.
+    pub fn update(
+        &mut self,
+        traversal: &Traversal,
+        selected: Option<TreeIndex>,
+        glob_root: Option<TreeIndex>,
+        apparent_size: bool,
+    ) {
+        let Some((selected, entry)) =
+            selected.and_then(|index| Some((index, traversal.tree.node_weight(index)?)))
+        else {
+            self.computed_for = None;
+            self.preview = None;
+            return;
+        };
+        let key = (selected, entry.size, apparent_size);
+        if self.computed_for == Some(key) {
+            return;
+        }
+
+        let path = path_of(&traversal.tree, selected, glob_root);
+        let (apparent, disk) = if apparent_size {
+            (entry.size, u128::from(entry.alternate_size))
+        } else {
+            (u128::from(entry.alternate_size), entry.size)
+        };
+        let content = if entry.is_dir {
+            let dir = path.as_path();
+            Content::LargestFiles(
+                largest_files(&traversal.tree, selected, LARGEST_FILES)
+                    .into_iter()
+                    .map(|(index, size)| {
+                        let path = path_of(&traversal.tree, index, glob_root);
+                        let path = path.strip_prefix(dir).map_or(path.clone(), Path::to_owned);
+                        (path, size)
+                    })
+                    .collect(),
+            )
+        } else if entry.is_small_files {
+            Content::None
+        } else {
+            file_content(&path)
+        };
+        self.preview = Some(Preview {
+            metadata: fs::symlink_metadata(&path).map_err(|err| err.to_string()),
+            path,
+            apparent_size: apparent,
+            disk_usage: disk,
+            entry_count: entry.entry_count.get(),
+            content,
+        });
+        self.computed_for = Some(key);
+    }
+
+    /// The path of the previewed entry.
+    #[cfg(test)]
+    pub fn path(&self) -> Option<&Path> {
+        self.preview.as_ref().map(|preview| preview.path.as_path())
+    }
+
+    /// The paths of the largest files below the previewed directory, relative to it.
+    #[cfg(test)]
+    pub fn largest_files(&self) -> Vec<&Path> {
+        match self.preview.as_ref().map(|preview| &preview.content) {
+            Some(Content::LargestFiles(files)) => {
+                files.iter().map(|(path, _)| path.as_path()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn render<'a>(
+        &self,
+        props: impl Borrow<PreviewPaneProps<'a>>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let PreviewPaneProps {
+            border_style,
+            format,
+            theme,
+        } = props.borrow();
+
+        let block = Block::default()
+            .title("Preview ")
+            .border_style(*border_style)
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let Some(preview) = self.preview.as_ref() else {
+            Paragraph::new("Select an entry to preview it.").render(inner_area, buf);
+            return;
+        };
+        let label = tui_style(theme.highlight);
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{name:<13}"), label),
+                Span::raw(value),
+            ])
+        };
+        let mut lines = vec![
+            field("Path", preview.path.display().to_string()),
+            field(
+                "Apparent size",
+                format.display(preview.apparent_size).to_string(),
+            ),
+            field("Disk usage", format.display(preview.disk_usage).to_string()),
+        ];
+        if let Some(count) = preview.entry_count {
+            lines.push(field("Entries", count.to_string()));
+        }
+        match &preview.metadata {
+            Ok(metadata) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::MetadataExt;
+                    lines.push(field("Inode", metadata.ino().to_string()));
+                    lines.push(field("Links", metadata.nlink().to_string()));
+                }
+                for (name, time) in [
+                    ("Modified", metadata.modified()),
+                    ("Accessed", metadata.accessed()),
+                    ("Created", metadata.created()),
+                ] {
+                    if let Ok(time) = time {
+                        lines.push(field(name, timestamp(time)));
+                    }
+                }
+            }
+            Err(err) => lines.push(field("Metadata", err.clone())),
+        }
+
+        lines.push(Line::from(""));
+        match &preview.content {
+            Content::None => {}
+            Content::Text(text) => lines.extend(text.iter().map(|line| Line::from(line.as_str()))),
+            Content::Binary => lines.push(Line::from("Binary file")),
+            Content::Special(kind) => lines.push(Line::from(*kind)),
+            Content::Unreadable(err) => lines.push(Line::from(format!("Could not read: {err}"))),
+            Content::LargestFiles(files) if files.is_empty() => {
+                lines.push(Line::from("There are no files below this directory."));
+            }
+            Content::LargestFiles(files) => {
+                lines.push(Line::styled("Largest files", label));
+                lines.extend(files.iter().map(|(path, size)| {
+                    Line::from(format!(
+                        "{:>width$}  {}",
+                        format.display(*size).to_string(),
+                        path.display(),
+                        width = format.width()
+                    ))
+                }));
+            }
+        }
+        Paragraph::new(Text::from(lines)).render(inner_area, buf);
+    }
+}
+
+fn timestamp(time: SystemTime) -> String {
+    DateTime::<chrono::Utc>::from(time)
+        .format("%d/%m/%Y %H:%M:%S UTC")
+        .to_string()
+}
+
+/// Return the `count` largest files below `dir` along with their size, largest first.
+pub fn largest_files(tree: &Tree, dir: TreeIndex, count: usize) -> Vec<(TreeIndex, u128)> {
+    let mut largest = BinaryHeap::with_capacity(count + 1);
+    let mut dirs = vec![dir];
+    while let Some(dir) = dirs.pop() {
+        for index in tree.neighbors_directed(dir, Direction::Outgoing) {
+            let entry = &tree[index];
+            if entry.is_dir {
+                dirs.push(index);
+            } else if !entry.is_small_files {
+                largest.push(Reverse((entry.size, index)));
+                if largest.len() > count {
+                    largest.pop();
+                }
+            }
+        }
+    }
+    largest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((size, index))| (index, size))
+        .collect()
+}
+
+/// Return what to show of the file at `path`, reading only regular files as opening others, like
+/// FIFOs or devices, may block.
+fn file_content(path: &Path) -> Content {
+    let file_type = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata.file_type(),
+        Err(err) => return Content::Unreadable(err.to_string()),
+    };
+    if file_type.is_symlink() {
+        Content::Special("Symlink")
+    } else if !file_type.is_file() {
+        Content::Special("Special file")
+    } else {
+        match read_text(path) {
+            Ok(Some(lines)) => Content::Text(lines),
+            Ok(None) => Content::Binary,
+            Err(err) => Content::Unreadable(err.to_string()),
+        }
+    }
+}
+
+/// Return the first lines of the file at `path`, or `None` if it doesn't look like text.
+fn read_text(path: &Path) -> std::io::Result<Option<Vec<String>>> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)?
+        .take(TEXT_PREVIEW_BYTES)
+        .read_to_end(&mut bytes)?;
+    Ok(text_lines(&bytes))
+}
+
+/// Return the lines of `bytes`, or `None` if they contain a NUL byte as binary files do.
+/// A line cut off at the end is kept.
+fn text_lines(bytes: &[u8]) -> Option<Vec<String>> {
+    if bytes.contains(&0) {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .map(|line| line.replace('\t', "    "))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interactive::app::tests::utils::add_entry;
+
+    #[test]
+    fn the_largest_files_are_found_in_all_directories_below() {
+        let mut tree = Tree::new();
+        let root = add_entry(&mut tree, None, "", 0, true);
+        let small = add_entry(&mut tree, Some(root), "small", 1, false);
+        let sub = add_entry(&mut tree, Some(root), "sub", 0, true);
+        let large = add_entry(&mut tree, Some(sub), "large", 30, false);
+        let medium = add_entry(&mut tree, Some(root), "medium", 20, false);
+        let nested = add_entry(&mut tree, Some(sub), "nested", 0, true);
+        let larger = add_entry(&mut tree, Some(nested), "larger", 25, false);
+
+        assert_eq!(
+            largest_files(&tree, root, 3),
+            [(large, 30), (larger, 25), (medium, 20)],
+            "directories are only searched, and the smallest files are left out"
+        );
+        assert_eq!(largest_files(&tree, nested, 10), [(larger, 25)]);
+        assert_eq!(largest_files(&tree, small, 10), []);
+    }
+
+    #[test]
+    fn only_files_without_nul_bytes_are_shown_as_text() {
+        assert_eq!(
+            text_lines(b"first\n\tsecond\nthi"),
+            Some(vec!["first".into(), "    second".into(), "thi".into()])
+        );
+        assert_eq!(text_lines(b"\x7fELF\0\x01"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn only_regular_files_are_read() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("file");
+        fs::write(&file, "text")?;
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&file, &link)?;
+        let socket = dir.path().join("socket");
+        let _listener = std::os::unix::net::UnixListener::bind(&socket)?;
+
+        assert!(matches!(file_content(&file), Content::Text(lines) if lines == ["text"]));
+        assert!(matches!(file_content(&link), Content::Special("Symlink")));
+        assert!(matches!(
+            file_content(&socket),
+            Content::Special("Special file")
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::interactive::{
+    gitignore::{RepositoryUsage, usage_by_repository},
+    widgets::tui_style,
+};
+use dua::{
+    ByteFormat,
+    theme::Theme,
+    traverse::{Traversal, TreeIndex},
+};
+use std::borrow::Borrow;
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+/// Shows how many bytes of each git repository below the scanned roots are tracked, untracked,
+/// ignored or precious.
+#[derive(Default)]
+pub struct RepositoriesPane {
+    /// The size and entry count of the root the usage was computed for.
+    computed_for: Option<(u128, Option<u64>)>,
+    /// The usage of each repository, largest ignored bytes first.
+    usage: Vec<RepositoryUsage>,
+}
+
+pub struct RepositoriesPaneProps<'a> {
+    pub border_style: Style,
+    pub format: ByteFormat,
+    pub theme: &'a Theme,
+}
+
+impl RepositoriesPane {
+    /// Recompute the usage of all repositories below `root` if it changed since the last call.
+    pub fn update(&mut self, traversal: &Traversal, root: TreeIndex) {
+        let Some(entry) = traversal.tree.node_weight(root) else {
+            return;
+        };
+        let key = (entry.size, entry.entry_count.get());
+        if self.computed_for == Some(key) {
+            return;
+        }
+        self.usage = usage_by_repository(&traversal.tree, root);
+        self.computed_for = Some(key);
+    }
+
+    /// The usage of each repository, largest ignored bytes first.
+    #[cfg(test)]
+    pub fn usage(&self) -> &[RepositoryUsage] {
+        &self.usage
+    }
+
+    pub fn render<'a>(
+        &self,
+        props: impl Borrow<RepositoriesPaneProps<'a>>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let RepositoriesPaneProps {
+            border_style,
+            format,
+            theme,
+        } = props.borrow();
+
+        let ignored: u128 = self.usage.iter().map(|usage| usage.ignored).sum();
+        let block = Block::default()
+            .title(format!(
+                "Git repositories ({} ignored) ",
+                format.display(ignored)
+            ))
+            .border_style(*border_style)
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        if self.computed_for.is_none() {
+            Paragraph::new("Waiting for the scan to finish.").render(inner_area, buf);
+            return;
+        }
+        if self.usage.is_empty() {
+            Paragraph::new("There are no git repositories below the scanned directories.")
+                .render(inner_area, buf);
+            return;
+        }
+        let column = |bytes: u128| {
+            format!(
+                "{:>width$} ",
+                format.display(bytes).to_string(),
+                width = format.width()
+            )
+        };
+        let heading = |name: &str| format!("{name:>width$} ", width = format.width());
+        let label = tui_style(theme.highlight);
+        let mut lines = vec![Line::styled(
+            ["tracked", "untracked", "ignored", "precious"]
+                .map(heading)
+                .concat()
+                + " repository",
+            label,
+        )];
+        lines.extend(self.usage.iter().map(|usage| {
+            Line::from(vec![
+                Span::raw(column(usage.tracked)),
+                Span::raw(column(usage.untracked)),
+                Span::styled(column(usage.ignored), label),
+                Span::raw(column(usage.precious)),
+                Span::raw(format!(" {}", usage.path.display())),
+            ])
+        }));
+        Paragraph::new(Text::from(lines)).render(inner_area, buf);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::interactive::{
+    EntryDataBundle,
+    widgets::{entry_style, tui_style},
+};
+use dua::{ByteFormat, theme::Theme, traverse::TreeIndex};
+use std::{borrow::Borrow, cmp::Reverse};
+use tui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+/// Terminal cells are about twice as high as they are wide, which is accounted for to make
+/// rectangles of equal proportions look square.
+const CELL_ASPECT_RATIO: f64 = 2.0;
+
+/// Shows the entries of the current directory as rectangles whose areas are proportional to
+/// their sizes, highlighting the selected one.
+#[derive(Default)]
+pub struct TreemapPane {
+    /// The rectangles of the entries as last drawn.
+    rects: Vec<(TreeIndex, Rect)>,
+    /// The entry whose rectangle was highlighted when last drawn.
+    highlighted: Option<TreeIndex>,
+}
+
+/// The side of the highlighted rectangle to move the selection to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreemapDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+pub struct TreemapPaneProps<'a> {
+    pub border_style: Style,
+    pub format: ByteFormat,
+    pub entries: &'a [EntryDataBundle],
+    pub selected: Option<TreeIndex>,
+    pub theme: &'a Theme,
+}
+
+impl TreemapPane {
+    /// Return the rectangle of the entry at `index` as last drawn, if it was visible.
+    pub fn rect_of(&self, index: TreeIndex) -> Option<Rect> {
+        self.rects
+            .iter()
+            .find(|(entry, _)| *entry == index)
+            .map(|(_, rect)| *rect)
+    }
+
+    /// Return the entry drawn at `column` and `row`, if any.
+    pub fn entry_at(&self, column: u16, row: u16) -> Option<TreeIndex> {
+        self.rects
+            .iter()
+            .find(|(_, rect)| rect.contains(Position::new(column, row)))
+            .map(|(index, _)| *index)
+    }
+
+    /// Return the entry whose rectangle is closest to the highlighted one in `direction`, or
+    /// the first entry if nothing is highlighted.
+    pub fn neighbour(&self, direction: TreemapDirection) -> Option<TreeIndex> {
+        use TreemapDirection::{Down, Left, Right, Up};
+        let Some(from) = self.highlighted.and_then(|index| self.rect_of(index)) else {
+            return self.rects.first().map(|(index, _)| *index);
+        };
+        let overlap = |start: u16, end: u16, other_start: u16, other_end: u16| {
+            end.min(other_end).saturating_sub(start.max(other_start))
+        };
+        self.rects
+            .iter()
+            .filter_map(|&(index, rect)| {
+                let (gap, overlap) = match direction {
+                    Up => (
+                        from.top().checked_sub(rect.bottom())?,
+                        overlap(from.left(), from.right(), rect.left(), rect.right()),
+                    ),
+                    Down => (
+                        rect.top().checked_sub(from.bottom())?,
+                        overlap(from.left(), from.right(), rect.left(), rect.right()),
+                    ),
+                    Left => (
+                        from.left().checked_sub(rect.right())?,
+                        overlap(from.top(), from.bottom(), rect.top(), rect.bottom()),
+                    ),
+                    Right => (
+                        rect.left().checked_sub(from.right())?,
+                        overlap(from.top(), from.bottom(), rect.top(), rect.bottom()),
+                    ),
+                };
+                Some(((overlap == 0, gap, Reverse(overlap)), index))
+            })
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, index)| index)
+    }
+
+    pub fn render<'a>(
+        &mut self,
+        props: impl Borrow<TreemapPaneProps<'a>>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let TreemapPaneProps {
+            border_style,
+            format,
+            entries,
+            selected,
+            theme,
+        } = props.borrow();
+
+        // In tree mode, only the entries of the current directory are laid out, and nested
+        // entries highlight the directory they are in.
+        let selected = selected.and_then(|selected| {
+            let position = entries.iter().position(|entry| entry.index == selected)?;
+            entries[..=position]
+                .iter()
+                .rev()
+                .find(|entry| entry.depth == 0)
+                .map(|entry| entry.index)
+        });
+        let entries: Vec<_> = entries.iter().filter(|entry| entry.depth == 0).collect();
+
+        let total: u128 = entries.iter().map(|entry| entry.size).sum();
+        let block = Block::default()
+            .title(format!("Treemap ({}) ", format.display(total)))
+            .border_style(*border_style)
+            .borders(Borders::ALL);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let sizes: Vec<_> = entries.iter().map(|entry| entry.size).collect();
+        self.highlighted = selected;
+        self.rects.clear();
+        for (entry, rect) in entries.iter().zip(squarify(&sizes, inner_area)) {
+            if rect.is_empty() {
+                continue;
+            }
+            self.rects.push((entry.index, rect));
+            let mut style = entry_style(theme, !entry.is_dir, false);
+            if Some(entry.index) == selected {
+                style = style
+                    .patch(tui_style(theme.selection))
+                    .add_modifier(Modifier::BOLD);
+            }
+            if rect.width < 3 || rect.height < 2 {
+                buf.set_style(rect, style);
+                for position in rect.positions() {
+                    buf[position].set_symbol("░");
+                }
+                continue;
+            }
+            let block = Block::default()
+                .title(entry.name.to_string_lossy().into_owned())
+                .border_style(style)
+                .title_style(style)
+                .borders(Borders::ALL);
+            let inner = block.inner(rect);
+            block.render(rect, buf);
+            Paragraph::new(Line::from(format.display(entry.size).to_string()))
+                .style(style)
+                .render(inner, buf);
+        }
+    }
+}
+
+/// Lay out rectangles with areas proportional to `sizes` within `area` so that they are as
+/// square as possible, returning them in the order of `sizes`.
+///
+/// Empty entries, and those too small to cover a cell, get an empty rectangle.
+#[expect(
+    clippy::cast_precision_loss,
+    reason = "sizes only need to be roughly proportional"
+)]
+pub fn squarify(sizes: &[u128], area: Rect) -> Vec<Rect> {
+    let mut out = vec![Rect::default(); sizes.len()];
+    let mut order: Vec<_> = (0..sizes.len()).filter(|&idx| sizes[idx] > 0).collect();
+    if order.is_empty() || area.is_empty() {
+        return out;
+    }
+    order.sort_by(|&lhs, &rhs| sizes[rhs].cmp(&sizes[lhs]));
+
+    let mut free = Area {
+        x: 0.0,
+        y: 0.0,
+        width: f64::from(area.width),
+        height: f64::from(area.height) * CELL_ASPECT_RATIO,
+    };
+    let total: f64 = order.iter().map(|&idx| sizes[idx] as f64).sum();
+    let scale = free.width * free.height / total;
+    let areas: Vec<_> = order.iter().map(|&idx| sizes[idx] as f64 * scale).collect();
+
+    let mut start = 0;
+    while start < areas.len() {
+        let side = free.width.min(free.height);
+        let mut end = start + 1;
+        while end < areas.len()
+            && worst_aspect_ratio(&areas[start..=end], side)
+                <= worst_aspect_ratio(&areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let row_area: f64 = row.iter().sum();
+        if free.width >= free.height {
+            let width = row_area / free.height;
+            let mut y = free.y;
+            for (&entry_area, &idx) in row.iter().zip(&order[start..end]) {
+                let height = entry_area / width;
+                out[idx] = to_cells(free.x, y, width, height, area);
+                y += height;
+            }
+            free.x += width;
+            free.width -= width;
+        } else {
+            let height = row_area / free.width;
+            let mut x = free.x;
+            for (&entry_area, &idx) in row.iter().zip(&order[start..end]) {
+                let width = entry_area / height;
+                out[idx] = to_cells(x, free.y, width, height, area);
+                x += width;
+            }
+            free.y += height;
+            free.height -= height;
+        }
+        start = end;
+    }
+    out
+}
+
+/// A rectangle in cell widths, relative to the area to lay out in.
+struct Area {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Return the largest ratio of the longer to the shorter side among the rectangles of a row with
+/// `areas`, laid out along a side of length `side`.
+fn worst_aspect_ratio(areas: &[f64], side: f64) -> f64 {
+    let sum: f64 = areas.iter().sum();
+    let (min, max) = areas.iter().fold((f64::MAX, 0.0_f64), |(min, max), &area| {
+        (min.min(area), max.max(area))
+    });
+    let (side, sum) = (side * side, sum * sum);
+    (side * max / sum).max(sum / (side * min))
+}
+
+/// Snap a rectangle to the cells of `area`, rounding its edges so that neighbours don't overlap
+/// or leave gaps.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "edges are rounded and within the bounds of `area`"
+)]
+fn to_cells(x: f64, y: f64, width: f64, height: f64, area: Rect) -> Rect {
+    let (y, height) = (y / CELL_ASPECT_RATIO, height / CELL_ASPECT_RATIO);
+    let edge = |value: f64, max: u16| value.round().clamp(0.0, f64::from(max)) as u16;
+    let (left, right) = (edge(x, area.width), edge(x + width, area.width));
+    let (top, bottom) = (edge(y, area.height), edge(y + height, area.height));
+    Rect {
+        x: area.x + left,
+        y: area.y + top,
+        width: right - left,
+        height: bottom - top,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rectangles_cover_the_area_without_overlapping() {
+        let area = Rect::new(3, 2, 60, 20);
+        let sizes = [0, 600, 100, 300, 200, 1, 400];
+        let rects = squarify(&sizes, area);
+        assert_eq!(
+            rects.len(),
+            sizes.len(),
+            "one rectangle per entry, in order"
+        );
+        assert!(rects[0].is_empty(), "empty entries aren't shown");
+
+        for (idx, rect) in rects.iter().enumerate() {
+            assert!(
+                rect.is_empty() || rect.intersection(area) == *rect,
+                "{rect:?} lies within the area"
+            );
+            for other in &rects[idx + 1..] {
+                assert!(
+                    rect.is_empty() || other.is_empty() || !rect.intersects(*other),
+                    "{rect:?} and {other:?} don't overlap"
+                );
+            }
+        }
+        let covered: u32 = rects.iter().map(|rect| rect.area()).sum();
+        assert_eq!(covered, area.area(), "there are no gaps");
+
+        let cells = |idx: usize| i64::from(rects[idx].area());
+        assert!(cells(1) > cells(6) && cells(6) > cells(3) && cells(3) > cells(4));
+        assert!(
+            (cells(1) - 3 * cells(4)).abs() <= cells(4) / 2,
+            "areas are roughly proportional to the sizes: {rects:?}"
+        );
+    }
+
+    #[test]
+    fn equally_sized_entries_are_about_square() {
+        let rects = squarify(&[1, 1, 1, 1], Rect::new(0, 0, 40, 20));
+        for rect in rects {
+            assert_eq!((rect.width, rect.height), (20, 10), "{rect:?}");
+        }
+    }
+
+    #[test]
+    fn nothing_is_laid_out_without_space_or_sizes() {
+        assert!(
+            squarify(&[1, 2], Rect::new(0, 0, 0, 10))
+                .into_iter()
+                .all(Rect::is_empty)
+        );
+        assert!(
+            squarify(&[0, 0], Rect::new(0, 0, 10, 10))
+                .into_iter()
+                .all(Rect::is_empty)
+        );
+    }
+}
@@ -7,7 +7,7 @@
 mod aggregate;
 mod common;
 mod config;
-pub use config::Config;
+pub use config::{CleanupRule, Config, keys, theme};
 mod crossdev;
 mod inodefilter;
 pub(crate) use dua_core as walk;
@@ -15,6 +15,12 @@ pub(crate) use dua_core as walk;
 /// Filesystem traversal, in-memory tree representation, and traversal events.
 pub mod traverse;
 
-pub use aggregate::aggregate;
+/// Named paths to jump to in interactive mode, stored on disk for each set of roots.
+pub mod bookmarks;
+
+/// Owners, groups and permissions of traversed entries, stored once per distinct combination.
+pub mod ownership;
+
+pub use aggregate::{aggregate, aggregate_by_owner};
 pub use common::*;
 pub(crate) use inodefilter::InodeFilter;
@@ -10,6 +10,8 @@ use std::{
     process,
 };
 
+#[cfg(feature = "tui-crossplatform")]
+use crate::interactive::command::TerminalModes;
 #[cfg(feature = "tui-crossplatform")]
 use crate::interactive::input::{input_channel, input_channel_from_chars};
 #[cfg(feature = "tui-crossplatform")]
@@ -20,12 +22,15 @@ use crossterm::{
     terminal::{EnterAlternateScreen, enable_raw_mode},
 };
 #[cfg(feature = "tui-crossplatform")]
+use dua::bookmarks::Bookmarks;
+#[cfg(feature = "tui-crossplatform")]
 use tui::{Terminal, backend::CrosstermBackend};
 
 mod crossdev;
 #[cfg(feature = "tui-crossplatform")]
 mod interactive;
 mod options;
+mod shell_integration;
 
 fn stderr_if_tty() -> Option<io::Stderr> {
     let stderr = io::stderr();
@@ -37,10 +42,15 @@ fn stderr_if_tty() -> Option<io::Stderr> {
 }
 
 #[cfg(feature = "tui-crossplatform")]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag tracks an independent terminal mode to restore"
+)]
 struct InteractiveTerminalGuard {
     raw_mode: bool,
     alternate_screen: bool,
     focus_change: bool,
+    mouse_capture: bool,
 }
 
 #[cfg(feature = "tui-crossplatform")]
@@ -49,6 +59,9 @@ impl Drop for InteractiveTerminalGuard {
         if self.focus_change {
             crossterm::execute!(io::stderr(), crossterm::event::DisableFocusChange).ok();
         }
+        if self.mouse_capture {
+            crossterm::execute!(io::stderr(), crossterm::event::DisableMouseCapture).ok();
+        }
         crossterm::execute!(io::stderr(), crossterm::cursor::Show).ok();
         if self.raw_mode {
             crossterm::terminal::disable_raw_mode().ok();
@@ -105,12 +118,29 @@ fn main() -> Result<()> {
             traversal: subcommand_traversal,
             no_entry_check,
             once,
+            collapse_files_below,
+            cwd_file,
         }) => {
             let traversal = merge_traversal_args(&global_traversal, &subcommand_traversal);
             let config = dua::Config::load()?;
             let enable_focus_change = config.notifications.any_enabled();
+            let enable_mouse_capture = config.mouse == Some(true);
             let byte_format = traversal.byte_format(&config);
-            let walk_options = walk_options_from(&traversal)?;
+            let walk_options = dua::WalkOptions {
+                collapse_files_below,
+                ..walk_options_from(&traversal)?
+            };
+            // Bookmarks are stored for the roots as given, before the working directory changes.
+            // A bookmarks file that can't be read leaves us with bookmarks that are only kept in
+            // memory, so it isn't overwritten.
+            let bookmarks = Bookmarks::path()
+                .ok()
+                .map(|path| Bookmarks::load(path, &traversal.input))
+                .transpose()
+                .unwrap_or_else(|err| {
+                    log::warn!("Bookmarks are kept in memory only: {err:#}");
+                    None
+                });
             let input_paths = extract_paths_maybe_set_cwd(traversal.input, &walk_options)?;
 
             let no_tty_msg = "Interactive mode requires a connected terminal";
@@ -124,6 +154,7 @@ fn main() -> Result<()> {
                     raw_mode: false,
                     alternate_screen: false,
                     focus_change: false,
+                    mouse_capture: false,
                 }
             } else {
                 enable_raw_mode().with_context(|| no_tty_msg)?;
@@ -132,10 +163,15 @@ fn main() -> Result<()> {
                     execute!(stderr, crossterm::event::EnableFocusChange)
                         .with_context(|| no_tty_msg)?;
                 }
+                if enable_mouse_capture {
+                    execute!(stderr, crossterm::event::EnableMouseCapture)
+                        .with_context(|| no_tty_msg)?;
+                }
                 InteractiveTerminalGuard {
                     raw_mode: true,
                     alternate_screen: true,
                     focus_change: enable_focus_change,
+                    mouse_capture: enable_mouse_capture,
                 }
             };
             let stderr = io::BufWriter::new(stderr);
@@ -150,6 +186,15 @@ fn main() -> Result<()> {
                 input_paths,
                 config,
             )?;
+            if let Some(bookmarks) = bookmarks {
+                app.state.bookmarks = bookmarks;
+            }
+            if terminal_guard.alternate_screen {
+                app.state.terminal_modes = Some(TerminalModes {
+                    mouse_capture: terminal_guard.mouse_capture,
+                    focus_change: terminal_guard.focus_change,
+                });
+            }
             app.traverse()?;
 
             let res = match once {
@@ -158,11 +203,23 @@ fn main() -> Result<()> {
                 }
                 None => app.process_events(
                     &mut terminal,
-                    input_channel(app.state.terminal_focus.clone()),
+                    input_channel(
+                        app.state.terminal_focus.clone(),
+                        app.state.input_pause.clone(),
+                    ),
                 ),
             };
 
-            let res = res.map(|r| (r, app.window.mark.take().map(|pane| pane.into_paths())));
+            let res = res.map(|r| {
+                (
+                    r,
+                    app.window
+                        .mark
+                        .take()
+                        .map(|pane| pane.into_paths().collect()),
+                    app.state.quit_directory.take(),
+                )
+            });
             // Leak app memory to avoid having to wait for the hashmap to deallocate,
             // which causes a noticeable delay shortly before the the program exits anyway.
             std::mem::forget(app);
@@ -173,11 +230,17 @@ fn main() -> Result<()> {
 
             // Exit 'quickly' to avoid having to not have to deal with slightly different types in the other match branches
             let exit_code = match res {
-                Ok((walk_result, paths)) => {
-                    if let Some(paths) = paths {
-                        for path in paths {
-                            println!("{}", path.display());
+                Ok((walk_result, paths, quit_directory)) => {
+                    let paths: Option<Vec<_>> = match (quit_directory, &cwd_file) {
+                        (Some(directory), Some(cwd_file)) => {
+                            write_cwd_file(cwd_file, &directory)?;
+                            paths
                         }
+                        (Some(directory), None) => Some(vec![directory]),
+                        (None, _) => paths,
+                    };
+                    for path in paths.into_iter().flatten() {
+                        println!("{}", path.display());
                     }
                     walk_result.to_exit_code()
                 }
@@ -193,6 +256,7 @@ fn main() -> Result<()> {
             no_total,
             no_sort,
             statistics,
+            by_owner,
         }) => {
             let traversal = merge_traversal_args(&global_traversal, &subcommand_traversal);
             let config = dua::Config::load()?;
@@ -201,7 +265,12 @@ fn main() -> Result<()> {
             let input_paths = extract_paths_maybe_set_cwd(traversal.input, &walk_options)?;
             let stdout = io::stdout();
             let stdout_locked = stdout.lock();
-            let (res, stats) = dua::aggregate(
+            let aggregate = if by_owner {
+                dua::aggregate_by_owner
+            } else {
+                dua::aggregate
+            };
+            let (res, stats) = aggregate(
                 stdout_locked,
                 stderr_if_tty(),
                 walk_options,
@@ -215,7 +284,14 @@ fn main() -> Result<()> {
             }
             res
         }
-        Some(Completions { shell }) => {
+        Some(Completions {
+            shell,
+            shell_integration: true,
+        }) => {
+            print!("{}", shell_integration::snippet(shell)?);
+            return Ok(());
+        }
+        Some(Completions { shell, .. }) => {
             let mut cmd = options::Args::command();
             let dua = cmd.get_name().to_string();
             clap_complete::generate(shell, &mut cmd, dua, &mut io::stdout());
@@ -277,6 +353,8 @@ fn merge_traversal_args(
         apparent_size: global.apparent_size || subcommand.apparent_size,
         count_hard_links: global.count_hard_links || subcommand.count_hard_links,
         stay_on_filesystem: global.stay_on_filesystem || subcommand.stay_on_filesystem,
+        follow_links: global.follow_links || subcommand.follow_links,
+        max_depth: global.max_depth.or(subcommand.max_depth),
         ignore_dirs: if is_default_ignore_dirs(&global.ignore_dirs) {
             subcommand.ignore_dirs.clone()
         } else {
@@ -298,6 +376,9 @@ fn walk_options_from(traversal: &options::TraversalArgs) -> Result<dua::WalkOpti
         apparent_size: traversal.apparent_size,
         count_hard_links: traversal.count_hard_links,
         cross_filesystems: !traversal.stay_on_filesystem,
+        follow_links: traversal.follow_links,
+        max_depth: traversal.max_depth,
+        collapse_files_below: None,
         ignore_dirs: canonicalize_ignore_dirs(&traversal.ignore_dirs),
         ignore_patterns: dua::IgnorePatterns::from_files(&traversal.ignore_from)?,
     };
@@ -457,6 +538,17 @@ fn write_default_config_file(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Write `directory` to `cwd_file` for a shell to change into it, without a trailing newline.
+#[cfg(feature = "tui-crossplatform")]
+fn write_cwd_file(cwd_file: &Path, directory: &Path) -> Result<()> {
+    fs::write(cwd_file, directory.as_os_str().as_encoded_bytes()).with_context(|| {
+        format!(
+            "Could not write the directory to change into to {}",
+            cwd_file.display()
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::{merge_traversal_args, write_default_config_file};
@@ -487,6 +579,8 @@ mod tests {
             apparent_size: true,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![],
             ignore_from: vec![PathBuf::from("global-ignore")],
             input: vec![],
@@ -498,6 +592,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: true,
             stay_on_filesystem: true,
+            follow_links: false,
+            max_depth: Some(2),
             ignore_dirs: vec![],
             ignore_from: vec![PathBuf::from("subcommand-ignore")],
             input: vec![PathBuf::from("subcommand-input")],
@@ -507,6 +603,7 @@ mod tests {
 
         assert_eq!(merged.threads, custom_threads);
         assert_eq!(merged.input, subcommand.input);
+        assert_eq!(merged.max_depth, Some(2));
         assert_eq!(
             merged.ignore_from,
             [
@@ -524,6 +621,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![],
             ignore_from: vec![],
             input: vec![],
@@ -535,6 +634,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![],
             ignore_from: vec![],
             input: vec![],
@@ -553,6 +654,8 @@ mod tests {
             apparent_size: true,
             count_hard_links: false,
             stay_on_filesystem: true,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![],
             ignore_from: vec![],
             input: vec![],
@@ -564,6 +667,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: true,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![],
             ignore_from: vec![],
             input: vec![],
@@ -585,6 +690,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![PathBuf::from("/custom-global-ignore")],
             ignore_from: vec![],
             input: vec![],
@@ -596,6 +703,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![PathBuf::from("/custom-subcommand-ignore")],
             ignore_from: vec![],
             input: vec![],
@@ -614,6 +723,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: super::options::DEFAULT_IGNORE_DIRS
                 .iter()
                 .map(PathBuf::from)
@@ -628,6 +739,8 @@ mod tests {
             apparent_size: false,
             count_hard_links: false,
             stay_on_filesystem: false,
+            follow_links: false,
+            max_depth: None,
             ignore_dirs: vec![PathBuf::from("/custom-subcommand-ignore")],
             ignore_from: vec![],
             input: vec![],
@@ -74,6 +74,10 @@ impl TraversalArgs {
 }
 
 #[derive(Debug, Clone, clap::Args)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag is an independent command-line switch"
+)]
 pub struct TraversalArgs {
     /// The amount of threads to use. Defaults to 0, indicating the amount of logical processors.
     /// Set to 1 to use only a single thread.
@@ -124,6 +128,33 @@ pub struct TraversalArgs {
     )]
     pub stay_on_filesystem: bool,
 
+    /// Follow symbolic links and count what they point to instead of the links themselves.
+    ///
+    /// Each directory is traversed only once, even if multiple links lead to it, which also
+    /// prevents infinite loops. Files reachable through multiple links are counted once unless
+    /// `--count-hard-links` is set.
+    #[clap(
+        short = 'L',
+        long,
+        env = "DUA_FOLLOW_LINKS",
+        help_heading = "Traversal Options"
+    )]
+    pub follow_links: bool,
+
+    /// Show directories only up to this many levels below each input path.
+    ///
+    /// Directories at that depth are still counted with everything they contain, but what's
+    /// inside of them isn't listed, which keeps memory usage low on very large filesystems.
+    /// With a single directory as input, its entries are the input paths.
+    #[clap(
+        short = 'd',
+        long,
+        value_name = "DEPTH",
+        env = "DUA_MAX_DEPTH",
+        help_heading = "Traversal Options"
+    )]
+    pub max_depth: Option<usize>,
+
     /// One or more absolute directories to ignore. Note that these are not ignored if they are passed as input path.
     ///
     /// Hence, they will only be ignored if they are eventually reached as part of the traversal.
@@ -173,6 +204,19 @@ pub enum Command {
         /// Exit automatically after traversal, optionally replaying the given single-character keys first.
         #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "")]
         once: Option<String>,
+        /// Combine the files of each directory that are smaller than SIZE into a single entry,
+        /// like `1234 small files`, which can be listed individually by entering it.
+        ///
+        /// This bounds memory usage on filesystems with hundreds of millions of files, while sizes
+        /// and entry counts remain exact. SIZE is in bytes unless it has a unit, like `64KiB` or `1MB`.
+        #[clap(long, value_name = "SIZE", value_parser = parse_size, env = "DUA_COLLAPSE_FILES_BELOW")]
+        collapse_files_below: Option<u64>,
+        /// When quitting with `Q` to change into the directory in view, write that directory to
+        /// FILE instead of printing it in place of the marked paths.
+        ///
+        /// `dua completions <SHELL> --shell-integration` prints a shell function doing that.
+        #[clap(long, value_name = "FILE")]
+        cwd_file: Option<PathBuf>,
     },
     /// Aggregate the consumed space of one or more directories or files
     #[clap(name = "aggregate", visible_alias = "a")]
@@ -189,11 +233,20 @@ pub enum Command {
         /// If set, no total column will be computed for multiple inputs
         #[clap(long)]
         no_total: bool,
+        /// If set, sum up the bytes and entries of each user owning them instead of each input.
+        ///
+        /// Owners are only known on Unix. Unless `--no-sort` is given, users are sorted by their size.
+        #[clap(long)]
+        by_owner: bool,
     },
     /// Generate shell completions
     Completions {
         /// The shell to generate a completions-script for
         shell: Shell,
+        /// Print the definition of a `duacd` function instead, which runs `dua interactive` and
+        /// changes into the directory it was quit in with `Q`.
+        #[clap(long)]
+        shell_integration: bool,
     },
     /// Configuration related commands
     Config {
@@ -203,6 +256,13 @@ pub enum Command {
     },
 }
 
+#[cfg(feature = "tui-crossplatform")]
+fn parse_size(input: &str) -> Result<u64, String> {
+    byte_unit::Byte::parse_str(input, false)
+        .map(|size| size.as_u64())
+        .map_err(|err| err.to_string())
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum ConfigCommand {
     /// Open the configuration file in `$EDITOR`.
@@ -285,6 +345,35 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "tui-crossplatform")]
+    fn collapse_files_below_accepts_sizes_with_units() {
+        for (input, expected) in [("512", 512), ("4KiB", 4096), ("1MB", 1_000_000)] {
+            let args = Args::try_parse_from(["dua", "i", "--collapse-files-below", input])
+                .expect("sizes parse");
+            let Some(super::Command::Interactive {
+                collapse_files_below,
+                ..
+            }) = args.command
+            else {
+                panic!("expected interactive subcommand");
+            };
+            assert_eq!(collapse_files_below, Some(expected), "{input}");
+        }
+        Args::try_parse_from(["dua", "i", "--collapse-files-below", "many"])
+            .expect_err("sizes need to be numbers");
+    }
+
+    #[test]
+    #[cfg(feature = "tui-crossplatform")]
+    fn cwd_file_is_accepted_by_the_interactive_subcommand() {
+        let args = Args::try_parse_from(["dua", "i", "--cwd-file", "cwd"]).expect("valid args");
+        let Some(super::Command::Interactive { cwd_file, .. }) = args.command else {
+            panic!("expected interactive subcommand");
+        };
+        assert_eq!(cwd_file, Some(PathBuf::from("cwd")));
+    }
+
     #[test]
     fn traversal_options_are_rejected_after_config_edit() {
         let err = Args::try_parse_from(["dua", "config", "edit", "--format", "metric"])
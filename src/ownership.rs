@@ -0,0 +1,284 @@
+use std::{collections::HashMap, num::NonZeroU32, sync::Arc};
+
+/// The owner, group and permission bits of an entry, as recorded on Unix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ownership {
+    /// The id of the user owning the entry.
+    pub uid: u32,
+    /// The id of the group owning the entry.
+    pub gid: u32,
+    /// The file type and permission bits, like `st_mode`.
+    pub mode: u32,
+}
+
+impl Ownership {
+    /// Obtain the ownership of the entry `metadata` belongs to.
+    #[cfg(unix)]
+    #[must_use]
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+        Ownership {
+            uid: metadata.uid(),
+            gid: metadata.gid(),
+            mode: metadata.mode(),
+        }
+    }
+
+    /// Return the permissions in the style of `ls -l`, like `drwxr-xr-x`.
+    #[must_use]
+    pub fn permissions(&self) -> String {
+        const TYPE_MASK: u32 = 0o170_000;
+        let file_type = match self.mode & TYPE_MASK {
+            0o040_000 => 'd',
+            0o120_000 => 'l',
+            0o020_000 => 'c',
+            0o060_000 => 'b',
+            0o010_000 => 'p',
+            0o140_000 => 's',
+            _ => '-',
+        };
+        let mut out = String::with_capacity(10);
+        out.push(file_type);
+        for (shift, special, special_char) in [(6, 0o4000, 's'), (3, 0o2000, 's'), (0, 0o1000, 't')]
+        {
+            let bits = (self.mode >> shift) & 0o7;
+            out.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            out.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+            out.push(match (bits & 0o1 != 0, self.mode & special != 0) {
+                (true, true) => special_char,
+                (false, true) => special_char.to_ascii_uppercase(),
+                (true, false) => 'x',
+                (false, false) => '-',
+            });
+        }
+        out
+    }
+}
+
+/// Refers to an [`Ownership`] within [`Ownerships`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct OwnershipId(NonZeroU32);
+
+impl OwnershipId {
+    fn position(self) -> usize {
+        self.0.get() as usize - 1
+    }
+}
+
+/// The distinct ownerships of all entries of a traversal, along with the names of their users
+/// and groups.
+///
+/// Names are looked up once when a user or group is seen for the first time.
+#[derive(Debug, Default, Clone)]
+pub struct Ownerships {
+    by_position: Vec<Ownership>,
+    ids: HashMap<Ownership, OwnershipId>,
+    user_names: HashMap<u32, Option<Arc<str>>>,
+    group_names: HashMap<u32, Option<Arc<str>>>,
+}
+
+impl Ownerships {
+    /// Return the id of `ownership`, adding it if it wasn't seen before.
+    pub fn intern(&mut self, ownership: Ownership) -> OwnershipId {
+        if let Some(id) = self.ids.get(&ownership) {
+            return *id;
+        }
+        self.push(ownership)
+    }
+
+    fn push(&mut self, ownership: Ownership) -> OwnershipId {
+        self.by_position.push(ownership);
+        let id = OwnershipId(
+            u32::try_from(self.by_position.len())
+                .ok()
+                .and_then(NonZeroU32::new)
+                .expect("fewer than 4 billion distinct ownerships"),
+        );
+        self.ids.entry(ownership).or_insert(id);
+        self.user_names
+            .entry(ownership.uid)
+            .or_insert_with(|| user_name_of(ownership.uid));
+        self.group_names
+            .entry(ownership.gid)
+            .or_insert_with(|| group_name_of(ownership.gid));
+        id
+    }
+
+    /// Return the ownership `id` refers to, or `None` if it's not part of this instance.
+    #[must_use]
+    pub fn get(&self, id: OwnershipId) -> Option<Ownership> {
+        self.by_position.get(id.position()).copied()
+    }
+
+    /// Return the name of the user with `uid`, if it's known.
+    #[must_use]
+    pub fn user_name(&self, uid: u32) -> Option<&str> {
+        self.user_names.get(&uid)?.as_deref()
+    }
+
+    /// Return the name of the group with `gid`, if it's known.
+    #[must_use]
+    pub fn group_name(&self, gid: u32) -> Option<&str> {
+        self.group_names.get(&gid)?.as_deref()
+    }
+
+    /// Return all ownerships in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = Ownership> + '_ {
+        self.by_position.iter().copied()
+    }
+
+    /// Return the amount of ownerships.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.by_position.len()
+    }
+
+    /// Return `true` if no ownership was added yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.by_position.is_empty()
+    }
+}
+
+/// Collect `ownerships` so that their ids correspond to their position, starting at 1.
+impl FromIterator<Ownership> for Ownerships {
+    fn from_iter<T: IntoIterator<Item = Ownership>>(ownerships: T) -> Self {
+        let mut out = Ownerships::default();
+        for ownership in ownerships {
+            out.push(ownership);
+        }
+        out
+    }
+}
+
+/// The amount of bytes and entries owned by one user.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OwnerUsage {
+    /// The id of the owning user, or `None` if the owner of the entries isn't known.
+    pub uid: Option<u32>,
+    /// The total size of all entries owned by the user.
+    pub bytes: u128,
+    /// The amount of entries owned by the user.
+    pub entries: u64,
+    /// The amount of I/O errors encountered while looking at entries of the user.
+    pub io_errors: u64,
+}
+
+/// Sums up the bytes and entries of each owning user.
+#[derive(Debug, Default, Clone)]
+pub struct UsageByOwner {
+    by_uid: HashMap<Option<u32>, OwnerUsage>,
+}
+
+impl UsageByOwner {
+    /// Return the usage of the user with `uid`, to add the bytes and entries they own.
+    pub fn of(&mut self, uid: Option<u32>) -> &mut OwnerUsage {
+        self.by_uid.entry(uid).or_insert_with(|| OwnerUsage {
+            uid,
+            ..OwnerUsage::default()
+        })
+    }
+
+    /// Return the usage of all users, smallest first, with ties ordered by their id and unknown
+    /// owners last.
+    #[must_use]
+    pub fn into_sorted_by_size(self) -> Vec<OwnerUsage> {
+        let mut usage: Vec<_> = self.by_uid.into_values().collect();
+        usage.sort_by_key(|usage| (usage.bytes, usage.uid.is_none(), usage.uid));
+        usage
+    }
+}
+
+/// Look up the name of the user with `uid`, or `None` if there is no such user.
+#[cfg(unix)]
+#[must_use]
+pub fn user_name_of(uid: u32) -> Option<Arc<str>> {
+    uzers::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().into())
+}
+
+/// Look up the name of the user with `uid`, which isn't possible on this platform.
+#[cfg(not(unix))]
+#[must_use]
+pub fn user_name_of(_uid: u32) -> Option<Arc<str>> {
+    None
+}
+
+#[cfg(unix)]
+fn group_name_of(gid: u32) -> Option<Arc<str>> {
+    uzers::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().into())
+}
+
+#[cfg(not(unix))]
+fn group_name_of(_gid: u32) -> Option<Arc<str>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_are_displayed_like_ls() {
+        let permissions = |mode| {
+            Ownership {
+                uid: 0,
+                gid: 0,
+                mode,
+            }
+            .permissions()
+        };
+        assert_eq!(permissions(0o040_755), "drwxr-xr-x");
+        assert_eq!(permissions(0o100_644), "-rw-r--r--");
+        assert_eq!(permissions(0o120_777), "lrwxrwxrwx");
+        assert_eq!(permissions(0o104_755), "-rwsr-xr-x");
+        assert_eq!(permissions(0o041_777), "drwxrwxrwt");
+        assert_eq!(permissions(0o102_640), "-rw-r-S---");
+    }
+
+    #[test]
+    fn equal_ownerships_are_interned_once() {
+        let mut ownerships = Ownerships::default();
+        let a = Ownership {
+            uid: 1,
+            gid: 2,
+            mode: 0o100_644,
+        };
+        let b = Ownership {
+            mode: 0o100_600,
+            ..a
+        };
+        let a_id = ownerships.intern(a);
+        let b_id = ownerships.intern(b);
+        assert_ne!(a_id, b_id);
+        assert_eq!(ownerships.intern(a), a_id, "known ownerships keep their id");
+        assert_eq!(ownerships.len(), 2);
+        assert_eq!(ownerships.get(a_id), Some(a));
+        assert_eq!(ownerships.get(b_id), Some(b));
+    }
+
+    #[test]
+    fn usage_is_summed_per_owner_and_sorted_by_size() {
+        let mut usage = UsageByOwner::default();
+        for (uid, bytes) in [(Some(1), 10), (None, 5), (Some(2), 5), (Some(1), 20)] {
+            let owner = usage.of(uid);
+            owner.bytes += bytes;
+            owner.entries += 1;
+        }
+        let summary: Vec<_> = usage
+            .into_sorted_by_size()
+            .into_iter()
+            .map(|usage| (usage.uid, usage.bytes, usage.entries))
+            .collect();
+        assert_eq!(
+            summary,
+            [(Some(2), 5, 1), (None, 5, 1), (Some(1), 30, 2)],
+            "equally large owners are ordered by id, with unknown ones last"
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::{Result, bail};
+use clap_complete::Shell;
+
+const POSIX: &str = r#"# Run `dua interactive` and change into the directory it was quit in with `Q`.
+duacd() {
+    local cwd_file exit_code
+    cwd_file="$(mktemp)" || return
+    command dua interactive --cwd-file "$cwd_file" "$@"
+    exit_code=$?
+    if [ -s "$cwd_file" ]; then
+        cd -- "$(cat -- "$cwd_file")" || exit_code=$?
+    fi
+    rm -f -- "$cwd_file"
+    return $exit_code
+}
+"#;
+
+const FISH: &str = r"# Run `dua interactive` and change into the directory it was quit in with `Q`.
+function duacd
+    set -l cwd_file (mktemp); or return
+    command dua interactive --cwd-file $cwd_file $argv
+    set -l exit_code $status
+    if test -s $cwd_file
+        cd (cat $cwd_file); or set exit_code $status
+    end
+    rm -f $cwd_file
+    return $exit_code
+end
+";
+
+const POWERSHELL: &str = r"# Run `dua interactive` and change into the directory it was quit in with `Q`.
+function duacd {
+    $cwdFile = New-TemporaryFile
+    dua interactive --cwd-file $cwdFile.FullName @args
+    $exitCode = $LASTEXITCODE
+    $cwd = Get-Content -Raw -LiteralPath $cwdFile.FullName
+    if ($cwd) {
+        Set-Location -LiteralPath $cwd
+    }
+    Remove-Item -LiteralPath $cwdFile.FullName
+    $global:LASTEXITCODE = $exitCode
+}
+";
+
+/// Return the definition of a `duacd` function for `shell`, which changes into the directory
+/// `dua interactive` was quit in.
+pub fn snippet(shell: Shell) -> Result<&'static str> {
+    Ok(match shell {
+        Shell::Bash | Shell::Zsh => POSIX,
+        Shell::Fish => FISH,
+        Shell::PowerShell => POWERSHELL,
+        other => bail!("There is no shell integration for {other} yet"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_snippets_pass_a_cwd_file() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let snippet = snippet(shell).expect("supported");
+            assert!(snippet.contains("duacd"), "{shell}");
+            assert!(snippet.contains("--cwd-file"), "{shell}");
+        }
+        assert!(snippet(Shell::Elvish).is_err());
+    }
+}
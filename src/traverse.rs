@@ -1,4 +1,8 @@
-use crate::{Throttle, WalkOptions, WalkRoot, crossdev, inodefilter::InodeFilter};
+use crate::{
+    Throttle, WalkOptions, WalkRoot, crossdev,
+    inodefilter::InodeFilter,
+    ownership::{OwnershipId, Ownerships, UsageByOwner},
+};
 
 use crossbeam::channel::Receiver;
 #[cfg(not(windows))]
@@ -8,6 +12,7 @@ use std::time::Instant;
 use std::{
     collections::HashMap,
     fmt, io,
+    num::NonZeroU64,
     path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
@@ -20,20 +25,37 @@ pub type Tree = StableGraph<EntryData, (), Directed>;
 
 /// Data stored for each filesystem entry in the traversal tree.
 #[derive(Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntryData {
     /// The entry name relative to its parent.
     pub name: PathBuf,
     /// The entry's size in bytes. If it's a directory, the size is the aggregated file size of all children
     /// plus the  size of the directory entry itself
     pub size: u128,
+    /// The disk usage if [`size`](Self::size) is the apparent size and vice versa, aggregated
+    /// the same way, so one can be shown instead of the other without traversing again.
+    ///
+    /// It's stored in fewer bytes than `size` to save memory, and saturates if it doesn't fit.
+    ///
+    /// See [`Traversal::swap_sizes()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alternate_size: u64,
     /// Last modification time if available.
     pub mtime: SystemTime,
-    /// Recursive entry count for directories, or `None` for files.
-    pub entry_count: Option<u64>,
+    /// Recursive entry count for directories, or none for files.
+    pub entry_count: EntryCount,
     /// If set, the item meta-data could not be obtained
     pub metadata_io_error: bool,
     /// `true` if the entry is a directory.
     pub is_dir: bool,
+    /// `true` if this node stands for all files of its parent directory that were smaller than
+    /// [`WalkOptions::collapse_files_below`], with `entry_count` being their amount.
+    /// Its name is empty, so its path is the one of its directory, and its ownership is the one
+    /// of its directory as well.
+    pub is_small_files: bool,
+    /// The owner, group and permissions of the entry within [`Traversal::ownerships`], if they
+    /// were recorded, which only happens on Unix.
+    pub ownership: Option<OwnershipId>,
 }
 
 impl Default for EntryData {
@@ -41,10 +63,13 @@ impl Default for EntryData {
         EntryData {
             name: PathBuf::default(),
             size: u128::default(),
+            alternate_size: u64::default(),
             mtime: UNIX_EPOCH,
-            entry_count: None,
+            entry_count: EntryCount::default(),
             metadata_io_error: bool::default(),
             is_dir: false,
+            is_small_files: false,
+            ownership: None,
         }
     }
 }
@@ -54,14 +79,68 @@ impl fmt::Debug for EntryData {
         f.debug_struct("EntryData")
             .field("name", &self.name)
             .field("size", &self.size)
-            .field("entry_count", &self.entry_count)
+            .field("alternate_size", &self.alternate_size)
+            .field("entry_count", &self.entry_count.get())
             // Skip mtime
             .field("metadata_io_error", &self.metadata_io_error)
+            .field("is_small_files", &self.is_small_files)
+            .field("ownership", &self.ownership)
             .finish()
     }
 }
 
+/// An optional amount of entries, stored in the space of a `u64` instead of the one of an
+/// `Option<u64>`, which is twice as large.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Option<u64>", into = "Option<u64>")
+)]
+pub struct EntryCount(Option<NonZeroU64>);
+
+impl EntryCount {
+    /// Create a count of `count` entries, saturating below `u64::MAX`.
+    #[must_use]
+    pub fn new(count: Option<u64>) -> Self {
+        EntryCount(count.map(|count| NonZeroU64::MIN.saturating_add(count)))
+    }
+
+    /// Return the amount of entries, if there is one.
+    #[must_use]
+    pub fn get(self) -> Option<u64> {
+        self.0.map(|count| count.get() - 1)
+    }
+
+    /// Add `count` entries, starting from zero if there was no amount yet.
+    pub fn add(&mut self, count: u64) {
+        *self = EntryCount::new(Some(self.get().unwrap_or(0).saturating_add(count)));
+    }
+}
+
+impl From<Option<u64>> for EntryCount {
+    fn from(count: Option<u64>) -> Self {
+        EntryCount::new(count)
+    }
+}
+
+impl From<EntryCount> for Option<u64> {
+    fn from(count: EntryCount) -> Self {
+        count.get()
+    }
+}
+
+/// Return `size` as it is stored in [`EntryData::alternate_size`].
+fn alternate_size(size: u128) -> u64 {
+    u64::try_from(size).unwrap_or(u64::MAX)
+}
+
 /// The result of the previous filesystem traversal
+///
+/// With the `serde` feature, it serializes as a list of nodes in which each node refers to its
+/// parent by its position in the list, with the root being first. This representation doesn't
+/// depend on node indices, and only nodes reachable from the root are included.
+/// [`Traversal::start_time`] isn't serialized and is set to the time of deserialization.
 #[derive(Debug)]
 pub struct Traversal {
     /// A tree representing the entire filestem traversal
@@ -72,6 +151,8 @@ pub struct Traversal {
     pub start_time: Instant,
     /// The time it cost to compute the traversal, when done.
     pub cost: Option<Duration>,
+    /// The distinct ownerships referred to by [`EntryData::ownership`].
+    pub ownerships: Ownerships,
 }
 
 impl Default for Traversal {
@@ -91,7 +172,32 @@ impl Traversal {
             root_index,
             start_time: Instant::now(),
             cost: None,
+            ownerships: Ownerships::default(),
+        }
+    }
+
+    /// Walk all `paths` and block until the traversal is complete, returning it along with
+    /// statistics about the walk.
+    ///
+    /// Each path becomes a direct child of [`Traversal::root_index`], named by the path as given,
+    /// so [`Traversal::path_of()`] yields paths that can be used with the filesystem directly.
+    #[must_use]
+    pub fn from_paths(walk_options: &WalkOptions, paths: Vec<PathBuf>) -> (Self, TraversalStats) {
+        let mut traversal = Traversal::new();
+        let mut integration = BackgroundTraversal::with_receiver(
+            traversal.root_index,
+            walk_options,
+            false,
+            true,
+            None,
+            crossbeam::channel::never(),
+        );
+        let pattern_roots = walk_options.ignore_patterns.as_ref().map(|_| paths.clone());
+        for event in traversal_events(walk_options, paths, pattern_roots.as_deref(), false) {
+            integration.integrate_traversal_event(&mut traversal, event);
         }
+        traversal.cost = integration.stats.elapsed;
+        (traversal, integration.stats)
     }
 
     /// Return `true` if this traversal is considered expensive to recompute.
@@ -99,6 +205,121 @@ impl Traversal {
     pub fn is_costly(&self) -> bool {
         self.cost.is_none_or(|d| d.as_secs_f32() > 10.0)
     }
+
+    /// Return the parent of the node at `index`, or `None` if it is the root.
+    #[must_use]
+    pub fn parent_of(&self, index: TreeIndex) -> Option<TreeIndex> {
+        self.tree
+            .neighbors_directed(index, Direction::Incoming)
+            .next()
+    }
+
+    /// Return the path of the node at `index` by joining the names of all of its ancestors.
+    ///
+    /// The root node itself has an empty path.
+    #[must_use]
+    pub fn path_of(&self, mut index: TreeIndex) -> PathBuf {
+        let mut names = Vec::new();
+        while let Some(parent) = self.parent_of(index) {
+            names.push(&self.tree[index].name);
+            index = parent;
+        }
+        names.into_iter().rev().collect()
+    }
+
+    /// Find the node whose [path](Traversal::path_of()) is `path`, or `None` if it wasn't traversed.
+    #[must_use]
+    pub fn find_by_path(&self, path: impl AsRef<Path>) -> Option<TreeIndex> {
+        let path = path.as_ref();
+        if path.as_os_str().is_empty() {
+            return Some(self.root_index);
+        }
+        self.tree
+            .neighbors_directed(self.root_index, Direction::Outgoing)
+            .find_map(|top_level| {
+                let mut components = path
+                    .strip_prefix(&self.tree[top_level].name)
+                    .ok()?
+                    .components();
+                components.try_fold(top_level, |index, component| {
+                    self.tree
+                        .neighbors_directed(index, Direction::Outgoing)
+                        .find(|child| self.tree[*child].name.as_os_str() == component.as_os_str())
+                })
+            })
+    }
+
+    /// Return the direct children of the node at `index`, largest first.
+    #[must_use]
+    pub fn children_by_size(&self, index: TreeIndex) -> Vec<TreeIndex> {
+        let mut children: Vec<_> = self
+            .tree
+            .neighbors_directed(index, Direction::Outgoing)
+            .collect();
+        children.sort_by(|a, b| self.tree[*b].size.cmp(&self.tree[*a].size));
+        children
+    }
+
+    /// Iterate all nodes below the one at `index` along with their full path, parents before
+    /// their children.
+    pub fn descendants(&self, index: TreeIndex) -> impl Iterator<Item = (TreeIndex, PathBuf)> + '_ {
+        let mut stack: Vec<_> = self
+            .tree
+            .neighbors_directed(index, Direction::Outgoing)
+            .map(|child| (child, self.path_of(child)))
+            .collect();
+        std::iter::from_fn(move || {
+            let (index, path) = stack.pop()?;
+            stack.extend(
+                self.tree
+                    .neighbors_directed(index, Direction::Outgoing)
+                    .map(|child| (child, path.join(&self.tree[child].name))),
+            );
+            Some((index, path))
+        })
+    }
+
+    /// Exchange [`EntryData::size`] and [`EntryData::alternate_size`] of all nodes, to use the
+    /// apparent size instead of the disk usage or vice versa.
+    pub fn swap_sizes(&mut self) {
+        for entry in self.tree.node_weights_mut() {
+            let size = entry.size;
+            entry.size = u128::from(entry.alternate_size);
+            entry.alternate_size = alternate_size(size);
+        }
+    }
+
+    /// Sum up the bytes and entries of all nodes below the one at `index` by the user owning them.
+    ///
+    /// Directories only account for their own size and entry, unless their contents weren't
+    /// traversed. Nodes without recorded ownership are attributed to an unknown owner.
+    #[must_use]
+    pub fn usage_by_owner(&self, index: TreeIndex) -> UsageByOwner {
+        let entry_count = |entry: &EntryData| entry.entry_count.get().unwrap_or(1);
+        let mut usage = UsageByOwner::default();
+        let mut stack: Vec<_> = self
+            .tree
+            .neighbors_directed(index, Direction::Outgoing)
+            .collect();
+        while let Some(index) = stack.pop() {
+            let entry = &self.tree[index];
+            let (mut bytes, mut entries) = (entry.size, entry_count(entry));
+            for child in self.tree.neighbors_directed(index, Direction::Outgoing) {
+                let child_entry = &self.tree[child];
+                bytes = bytes.saturating_sub(child_entry.size);
+                entries = entries.saturating_sub(entry_count(child_entry));
+                stack.push(child);
+            }
+            let uid = entry
+                .ownership
+                .and_then(|id| self.ownerships.get(id))
+                .map(|ownership| ownership.uid);
+            let owner = usage.of(uid);
+            owner.bytes += bytes;
+            owner.entries += entries;
+        }
+        usage
+    }
 }
 
 /// Runtime statistics gathered while traversal is running.
@@ -148,6 +369,10 @@ pub struct BackgroundTraversal {
     pub stats: TraversalStats,
     /// Nodes keyed by root allocation identity and path so overlapping roots build separate trees.
     nodes_by_path: HashMap<(usize, PathBuf), TreeIndex>,
+    /// The small-files node of each directory that has one.
+    small_files_by_parent: HashMap<TreeIndex, TreeIndex>,
+    /// Files at most this many levels below the root are never collapsed into small-files nodes.
+    expanded_depth: usize,
     inodes: InodeFilter,
     throttle: Option<Throttle>,
     skip_root: bool,
@@ -174,80 +399,63 @@ impl BackgroundTraversal {
             .spawn({
                 let walk_options = walk_options.clone();
                 move || {
-                    let mut io_errors = 0;
-                    let (mut root_paths, mut device_ids, mut walk_roots) = (
-                        Vec::with_capacity(input.len()),
-                        Vec::with_capacity(input.len()),
-                        Vec::with_capacity(input.len()),
-                    );
-                    for root_path in input {
-                        log::info!("Walking {}", root_path.display());
-                        let pattern_root = pattern_roots.as_deref().map(|pattern_roots| {
-                            pattern_roots
-                                .iter()
-                                .filter(|candidate| root_path.starts_with(candidate))
-                                .max_by_key(|candidate| candidate.components().count())
-                                .cloned()
-                                .unwrap_or_else(|| root_path.clone())
-                        });
-                        let device_id = if walk_options.cross_filesystems {
-                            0
-                        } else {
-                            let Ok(device_id) = crossdev::init(&root_path) else {
-                                // Skip roots that can't be accessed entirely.
-                                io_errors += 1;
-                                continue;
-                            };
-                            device_id
-                        };
-                        walk_roots.push(WalkRoot {
-                            index: walk_roots.len(),
-                            pattern_root,
-                            path: root_path.clone(),
-                            device_id,
-                        });
-                        device_ids.push(device_id);
-                        root_paths.push(Arc::new(root_path));
-                    }
-
-                    for (root, event) in walk_options.iter_from_paths(
-                        walk_roots,
-                        skip_root,
-                        crate::walk::Order::ParentFirst,
-                    ) {
-                        let crate::walk::RootEvent::Entry(entry) = event else {
-                            continue;
-                        };
-                        if entry_tx
-                            .send(TraversalEvent::Entry(
-                                entry.map(TraversalEntry),
-                                Arc::clone(&root_paths[root]),
-                                device_ids[root],
-                            ))
-                            .is_err()
-                        {
+                    for event in
+                        traversal_events(&walk_options, input, pattern_roots.as_deref(), skip_root)
+                    {
+                        let is_finished = matches!(event, TraversalEvent::Finished(_));
+                        if entry_tx.send(event).is_err() {
+                            if is_finished {
+                                log::error!("Failed to send TraversalEvents::Finished event");
+                            }
                             // The channel is closed, this means the user has
                             // requested to quit the app. Abort the walking.
                             return;
                         }
                     }
-                    if entry_tx.send(TraversalEvent::Finished(io_errors)).is_err() {
-                        log::error!("Failed to send TraversalEvents::Finished event");
-                    }
                 }
             })?;
 
-        Ok(Self {
+        Ok(Self::with_receiver(
+            root_idx,
+            walk_options,
+            skip_root,
+            use_root_path,
+            Some(Throttle::new(Duration::from_millis(250), None)),
+            entry_rx,
+        ))
+    }
+
+    fn with_receiver(
+        root_idx: TreeIndex,
+        walk_options: &WalkOptions,
+        skip_root: bool,
+        use_root_path: bool,
+        throttle: Option<Throttle>,
+        event_rx: Receiver<TraversalEvent>,
+    ) -> Self {
+        Self {
             walk_options: walk_options.clone(),
             root_idx,
             stats: TraversalStats::default(),
             nodes_by_path: HashMap::new(),
-            inodes: InodeFilter::default(),
-            throttle: Some(Throttle::new(Duration::from_millis(250), None)),
+            small_files_by_parent: HashMap::new(),
+            expanded_depth: 0,
+            inodes: InodeFilter::new(walk_options.follow_links, walk_options.count_hard_links),
+            throttle,
             skip_root,
             use_root_path,
-            event_rx: entry_rx,
-        })
+            event_rx,
+        }
+    }
+
+    /// Add files up to `depth` levels below the root individually, even if they are smaller than
+    /// [`WalkOptions::collapse_files_below`].
+    ///
+    /// With `skip_root`, a `depth` of `1` lists the small files of the refreshed directory.
+    #[must_use]
+    pub fn expand_small_files(mut self, depth: usize) -> Self {
+        self.expanded_depth = depth;
+        self
     }
 
     /// Integrate `event` into traversal `t` so its information is represented by it.
@@ -289,35 +497,42 @@ impl BackgroundTraversal {
                             }
                         }
 
-                        let mut file_size = 0u128;
+                        let (mut file_size, mut alternate_size) = (0u128, 0u128);
                         let mut mtime: SystemTime = UNIX_EPOCH;
                         data.is_dir = entry.file_type.is_dir();
                         if let Ok(m) = &entry.metadata {
-                            if self.walk_options.count_hard_links
-                                || self.inodes.add(m)
-                                    && (self.walk_options.cross_filesystems
-                                        || crossdev::is_same_device(device_id, m))
+                            if self.inodes.add(m)
+                                && (self.walk_options.cross_filesystems
+                                    || crossdev::is_same_device(device_id, m))
                             {
-                                if self.walk_options.apparent_size {
-                                    file_size = u128::from(m.len());
-                                } else {
-                                    file_size =
-                                        u128::from(
-                                            size_on_disk(
-                                                &entry.parent_path,
-                                                &data.name,
-                                                m,
-                                                data.is_dir,
-                                            )
-                                            .unwrap_or_else(|_| {
+                                let apparent_size = u128::from(m.len());
+                                let disk_size = u128::from(
+                                    size_on_disk(&entry.parent_path, &data.name, m, data.is_dir)
+                                        .unwrap_or_else(|_| {
+                                            // Only errors of the size in use are reported.
+                                            if !self.walk_options.apparent_size {
                                                 self.stats.io_errors += 1;
                                                 data.metadata_io_error = true;
-                                                0
-                                            }),
-                                        );
-                                }
+                                            }
+                                            0
+                                        }),
+                                );
+                                (file_size, alternate_size) = if self.walk_options.apparent_size {
+                                    (apparent_size, disk_size)
+                                } else {
+                                    (disk_size, apparent_size)
+                                };
                             } else {
-                                data.entry_count = Some(0);
+                                data.entry_count = EntryCount::new(Some(0));
+                            }
+
+                            #[cfg(unix)]
+                            {
+                                data.ownership = Some(
+                                    traversal
+                                        .ownerships
+                                        .intern(crate::ownership::Ownership::from_metadata(m)),
+                                );
                             }
 
                             if let Ok(modified) = m.modified() {
@@ -331,12 +546,26 @@ impl BackgroundTraversal {
                             data.metadata_io_error = true;
                         }
 
+                        let mut entry_count =
+                            u64::from(data.is_dir || data.entry_count.get() != Some(0));
+                        if let Some(summary) = &entry.summary {
+                            let (size, alternate) = self
+                                .walk_options
+                                .summarized_sizes(summary, &mut self.inodes);
+                            file_size += size;
+                            alternate_size += alternate;
+                            entry_count += summary.entries;
+                            self.stats.entries_traversed += summary.entries;
+                            self.stats.io_errors += summary.io_errors;
+                        }
+
                         data.mtime = mtime;
                         data.size = file_size;
+                        let alternate_size = self::alternate_size(alternate_size);
+                        data.alternate_size = alternate_size;
                         if data.is_dir {
-                            data.entry_count = Some(1);
+                            data.entry_count = EntryCount::new(Some(entry_count));
                         }
-                        let entry_count = u64::from(data.is_dir || data.entry_count != Some(0));
 
                         let parent_index = if walk_depth == 0 {
                             self.root_idx
@@ -351,10 +580,41 @@ impl BackgroundTraversal {
                                 .get(&(root, entry.parent_path.to_path_buf()))
                                 .expect("parent entries are emitted before their children")
                         };
-                        let entry_index = traversal.tree.add_node(data);
-                        traversal.tree.add_edge(parent_index, entry_index, ());
-                        if traversal.tree[entry_index].is_dir {
-                            self.nodes_by_path.insert((root, entry.path()), entry_index);
+                        let collapse = !data.is_dir
+                            && walk_depth > self.expanded_depth
+                            && self
+                                .walk_options
+                                .collapse_files_below
+                                .is_some_and(|limit| file_size < u128::from(limit));
+                        if collapse {
+                            let ownership = traversal.tree[parent_index].ownership;
+                            let small_files = *self
+                                .small_files_by_parent
+                                .entry(parent_index)
+                                .or_insert_with(|| {
+                                    let index = traversal.tree.add_node(EntryData {
+                                        entry_count: EntryCount::new(Some(0)),
+                                        is_small_files: true,
+                                        ownership,
+                                        ..Default::default()
+                                    });
+                                    traversal.tree.add_edge(parent_index, index, ());
+                                    index
+                                });
+                            let small_files = &mut traversal.tree[small_files];
+                            small_files.size += file_size;
+                            small_files.alternate_size =
+                                small_files.alternate_size.saturating_add(alternate_size);
+                            small_files.entry_count.add(entry_count);
+                            small_files.mtime = small_files.mtime.max(data.mtime);
+                            small_files.metadata_io_error |= data.metadata_io_error;
+                        } else {
+                            let entry_index = traversal.tree.add_node(data);
+                            traversal.tree.add_edge(parent_index, entry_index, ());
+                            // Summarized directories have no children to look up their parent.
+                            if traversal.tree[entry_index].is_dir && entry.summary.is_none() {
+                                self.nodes_by_path.insert((root, entry.path()), entry_index);
+                            }
                         }
 
                         let mut ancestor = Some(parent_index);
@@ -365,7 +625,9 @@ impl BackgroundTraversal {
                                 .next();
                             let entry = &mut traversal.tree[index];
                             entry.size += file_size;
-                            *entry.entry_count.get_or_insert(0) += entry_count;
+                            entry.alternate_size =
+                                entry.alternate_size.saturating_add(alternate_size);
+                            entry.entry_count.add(entry_count);
                         }
                     }
                     Err(_) => self.stats.io_errors += 1,
@@ -380,6 +642,7 @@ impl BackgroundTraversal {
                 self.throttle = None;
                 let root_size = traversal.tree[self.root_idx].size;
                 self.nodes_by_path = HashMap::new();
+                self.small_files_by_parent = HashMap::new();
                 self.stats.total_bytes = Some(root_size);
                 self.stats.elapsed = Some(self.stats.start.elapsed());
 
@@ -390,6 +653,164 @@ impl BackgroundTraversal {
     }
 }
 
+/// Walk `input` and produce the events that build a traversal tree from it, ending with
+/// [`TraversalEvent::Finished`] once all entries were produced.
+fn traversal_events(
+    walk_options: &WalkOptions,
+    input: Vec<PathBuf>,
+    pattern_roots: Option<&[PathBuf]>,
+    skip_root: bool,
+) -> impl Iterator<Item = TraversalEvent> + use<> {
+    let mut io_errors = 0;
+    let (mut root_paths, mut device_ids, mut walk_roots) = (
+        Vec::with_capacity(input.len()),
+        Vec::with_capacity(input.len()),
+        Vec::with_capacity(input.len()),
+    );
+    for root_path in input {
+        log::info!("Walking {}", root_path.display());
+        let pattern_root = pattern_roots.map(|pattern_roots| {
+            pattern_roots
+                .iter()
+                .filter(|candidate| root_path.starts_with(candidate))
+                .max_by_key(|candidate| candidate.components().count())
+                .cloned()
+                .unwrap_or_else(|| root_path.clone())
+        });
+        let device_id = if walk_options.cross_filesystems {
+            0
+        } else {
+            let Ok(device_id) = crossdev::init(&root_path) else {
+                // Skip roots that can't be accessed entirely.
+                io_errors += 1;
+                continue;
+            };
+            device_id
+        };
+        walk_roots.push(WalkRoot {
+            index: walk_roots.len(),
+            pattern_root,
+            path: root_path.clone(),
+            device_id,
+        });
+        device_ids.push(device_id);
+        root_paths.push(Arc::new(root_path));
+    }
+
+    walk_options
+        .iter_from_paths(walk_roots, skip_root, crate::walk::Order::ParentFirst)
+        .filter_map(move |(root, event)| {
+            let crate::walk::RootEvent::Entry(entry) = event else {
+                return None;
+            };
+            Some(TraversalEvent::Entry(
+                entry.map(TraversalEntry),
+                Arc::clone(&root_paths[root]),
+                device_ids[root],
+            ))
+        })
+        .chain(std::iter::once(TraversalEvent::Finished(io_errors)))
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{EntryData, Traversal, Tree};
+    use crate::ownership::{Ownership, Ownerships};
+    use petgraph::Direction;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+    use std::time::{Duration, Instant};
+
+    #[derive(Serialize, Deserialize)]
+    struct Node<E> {
+        /// The position of the parent node in the list of all nodes, or `None` for the root.
+        parent: Option<usize>,
+        entry: E,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct SerializedTraversal<E> {
+        /// All nodes in parent-first order.
+        nodes: Vec<Node<E>>,
+        cost: Option<Duration>,
+        /// The ownerships nodes refer to, with the first one having id 1.
+        #[serde(default)]
+        ownerships: Vec<Ownership>,
+    }
+
+    impl Serialize for Traversal {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut nodes = Vec::with_capacity(self.tree.node_count());
+            let mut stack = vec![(self.root_index, None)];
+            while let Some((index, parent)) = stack.pop() {
+                let position = nodes.len();
+                nodes.push(Node {
+                    parent,
+                    entry: &self.tree[index],
+                });
+                stack.extend(
+                    self.tree
+                        .neighbors_directed(index, Direction::Outgoing)
+                        .map(|child| (child, Some(position))),
+                );
+            }
+            SerializedTraversal {
+                nodes,
+                cost: self.cost,
+                ownerships: self.ownerships.iter().collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Traversal {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let SerializedTraversal {
+                nodes,
+                cost,
+                ownerships,
+            } = SerializedTraversal::<EntryData>::deserialize(deserializer)?;
+            let ownerships: Ownerships = ownerships.into_iter().collect();
+            let mut tree = Tree::with_capacity(nodes.len(), nodes.len().saturating_sub(1));
+            let mut indices = Vec::with_capacity(nodes.len());
+            for (position, Node { parent, entry }) in nodes.into_iter().enumerate() {
+                if let Some(id) = entry.ownership
+                    && ownerships.get(id).is_none()
+                {
+                    return Err(Error::custom(format!(
+                        "node {position} refers to unknown ownership {id:?}"
+                    )));
+                }
+                let index = tree.add_node(entry);
+                match (position, parent) {
+                    (0, None) => {}
+                    (0, Some(_)) => {
+                        return Err(Error::custom("the root node must not have a parent"));
+                    }
+                    (_, Some(parent)) if parent < position => {
+                        tree.add_edge(indices[parent], index, ());
+                    }
+                    (_, parent) => {
+                        return Err(Error::custom(format!(
+                            "node {position} must have a parent listed before it, got {parent:?}"
+                        )));
+                    }
+                }
+                indices.push(index);
+            }
+            let root_index = *indices
+                .first()
+                .ok_or_else(|| Error::custom("a traversal needs at least a root node"))?;
+            Ok(Traversal {
+                tree,
+                root_index,
+                start_time: Instant::now(),
+                cost,
+                ownerships,
+            })
+        }
+    }
+}
+
 #[cfg(not(windows))]
 /// Return disk usage for `name` on Unix-like platforms.
 fn size_on_disk(
@@ -416,6 +837,7 @@ fn size_on_disk(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::test_walk_options;
 
     #[test]
     fn ancestor_sizes_update_before_traversal_finishes() {
@@ -429,10 +851,7 @@ mod tests {
             &WalkOptions {
                 threads: 2,
                 count_hard_links: true,
-                apparent_size: true,
-                cross_filesystems: true,
-                ignore_dirs: std::collections::BTreeSet::default(),
-                ignore_patterns: None,
+                ..test_walk_options()
             },
             vec![dir.path().to_owned()],
             None,
@@ -478,12 +897,8 @@ mod tests {
         let mut background = BackgroundTraversal::start(
             traversal.root_index,
             &WalkOptions {
-                threads: 1,
                 count_hard_links: true,
-                apparent_size: true,
-                cross_filesystems: true,
-                ignore_dirs: std::collections::BTreeSet::default(),
-                ignore_patterns: None,
+                ..test_walk_options()
             },
             vec![dir.path().to_owned(), dir.path().to_owned()],
             None,
@@ -527,12 +942,9 @@ mod tests {
         let mut background = BackgroundTraversal::start(
             traversal.root_index,
             &WalkOptions {
-                threads: 1,
                 count_hard_links: true,
-                apparent_size: true,
                 cross_filesystems: false,
-                ignore_dirs: std::collections::BTreeSet::default(),
-                ignore_patterns: None,
+                ..test_walk_options()
             },
             vec![root.clone(), valid.clone()],
             None,
@@ -549,6 +961,414 @@ mod tests {
         assert_eq!(background.stats.io_errors, 1);
     }
 
+    #[test]
+    fn from_paths_builds_a_queryable_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested/large"), b"0123456789").unwrap();
+        std::fs::write(dir.path().join("small"), b"0").unwrap();
+
+        let (traversal, stats) = Traversal::from_paths(
+            &WalkOptions {
+                count_hard_links: true,
+                ..test_walk_options()
+            },
+            vec![dir.path().to_owned()],
+        );
+        assert_eq!(stats.io_errors, 0);
+        assert_eq!(stats.entries_traversed, 4);
+        assert!(traversal.cost.is_some(), "the traversal is complete");
+
+        let root = traversal
+            .find_by_path(dir.path())
+            .expect("root was traversed");
+        assert_eq!(traversal.path_of(root), dir.path());
+        let large = traversal
+            .find_by_path(dir.path().join("nested/large"))
+            .expect("nested file was traversed");
+        assert_eq!(traversal.tree[large].size, 10);
+        assert_eq!(traversal.find_by_path(dir.path().join("missing")), None);
+        assert_eq!(traversal.find_by_path(""), Some(traversal.root_index));
+
+        let names = traversal
+            .children_by_size(root)
+            .into_iter()
+            .map(|index| traversal.tree[index].name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [PathBuf::from("nested"), PathBuf::from("small")]);
+
+        let mut descendants = traversal
+            .descendants(root)
+            .map(|(index, path)| {
+                assert_eq!(traversal.find_by_path(&path), Some(index));
+                path.strip_prefix(dir.path()).unwrap().to_owned()
+            })
+            .collect::<Vec<_>>();
+        descendants.sort();
+        assert_eq!(
+            descendants,
+            [
+                PathBuf::from("nested"),
+                PathBuf::from("nested/large"),
+                PathBuf::from("small")
+            ]
+        );
+    }
+
+    #[test]
+    fn max_depth_summarizes_directories_at_the_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+        std::fs::write(dir.path().join("nested/deeper/large"), b"0123456789").unwrap();
+        std::fs::write(dir.path().join("nested/small"), b"0").unwrap();
+
+        let traverse = |max_depth| {
+            Traversal::from_paths(
+                &WalkOptions {
+                    threads: 2,
+                    max_depth,
+                    ..test_walk_options()
+                },
+                vec![dir.path().to_owned()],
+            )
+        };
+        let (full, full_stats) = traverse(None);
+        let (limited, limited_stats) = traverse(Some(1));
+
+        assert_eq!(limited_stats.io_errors, 0);
+        assert_eq!(
+            limited_stats.entries_traversed, full_stats.entries_traversed,
+            "summarized entries are still counted as traversed"
+        );
+        let nested = limited
+            .find_by_path(dir.path().join("nested"))
+            .expect("directories at the limit are part of the tree");
+        assert!(
+            limited.children_by_size(nested).is_empty(),
+            "but their contents are not"
+        );
+        assert_eq!(limited.find_by_path(dir.path().join("nested/small")), None);
+
+        for path in [dir.path().to_owned(), dir.path().join("nested")] {
+            let (full_entry, limited_entry) = (
+                &full.tree[full.find_by_path(&path).unwrap()],
+                &limited.tree[limited.find_by_path(&path).unwrap()],
+            );
+            assert_eq!(
+                limited_entry.size, full_entry.size,
+                "sizes of {path:?} are the same with and without a depth limit"
+            );
+            assert_eq!(limited_entry.entry_count, full_entry.entry_count);
+        }
+    }
+
+    #[test]
+    fn max_depth_keeps_sizes_with_ignore_patterns_and_followed_links() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+        std::fs::write(dir.path().join("nested/deeper/large"), [1; 10_000]).unwrap();
+        std::fs::write(
+            dir.path().join("nested/deeper/excluded.log"),
+            vec![2; 20_000],
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("nested/excluded.log"), vec![3; 30_000]).unwrap();
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("deeper", dir.path().join("nested/dir-link")).unwrap();
+            std::os::unix::fs::symlink("deeper/large", dir.path().join("nested/file-link"))
+                .unwrap();
+        }
+        let patterns = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(patterns.path(), "*.log\n").unwrap();
+
+        for follow_links in [false, true] {
+            let traverse = |max_depth| {
+                Traversal::from_paths(
+                    &WalkOptions {
+                        threads: 2,
+                        follow_links,
+                        max_depth,
+                        ignore_patterns: crate::IgnorePatterns::from_files(&[patterns
+                            .path()
+                            .to_owned()])
+                        .unwrap(),
+                        ..test_walk_options()
+                    },
+                    vec![dir.path().to_owned()],
+                )
+                .0
+            };
+            let (full, limited) = (traverse(None), traverse(Some(1)));
+            let nested = dir.path().join("nested");
+            let (full_entry, limited_entry) = (
+                &full.tree[full.find_by_path(&nested).unwrap()],
+                &limited.tree[limited.find_by_path(&nested).unwrap()],
+            );
+            assert!(
+                full_entry.size < 20_000,
+                "excluded files aren't counted, with follow-links {follow_links}"
+            );
+            assert_eq!(
+                limited_entry.size, full_entry.size,
+                "sizes are the same with and without a depth limit, with follow-links {follow_links}"
+            );
+        }
+    }
+
+    #[test]
+    fn both_sizes_are_recorded_and_can_be_swapped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+        std::fs::write(dir.path().join("nested/deeper/large"), [1; 10_000]).unwrap();
+        std::fs::write(dir.path().join("nested/small"), b"0").unwrap();
+        std::fs::File::create(dir.path().join("sparse"))
+            .unwrap()
+            .set_len(1024 * 1024)
+            .unwrap();
+
+        for max_depth in [None, Some(1)] {
+            let traverse = |apparent_size| {
+                Traversal::from_paths(
+                    &WalkOptions {
+                        apparent_size,
+                        max_depth,
+                        ..test_walk_options()
+                    },
+                    vec![dir.path().to_owned()],
+                )
+                .0
+            };
+            let (apparent, mut disk) = (traverse(true), traverse(false));
+            let sizes = |traversal: &Traversal| {
+                let mut sizes: Vec<_> = traversal
+                    .descendants(traversal.root_index)
+                    .map(|(index, path)| {
+                        let entry = &traversal.tree[index];
+                        (path, entry.size, u128::from(entry.alternate_size))
+                    })
+                    .collect();
+                sizes.sort();
+                sizes
+            };
+            let apparent_sizes = sizes(&apparent);
+            assert_eq!(
+                apparent_sizes,
+                sizes(&disk)
+                    .into_iter()
+                    .map(|(path, size, alternate)| (path, alternate, size))
+                    .collect::<Vec<_>>(),
+                "the alternate size is the size of the other mode, with max-depth {max_depth:?}"
+            );
+            disk.swap_sizes();
+            assert_eq!(sizes(&disk), apparent_sizes);
+        }
+    }
+
+    #[test]
+    fn usage_by_owner_accounts_for_all_entries_below_a_node() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+        std::fs::write(dir.path().join("nested/deeper/large"), b"0123456789").unwrap();
+        std::fs::write(dir.path().join("nested/small"), b"0").unwrap();
+
+        for (max_depth, collapse_files_below) in [(None, None), (Some(1), None), (None, Some(5))] {
+            let (traversal, _) = Traversal::from_paths(
+                &WalkOptions {
+                    max_depth,
+                    collapse_files_below,
+                    ..test_walk_options()
+                },
+                vec![dir.path().to_owned()],
+            );
+            let root = &traversal.tree[traversal.find_by_path(dir.path()).unwrap()];
+            let usage = traversal
+                .usage_by_owner(traversal.root_index)
+                .into_sorted_by_size();
+            assert_eq!(
+                usage.len(),
+                1,
+                "everything belongs to the same user, even collapsed small files"
+            );
+            assert_eq!(
+                usage[0].bytes, root.size,
+                "with max-depth {max_depth:?} and collapse-files-below {collapse_files_below:?}"
+            );
+            assert_eq!(Some(usage[0].entries), root.entry_count.get());
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::MetadataExt;
+                let uid = std::fs::metadata(dir.path()).unwrap().uid();
+                assert_eq!(usage[0].uid, Some(uid));
+            }
+        }
+    }
+
+    #[test]
+    fn small_files_are_collapsed_per_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("a"), b"0").unwrap();
+        std::fs::write(dir.path().join("b"), b"01").unwrap();
+        std::fs::write(dir.path().join("large"), [0; 100]).unwrap();
+        std::fs::write(dir.path().join("nested/c"), b"0").unwrap();
+
+        let traverse = |collapse_files_below| {
+            Traversal::from_paths(
+                &WalkOptions {
+                    collapse_files_below,
+                    ..test_walk_options()
+                },
+                vec![dir.path().to_owned()],
+            )
+            .0
+        };
+        let full = traverse(None);
+        let collapsed = traverse(Some(10));
+
+        let small_files_of = |path: &Path| {
+            let directory = collapsed.find_by_path(path).unwrap();
+            let small_files = collapsed
+                .children_by_size(directory)
+                .into_iter()
+                .filter(|child| collapsed.tree[*child].is_small_files)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                small_files.len(),
+                1,
+                "there is one small-files node per directory"
+            );
+            &collapsed.tree[small_files[0]]
+        };
+        let root_small_files = small_files_of(dir.path());
+        assert_eq!(root_small_files.size, 3);
+        assert_eq!(root_small_files.entry_count.get(), Some(2));
+        assert_eq!(small_files_of(&dir.path().join("nested")).size, 1);
+        assert!(collapsed.find_by_path(dir.path().join("large")).is_some());
+        assert_eq!(collapsed.find_by_path(dir.path().join("a")), None);
+
+        for path in [dir.path().to_owned(), dir.path().join("nested")] {
+            let (full_entry, collapsed_entry) = (
+                &full.tree[full.find_by_path(&path).unwrap()],
+                &collapsed.tree[collapsed.find_by_path(&path).unwrap()],
+            );
+            assert_eq!(collapsed_entry.size, full_entry.size);
+            assert_eq!(collapsed_entry.entry_count, full_entry.entry_count);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn following_links_counts_their_targets_once() {
+        use std::os::unix::fs::{MetadataExt, symlink};
+
+        let dir = tempfile::tempdir().unwrap();
+        let (root, outside) = (dir.path().join("root"), dir.path().join("outside"));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("data"), b"content").unwrap();
+        symlink(&outside, root.join("link")).unwrap();
+        symlink(&outside, root.join("other-link")).unwrap();
+        symlink(outside.join("data"), root.join("data-link")).unwrap();
+
+        let dir_size = |path: &Path| u128::from(std::fs::metadata(path).unwrap().size());
+        for count_hard_links in [false, true] {
+            let (traversal, stats) = Traversal::from_paths(
+                &WalkOptions {
+                    threads: 2,
+                    follow_links: true,
+                    count_hard_links,
+                    ..test_walk_options()
+                },
+                vec![root.clone()],
+            );
+            assert_eq!(stats.io_errors, 0);
+            assert_eq!(
+                traversal.tree[traversal.root_index].size,
+                dir_size(&root) + dir_size(&outside) + 7,
+                "the linked directory and file are counted only once, \
+                 with count-hard-links {count_hard_links}"
+            );
+            assert_eq!(
+                traversal
+                    .descendants(traversal.root_index)
+                    .filter(|(_, path)| path.ends_with("data"))
+                    .count(),
+                1,
+                "only one of the links to the directory is traversed"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn traversal_round_trips_through_serde() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("a/b")).unwrap();
+        std::fs::write(dir.path().join("a/b/file"), b"content").unwrap();
+        std::fs::write(dir.path().join("other"), b"more content").unwrap();
+        let (traversal, _) = Traversal::from_paths(
+            &WalkOptions {
+                count_hard_links: true,
+                ..test_walk_options()
+            },
+            vec![dir.path().to_owned()],
+        );
+
+        let json = serde_json::to_string(&traversal).unwrap();
+        let restored: Traversal = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.cost, traversal.cost);
+        assert_eq!(restored.tree.node_count(), traversal.tree.node_count());
+        assert_eq!(
+            restored.tree[restored.root_index],
+            traversal.tree[traversal.root_index]
+        );
+        let entries = |traversal: &Traversal| {
+            let mut entries = traversal
+                .descendants(traversal.root_index)
+                .map(|(index, path)| (path, traversal.tree[index].clone()))
+                .collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            entries
+        };
+        assert_eq!(entries(&restored), entries(&traversal));
+        assert_eq!(
+            serde_json::to_string(&restored).unwrap(),
+            json,
+            "serializing again yields the same representation"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn traversal_deserialization_validates_the_tree() {
+        let entry = serde_json::to_value(EntryData::default()).unwrap();
+        for nodes in [
+            serde_json::json!([]),
+            serde_json::json!([{ "parent": 0, "entry": entry }]),
+            serde_json::json!([{ "parent": null, "entry": entry }, { "parent": null, "entry": entry }]),
+            serde_json::json!([{ "parent": null, "entry": entry }, { "parent": 1, "entry": entry }]),
+        ] {
+            let json = serde_json::json!({ "nodes": nodes, "cost": null });
+            assert!(
+                serde_json::from_value::<Traversal>(json.clone()).is_err(),
+                "{json} is not a valid tree"
+            );
+        }
+
+        let mut owned_entry = entry.clone();
+        owned_entry["ownership"] = serde_json::json!(1);
+        let nodes = serde_json::json!([{ "parent": null, "entry": owned_entry }]);
+        let ownership = serde_json::json!({ "uid": 0, "gid": 0, "mode": 0o100_644 });
+        let json = serde_json::json!({ "nodes": nodes, "cost": null, "ownerships": [ownership] });
+        assert!(serde_json::from_value::<Traversal>(json).is_ok());
+        let json = serde_json::json!({ "nodes": nodes, "cost": null });
+        assert!(
+            serde_json::from_value::<Traversal>(json.clone()).is_err(),
+            "{json} refers to an ownership that doesn't exist"
+        );
+    }
+
     #[test]
     fn size_of_entry_data() {
         assert!(